@@ -0,0 +1,56 @@
+use crate::auth::Token;
+use crate::config::api_url;
+
+use super::super::request::{get_json, send};
+use super::super::utils::{parse_item, parse_items, parse_next_href};
+use crate::api::Comment;
+use std::sync::{Arc, Mutex};
+
+/// Pages of 200 fetched before the rest of a track's comments are left out.
+const MAX_COMMENT_PAGES: usize = 25;
+
+/// All comments on a track, ordered by their position in the track.
+pub async fn fetch_track_comments(
+    token: Arc<Mutex<Token>>,
+    track_urn: String,
+) -> anyhow::Result<Vec<Comment>> {
+    let mut comments: Vec<Comment> = Vec::new();
+    let mut next = Some(api_url(&format!(
+        "/tracks/{}/comments?limit=200&linked_partitioning=true",
+        track_urn
+    )));
+    let mut pages = 0;
+    while let Some(url) = next
+        && pages < MAX_COMMENT_PAGES
+    {
+        let resp = get_json(&token, &url).await?;
+        pages += 1;
+        next = parse_next_href(&resp).filter(|next| *next != url);
+        comments.extend(parse_items::<Comment>(&resp));
+    }
+
+    comments.sort_by_key(|c| c.timestamp_ms);
+    Ok(comments)
+}
+
+pub async fn post_comment(
+    token: Arc<Mutex<Token>>,
+    track_urn: String,
+    body: String,
+    timestamp_ms: u64,
+) -> anyhow::Result<Comment> {
+    let url = api_url(&format!("/tracks/{}/comments", track_urn));
+    let payload = serde_json::json!({
+        "comment": {
+            "body": body,
+            "timestamp": timestamp_ms,
+        }
+    });
+
+    let resp: serde_json::Value = send(&token, |client| client.post(&url).json(&payload))
+        .await?
+        .json()
+        .await?;
+
+    parse_item(&resp).ok_or_else(|| anyhow::anyhow!("unexpected post comment response"))
+}
@@ -1,101 +1,109 @@
-use reqwest;
+use crate::auth::Token;
+use crate::config::api_url;
 
-use crate::auth::{Token, try_refresh_token};
+use super::super::request::{get_json, send};
+use super::super::utils::{parse_items, parse_next_href};
+use crate::api::{Playlist, Track};
 use std::sync::{Arc, Mutex};
 
 pub async fn like_track(token: Arc<Mutex<Token>>, track_id: u64) -> anyhow::Result<()> {
-    let _ = try_refresh_token(&token);
-
-    let access_token = { token.lock().unwrap().access_token.clone() };
-    let url = format!("https://api.soundcloud.com/likes/tracks/{}", track_id);
-
-    reqwest::Client::new()
-        .post(url)
-        .bearer_auth(access_token)
-        .send()
-        .await?
-        .error_for_status()?;
+    let url = api_url(&format!("/likes/tracks/{}", track_id));
+    send(&token, |client| client.post(&url)).await?;
 
     Ok(())
 }
 
 pub async fn unlike_track(token: Arc<Mutex<Token>>, track_id: u64) -> anyhow::Result<()> {
-    let _ = try_refresh_token(&token);
-
-    let access_token = { token.lock().unwrap().access_token.clone() };
-    let url = format!("https://api.soundcloud.com/likes/tracks/{}", track_id);
-
-    reqwest::Client::new()
-        .delete(url)
-        .bearer_auth(access_token)
-        .send()
-        .await?
-        .error_for_status()?;
+    let url = api_url(&format!("/likes/tracks/{}", track_id));
+    send(&token, |client| client.delete(&url)).await?;
 
     Ok(())
 }
 
 pub async fn like_playlist(token: Arc<Mutex<Token>>, playlist_id: u64) -> anyhow::Result<()> {
-    let _ = try_refresh_token(&token);
+    let url = api_url(&format!("/likes/playlists/{}", playlist_id));
+    send(&token, |client| client.post(&url)).await?;
 
-    let access_token = { token.lock().unwrap().access_token.clone() };
-    let url = format!("https://api.soundcloud.com/likes/playlists/{}", playlist_id);
+    Ok(())
+}
 
-    reqwest::Client::new()
-        .post(url)
-        .bearer_auth(access_token)
-        .send()
-        .await?
-        .error_for_status()?;
+pub async fn unlike_playlist(token: Arc<Mutex<Token>>, playlist_id: u64) -> anyhow::Result<()> {
+    let url = api_url(&format!("/likes/playlists/{}", playlist_id));
+    send(&token, |client| client.delete(&url)).await?;
 
     Ok(())
 }
 
-pub async fn unlike_playlist(token: Arc<Mutex<Token>>, playlist_id: u64) -> anyhow::Result<()> {
-    let _ = try_refresh_token(&token);
+pub async fn follow_user(token: Arc<Mutex<Token>>, user_id: u64) -> anyhow::Result<()> {
+    let url = api_url(&format!("/me/followings/{}", user_id));
+    send(&token, |client| client.put(&url)).await?;
 
-    let access_token = { token.lock().unwrap().access_token.clone() };
-    let url = format!("https://api.soundcloud.com/likes/playlists/{}", playlist_id);
+    Ok(())
+}
 
-    reqwest::Client::new()
-        .delete(url)
-        .bearer_auth(access_token)
-        .send()
-        .await?
-        .error_for_status()?;
+pub async fn unfollow_user(token: Arc<Mutex<Token>>, user_id: u64) -> anyhow::Result<()> {
+    let url = api_url(&format!("/me/followings/{}", user_id));
+    send(&token, |client| client.delete(&url)).await?;
 
     Ok(())
 }
 
-pub async fn follow_user(token: Arc<Mutex<Token>>, user_id: u64) -> anyhow::Result<()> {
-    let _ = try_refresh_token(&token);
+pub async fn repost_track(token: Arc<Mutex<Token>>, track_id: u64) -> anyhow::Result<()> {
+    let url = api_url(&format!("/reposts/tracks/{}", track_id));
+    send(&token, |client| client.post(&url)).await?;
 
-    let access_token = { token.lock().unwrap().access_token.clone() };
-    let url = format!("https://api.soundcloud.com/me/followings/{}", user_id);
+    Ok(())
+}
 
-    reqwest::Client::new()
-        .put(url)
-        .bearer_auth(access_token)
-        .send()
-        .await?
-        .error_for_status()?;
+pub async fn unrepost_track(token: Arc<Mutex<Token>>, track_id: u64) -> anyhow::Result<()> {
+    let url = api_url(&format!("/reposts/tracks/{}", track_id));
+    send(&token, |client| client.delete(&url)).await?;
 
     Ok(())
 }
 
-pub async fn unfollow_user(token: Arc<Mutex<Token>>, user_id: u64) -> anyhow::Result<()> {
-    let _ = try_refresh_token(&token);
+pub async fn repost_playlist(token: Arc<Mutex<Token>>, playlist_id: u64) -> anyhow::Result<()> {
+    let url = api_url(&format!("/reposts/playlists/{}", playlist_id));
+    send(&token, |client| client.post(&url)).await?;
 
-    let access_token = { token.lock().unwrap().access_token.clone() };
-    let url = format!("https://api.soundcloud.com/me/followings/{}", user_id);
+    Ok(())
+}
 
-    reqwest::Client::new()
-        .delete(url)
-        .bearer_auth(access_token)
-        .send()
-        .await?
-        .error_for_status()?;
+pub async fn unrepost_playlist(token: Arc<Mutex<Token>>, playlist_id: u64) -> anyhow::Result<()> {
+    let url = api_url(&format!("/reposts/playlists/{}", playlist_id));
+    send(&token, |client| client.delete(&url)).await?;
 
     Ok(())
 }
 
+/// Urns of the tracks and `tracks_uri`s of the playlists the user has reposted,
+/// used to seed the "reposted" markers.
+pub async fn fetch_my_reposts(
+    token: Arc<Mutex<Token>>,
+) -> anyhow::Result<(Vec<String>, Vec<String>)> {
+    let mut track_urns = Vec::new();
+    let mut next = Some(api_url("/me/reposts/tracks?limit=200&linked_partitioning=true"));
+    while let Some(url) = next {
+        let resp = get_json(&token, &url).await?;
+        next = parse_next_href(&resp);
+        track_urns.extend(
+            parse_items::<Track>(&resp)
+                .into_iter()
+                .map(|t| t.track_urn),
+        );
+    }
+
+    let mut playlist_uris = Vec::new();
+    let mut next = Some(api_url("/me/reposts/playlists?limit=200&linked_partitioning=true"));
+    while let Some(url) = next {
+        let resp = get_json(&token, &url).await?;
+        next = parse_next_href(&resp);
+        playlist_uris.extend(
+            parse_items::<Playlist>(&resp)
+                .into_iter()
+                .map(|p| p.tracks_uri),
+        );
+    }
+
+    Ok((track_urns, playlist_uris))
+}
@@ -0,0 +1,11 @@
+use super::super::cursor::next_page;
+use super::super::utils::parse_items;
+use crate::api::{API, Activity};
+
+impl API {
+    /// Next page of posts and reposts from followed users, empty once the stream is exhausted.
+    pub async fn get_feed(&self) -> anyhow::Result<Vec<Activity>> {
+        let page = next_page(&self.token, &self.feed).await?;
+        Ok(page.map(|resp| parse_items(&resp)).unwrap_or_default())
+    }
+}
@@ -1,6 +1,10 @@
 pub(crate) mod albums;
+pub(crate) mod comments;
 pub(crate) mod engagement;
+mod feed;
 pub(crate) mod following;
 mod likes;
 pub(crate) mod playlists;
+pub(crate) mod radio;
 pub(crate) mod search;
+pub(crate) mod users;
@@ -1,92 +1,36 @@
-use chrono::{DateTime, FixedOffset, Utc};
-use reqwest::blocking::Client;
-use reqwest;
+use crate::auth::Token;
+use crate::config::api_url;
 
-use crate::auth::{Token, try_refresh_token};
-
-use super::super::utils::{format_duration, format_playback_count, parse_next_href, parse_str, parse_u64};
+use super::super::cursor::peek_page;
+use super::super::request::{get_json, send};
+use super::super::utils::{collection_items, parse_item, parse_items, parse_next_href};
 use crate::api::{API, Playlist, Track};
 use std::sync::{Arc, Mutex};
 
 impl API {
-    pub fn get_playlists(&mut self) -> anyhow::Result<Vec<Playlist>> {
-        let _ = try_refresh_token(&self.token);
-
-        let token_guard = self.token.lock().unwrap();
-
-        let should_fetch_my =
-            !self.my_first_playlist_page_fetched || self.my_playlists_next_href.is_some();
-        let should_fetch_others =
-            !self.others_first_playlist_page_fetched || self.others_playlists_next_href.is_some();
-
-        if !should_fetch_my && !should_fetch_others {
-            return Ok(Vec::new());
-        }
-
-        let urls = vec![
-            self.my_playlists_next_href.clone().unwrap_or_else(|| {
-                "https://api.soundcloud.com/me/playlists?linked_partitioning=true&limit=40&show_tracks=false".to_string()
-            }),
-            self.others_playlists_next_href.clone().unwrap_or_else(|| {
-                "https://api.soundcloud.com/me/likes/playlists?limit=40&linked_partitioning=true"
-                    .to_string()
-            }),
-        ];
+    /// Next page of the user's own playlists and of the ones they liked. Neither
+    /// cursor moves unless both pages loaded, so a retry can't skip a page.
+    pub async fn get_playlists(&self) -> anyhow::Result<Vec<Playlist>> {
+        let (mine, liked) = futures::try_join!(
+            peek_page(&self.token, &self.my_playlists),
+            peek_page(&self.token, &self.liked_playlists),
+        )?;
+        let mine = mine.map(|page| page.advance(&self.my_playlists));
+        let liked = liked.map(|page| page.advance(&self.liked_playlists));
 
         let mut playlists = Vec::new();
-
-        for (i, url) in urls.iter().enumerate() {
-            if (i == 0 && !should_fetch_my) || (i == 1 && !should_fetch_others) {
+        for (resp, is_owned) in [(mine, true), (liked, false)] {
+            let Some(resp) = resp else {
                 continue;
-            }
-            let resp: serde_json::Value = Client::new()
-                .get(url)
-                .bearer_auth(&token_guard.access_token)
-                .send()?
-                .error_for_status()?
-                .json()?;
-
-            let next_href = parse_next_href(&resp);
-            if i == 0 {
-                self.my_playlists_next_href = next_href;
-                self.my_first_playlist_page_fetched = true;
-            } else {
-                self.others_playlists_next_href = next_href;
-                self.others_first_playlist_page_fetched = true;
-            }
-
-            if let Some(collection) = resp.get("collection").and_then(|v| v.as_array()) {
-                for playlist in collection {
-                    if i == 1
-                        && playlist
-                            .get("playlist_type")
-                            .and_then(|v| v.as_str())
-                            .unwrap_or("")
-                            != "PLAYLIST"
-                    {
-                        continue;
-                    }
-
-                    let title = parse_str(&playlist, "title");
-                    let track_count = parse_u64(&playlist, "track_count").to_string();
-                    let duration = format_duration(parse_u64(&playlist, "duration"));
-                    let created_at = DateTime::parse_from_str(
-                        &parse_str(&playlist, "created_at"),
-                        "%Y/%m/%d %H:%M:%S %z",
-                    )
-                    .unwrap_or_else(|_| {
-                        Utc::now().with_timezone(&FixedOffset::east_opt(0).unwrap())
-                    });
-                    let tracks_uri = parse_str(&playlist, "tracks_uri");
-
-                    playlists.push(Playlist {
-                        title,
-                        track_count,
-                        duration,
-                        created_at,
-                        tracks_uri,
-                        is_owned: i == 0,
-                    });
+            };
+            for item in collection_items(&resp) {
+                let playlist_type = item.get("playlist_type").and_then(|v| v.as_str());
+                if !is_owned && playlist_type != Some("PLAYLIST") {
+                    continue;
+                }
+                if let Some(mut playlist) = parse_item::<Playlist>(item) {
+                    playlist.is_owned = is_owned;
+                    playlists.push(playlist);
                 }
             }
         }
@@ -94,98 +38,44 @@ impl API {
         playlists.sort_by(|a, b| b.created_at.cmp(&a.created_at));
         Ok(playlists)
     }
-
-    pub fn get_playlist_tracks(&mut self, tracks_uri: &str) -> anyhow::Result<Vec<Track>> {
-        let _ = try_refresh_token(&self.token);
-
-        let token_guard = self.token.lock().unwrap();
-
-        let mut url = if tracks_uri.starts_with("http") {
-            tracks_uri.to_string()
-        } else {
-            format!("https://api.soundcloud.com{}", tracks_uri)
-        };
-        if url.contains('?') {
-            if !url.contains("linked_partitioning") {
-                url.push_str("&linked_partitioning=true");
-            }
-            if !url.contains("limit=") {
-                url.push_str("&limit=200");
-            }
-        } else {
-            url.push_str("?linked_partitioning=true&limit=200");
-        }
-
-        let resp: serde_json::Value = Client::new()
-            .get(&url)
-            .bearer_auth(&token_guard.access_token)
-            .send()?
-            .error_for_status()?
-            .json()?;
-
-        drop(token_guard);
-
-        let items = if let Some(collection) = resp.get("collection").and_then(|v| v.as_array()) {
-            collection.clone()
-        } else if let Some(array) = resp.as_array() {
-            array.clone()
-        } else {
-            Vec::new()
-        };
-
-        let mut tracks = Vec::new();
-        for track in items {
-            let title = parse_str(&track, "title");
-
-            let artists = parse_str(&track, "metadata_artist");
-            let artists = if !artists.is_empty() {
-                artists
-            } else {
-                parse_str(
-                    track.get("user").unwrap_or(&serde_json::Value::Null),
-                    "username",
-                )
-            };
-            let duration = format_duration(parse_u64(&track, "duration"));
-            let duration_ms = parse_u64(&track, "duration");
-
-            let playback_count = parse_u64(&track, "playback_count");
-            let playback_count = format_playback_count(playback_count);
-
-            let artwork_url = parse_str(&track, "artwork_url");
-            let stream_url = parse_str(&track, "stream_url");
-            let access = parse_str(&track, "access");
-            let track_urn = parse_str(&track, "urn");
-
-            tracks.push(Track {
-                title,
-                artists,
-                duration,
-                duration_ms,
-                playback_count,
-                artwork_url,
-                stream_url,
-                access,
-                track_urn,
-            });
-        }
-
-        Ok(tracks)
-    }
 }
 
+/// Every track of a playlist, following `next_href` through all of its pages.
 pub async fn fetch_playlist_tracks(
     token: Arc<Mutex<Token>>,
     tracks_uri: String,
 ) -> anyhow::Result<Vec<Track>> {
-    let _ = try_refresh_token(&token);
+    let pages = fetch_playlist_pages(&token, &tracks_uri).await?;
+    Ok(pages.iter().flat_map(parse_items::<Track>).collect())
+}
 
-    let access_token = { token.lock().unwrap().access_token.clone() };
+/// Urns of every track of a playlist, for building a replacement track list.
+/// Fails if any item can't be read, PUTting a list without it would delete it.
+async fn fetch_playlist_track_urns(
+    token: &Arc<Mutex<Token>>,
+    tracks_uri: &str,
+) -> anyhow::Result<Vec<String>> {
+    let pages = fetch_playlist_pages(token, tracks_uri).await?;
+    pages
+        .iter()
+        .flat_map(collection_items)
+        .map(|item| {
+            parse_item::<Track>(item)
+                .map(|track| track.track_urn)
+                .filter(|urn| !urn.is_empty())
+                .ok_or_else(|| anyhow::anyhow!("playlist has a track that couldn't be read"))
+        })
+        .collect()
+}
 
+async fn fetch_playlist_pages(
+    token: &Arc<Mutex<Token>>,
+    tracks_uri: &str,
+) -> anyhow::Result<Vec<serde_json::Value>> {
     let mut url = if tracks_uri.starts_with("http") {
-        tracks_uri
+        tracks_uri.to_string()
     } else {
-        format!("https://api.soundcloud.com{}", tracks_uri)
+        api_url(tracks_uri)
     };
     if url.contains('?') {
         if !url.contains("linked_partitioning") {
@@ -201,59 +91,127 @@ pub async fn fetch_playlist_tracks(
         url.push_str("?linked_partitioning=true&limit=200&access=playable,preview,blocked");
     }
 
-    let resp: serde_json::Value = reqwest::Client::new()
-        .get(&url)
-        .bearer_auth(access_token)
-        .send()
+    let mut pages = Vec::new();
+    let mut next = Some(url);
+    while let Some(url) = next {
+        let resp = get_json(token, &url).await?;
+        next = parse_next_href(&resp).filter(|href| *href != url);
+        pages.push(resp);
+    }
+    Ok(pages)
+}
+
+fn playlist_tracks_body(track_urns: &[String]) -> serde_json::Value {
+    serde_json::Value::Array(
+        track_urns
+            .iter()
+            .map(|urn| serde_json::json!({ "urn": urn }))
+            .collect(),
+    )
+}
+
+pub async fn create_playlist(
+    token: Arc<Mutex<Token>>,
+    title: String,
+    sharing: String,
+    track_urns: Vec<String>,
+) -> anyhow::Result<Playlist> {
+    let body = serde_json::json!({
+        "playlist": {
+            "title": title,
+            "sharing": sharing,
+            "tracks": playlist_tracks_body(&track_urns),
+        }
+    });
+
+    let url = api_url("/playlists");
+    let resp: serde_json::Value = send(&token, |client| client.post(&url).json(&body))
         .await?
-        .error_for_status()?
         .json()
         .await?;
 
-    let items = if let Some(collection) = resp.get("collection").and_then(|v| v.as_array()) {
-        collection.clone()
-    } else if let Some(array) = resp.as_array() {
-        array.clone()
-    } else {
-        Vec::new()
-    };
+    let mut playlist: Playlist = parse_item(&resp)
+        .ok_or_else(|| anyhow::anyhow!("unexpected create playlist response"))?;
+    playlist.is_owned = true;
+    Ok(playlist)
+}
 
-    let mut tracks = Vec::new();
-    for track in items {
-        let title = parse_str(&track, "title");
+/// Changes the title and/or sharing ("public" / "private") of an owned playlist.
+pub async fn update_playlist(
+    token: Arc<Mutex<Token>>,
+    playlist_id: u64,
+    title: Option<String>,
+    sharing: Option<String>,
+) -> anyhow::Result<()> {
+    let mut fields = serde_json::Map::new();
+    if let Some(title) = title {
+        fields.insert("title".to_string(), title.into());
+    }
+    if let Some(sharing) = sharing {
+        fields.insert("sharing".to_string(), sharing.into());
+    }
 
-        let artists = parse_str(&track, "metadata_artist");
-        let artists = if !artists.is_empty() {
-            artists
-        } else {
-            parse_str(
-                track.get("user").unwrap_or(&serde_json::Value::Null),
-                "username",
-            )
-        };
-        let duration = format_duration(parse_u64(&track, "duration"));
-        let duration_ms = parse_u64(&track, "duration");
+    let body = serde_json::json!({ "playlist": fields });
+    let url = api_url(&format!("/playlists/{}", playlist_id));
+    send(&token, |client| client.put(&url).json(&body)).await?;
 
-        let playback_count = parse_u64(&track, "playback_count");
-        let playback_count = format_playback_count(playback_count);
+    Ok(())
+}
 
-        let artwork_url = parse_str(&track, "artwork_url");
-        let stream_url = parse_str(&track, "stream_url");
-        let access = parse_str(&track, "access");
-        let track_urn = parse_str(&track, "urn");
+/// A change to a playlist's track list. The API only takes whole lists, so the edit
+/// is applied to the full list fetched right before it's PUT back.
+#[derive(Clone)]
+pub enum TrackEdit {
+    Add(String),
+    Remove(String),
+    /// Swaps the track with its neighbour above, or below.
+    Move { track_urn: String, up: bool },
+}
 
-        tracks.push(Track {
-            title,
-            artists,
-            duration,
-            duration_ms,
-            playback_count,
-            artwork_url,
-            stream_url,
-            access,
-            track_urn,
-        });
+impl TrackEdit {
+    fn apply(&self, track_urns: &mut Vec<String>) {
+        match self {
+            TrackEdit::Add(urn) => {
+                if !track_urns.contains(urn) {
+                    track_urns.push(urn.clone());
+                }
+            }
+            TrackEdit::Remove(urn) => track_urns.retain(|u| u != urn),
+            TrackEdit::Move { track_urn, up } => {
+                let Some(idx) = track_urns.iter().position(|u| u == track_urn) else {
+                    return;
+                };
+                let target = if *up { idx.checked_sub(1) } else { Some(idx + 1) };
+                if let Some(target) = target.filter(|&t| t < track_urns.len()) {
+                    track_urns.swap(idx, target);
+                }
+            }
+        }
     }
+}
+
+/// Loads every track of the playlist, applies `edit` and replaces the track list.
+pub async fn edit_playlist_tracks(
+    token: Arc<Mutex<Token>>,
+    tracks_uri: String,
+    playlist_id: u64,
+    edit: TrackEdit,
+) -> anyhow::Result<()> {
+    let mut track_urns = fetch_playlist_track_urns(&token, &tracks_uri).await?;
+    edit.apply(&mut track_urns);
+
+    let body = serde_json::json!({
+        "playlist": { "tracks": playlist_tracks_body(&track_urns) }
+    });
+    let url = api_url(&format!("/playlists/{}", playlist_id));
+    send(&token, |client| client.put(&url).json(&body)).await?;
+
+    Ok(())
+}
+
+pub async fn delete_playlist(token: Arc<Mutex<Token>>, playlist_id: u64) -> anyhow::Result<()> {
+    let url = api_url(&format!("/playlists/{}", playlist_id));
+    send(&token, |client| client.delete(&url)).await?;
 
-    Ok(tracks)
+    Ok(())
 }
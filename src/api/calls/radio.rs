@@ -0,0 +1,44 @@
+use crate::auth::Token;
+use crate::config::api_url;
+
+use super::following::fetch_following_tracks;
+use super::super::request::get_json;
+use super::super::utils::parse_items;
+use crate::api::Track;
+use std::cmp::Reverse;
+use std::sync::{Arc, Mutex};
+
+/// How many of the artist's own tracks lead an artist radio.
+const ARTIST_RADIO_OWN_TRACKS: usize = 3;
+
+pub async fn fetch_related_tracks(
+    token: Arc<Mutex<Token>>,
+    track_urn: String,
+) -> anyhow::Result<Vec<Track>> {
+    let url = api_url(&format!("/tracks/{}/related?limit=50", track_urn));
+
+    let resp = get_json(&token, &url).await?;
+
+    Ok(parse_items(&resp))
+}
+
+/// The artist's most played tracks followed by tracks related to the top one.
+pub async fn fetch_artist_radio(
+    token: Arc<Mutex<Token>>,
+    user_urn: String,
+) -> anyhow::Result<Vec<Track>> {
+    let mut own: Vec<Track> = fetch_following_tracks(Arc::clone(&token), user_urn)
+        .await?
+        .into_iter()
+        .filter(|t| t.is_playable())
+        .collect();
+    own.sort_by_key(|t| Reverse(t.playback_count));
+    own.truncate(ARTIST_RADIO_OWN_TRACKS);
+
+    let Some(seed) = own.first() else {
+        return Ok(Vec::new());
+    };
+    let related = fetch_related_tracks(token, seed.track_urn.clone()).await?;
+    own.extend(related);
+    Ok(own)
+}
@@ -0,0 +1,55 @@
+use crate::auth::Token;
+use crate::config::api_url;
+
+use super::super::request::get_json;
+use super::super::utils::{collection_items, parse_item, parse_items};
+use crate::api::{Album, Artist, Playlist, Track};
+use std::sync::{Arc, Mutex};
+
+fn build_user_url(user_urn: &str, suffix: &str) -> String {
+    let encoded_urn = user_urn.replace(':', "%3A");
+    if suffix.is_empty() {
+        api_url(&format!("/users/{}", encoded_urn))
+    } else {
+        api_url(&format!(
+            "/users/{}/{}?linked_partitioning=true&limit=200&access=playable,preview,blocked",
+            encoded_urn, suffix
+        ))
+    }
+}
+
+pub async fn fetch_user(token: Arc<Mutex<Token>>, user_urn: String) -> anyhow::Result<Artist> {
+    let resp = get_json(&token, &build_user_url(&user_urn, "")).await?;
+    parse_item(&resp).ok_or_else(|| anyhow::anyhow!("unexpected user response"))
+}
+
+/// The user's playlists and albums, which SoundCloud serves from the same endpoint.
+pub async fn fetch_user_playlists(
+    token: Arc<Mutex<Token>>,
+    user_urn: String,
+) -> anyhow::Result<(Vec<Playlist>, Vec<Album>)> {
+    let resp = get_json(&token, &build_user_url(&user_urn, "playlists")).await?;
+
+    let mut playlists = Vec::new();
+    let mut albums = Vec::new();
+    for item in collection_items(&resp) {
+        let is_album = item
+            .get("playlist_type")
+            .and_then(|v| v.as_str())
+            .is_some_and(|t| t.eq_ignore_ascii_case("album"));
+        if is_album {
+            albums.extend(parse_item::<Album>(item));
+        } else {
+            playlists.extend(parse_item::<Playlist>(item));
+        }
+    }
+    Ok((playlists, albums))
+}
+
+pub async fn fetch_user_reposts(
+    token: Arc<Mutex<Token>>,
+    user_urn: String,
+) -> anyhow::Result<Vec<Track>> {
+    let resp = get_json(&token, &build_user_url(&user_urn, "reposts/tracks")).await?;
+    Ok(parse_items(&resp))
+}
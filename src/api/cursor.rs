@@ -0,0 +1,152 @@
+use std::sync::{Arc, Mutex};
+
+use crate::auth::Token;
+
+use super::request::get_json;
+use super::utils::parse_next_href;
+
+/// Position in one paginated collection. Clones share it, so a load running
+/// on the runtime moves the cursor the view reads from.
+#[derive(Clone)]
+pub struct Cursor {
+    first_url: Arc<str>,
+    state: Arc<Mutex<CursorState>>,
+}
+
+struct CursorState {
+    next_href: Option<String>,
+    started: bool,
+}
+
+impl Cursor {
+    pub fn new(first_url: String) -> Self {
+        Self {
+            first_url: first_url.into(),
+            state: Arc::new(Mutex::new(CursorState {
+                next_href: None,
+                started: false,
+            })),
+        }
+    }
+
+    /// Url of the next page, `None` once the last one has loaded.
+    pub(crate) fn next_url(&self) -> Option<String> {
+        self.next_url_locked(&self.state.lock().unwrap())
+    }
+
+    /// Moves past the page at `url`, unless another load already did.
+    pub(crate) fn advance(&self, url: &str, next_href: Option<String>) {
+        let mut state = self.state.lock().unwrap();
+        if self.next_url_locked(&state).as_deref() == Some(url) {
+            state.started = true;
+            state.next_href = next_href;
+        }
+    }
+
+    fn next_url_locked(&self, state: &CursorState) -> Option<String> {
+        if !state.started {
+            Some(self.first_url.to_string())
+        } else {
+            state.next_href.clone()
+        }
+    }
+
+    /// Back to the first page.
+    pub fn reset(&self) {
+        let mut state = self.state.lock().unwrap();
+        state.started = false;
+        state.next_href = None;
+    }
+
+    pub fn is_exhausted(&self) -> bool {
+        self.next_url().is_none()
+    }
+}
+
+/// Loads the page `cursor` is at and moves it on, `None` once it's exhausted.
+pub(crate) async fn next_page(
+    token: &Arc<Mutex<Token>>,
+    cursor: &Cursor,
+) -> anyhow::Result<Option<serde_json::Value>> {
+    let Some(page) = peek_page(token, cursor).await? else {
+        return Ok(None);
+    };
+    Ok(Some(page.advance(cursor)))
+}
+
+/// A loaded page the cursor hasn't moved past yet.
+pub(crate) struct Page {
+    url: String,
+    resp: serde_json::Value,
+}
+
+impl Page {
+    /// Moves `cursor` past this page and hands back its response.
+    pub(crate) fn advance(self, cursor: &Cursor) -> serde_json::Value {
+        cursor.advance(&self.url, parse_next_href(&self.resp));
+        self.resp
+    }
+}
+
+/// Loads the page `cursor` is at without moving it, for callers that load
+/// several collections together and only move on once all of them arrived.
+pub(crate) async fn peek_page(
+    token: &Arc<Mutex<Token>>,
+    cursor: &Cursor,
+) -> anyhow::Result<Option<Page>> {
+    let Some(url) = cursor.next_url() else {
+        return Ok(None);
+    };
+    let resp = get_json(token, &url).await?;
+    Ok(Some(Page { url, resp }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FIRST: &str = "https://api/me/playlists?limit=2";
+    const SECOND: &str = "https://api/me/playlists?limit=2&offset=2";
+
+    #[test]
+    fn starts_at_the_first_page() {
+        let cursor = Cursor::new(FIRST.to_string());
+        assert_eq!(cursor.next_url().as_deref(), Some(FIRST));
+        assert!(!cursor.is_exhausted());
+    }
+
+    #[test]
+    fn advances_to_next_href_until_there_is_none() {
+        let cursor = Cursor::new(FIRST.to_string());
+        cursor.advance(FIRST, Some(SECOND.to_string()));
+        assert_eq!(cursor.next_url().as_deref(), Some(SECOND));
+        cursor.advance(SECOND, None);
+        assert_eq!(cursor.next_url(), None);
+        assert!(cursor.is_exhausted());
+    }
+
+    #[test]
+    fn ignores_a_page_another_load_already_moved_past() {
+        let cursor = Cursor::new(FIRST.to_string());
+        cursor.advance(FIRST, Some(SECOND.to_string()));
+        // a second load of the first page finishing late must not rewind or skip
+        cursor.advance(FIRST, Some("https://api/elsewhere".to_string()));
+        assert_eq!(cursor.next_url().as_deref(), Some(SECOND));
+    }
+
+    #[test]
+    fn clones_share_the_position() {
+        let cursor = Cursor::new(FIRST.to_string());
+        let clone = cursor.clone();
+        clone.advance(FIRST, Some(SECOND.to_string()));
+        assert_eq!(cursor.next_url().as_deref(), Some(SECOND));
+    }
+
+    #[test]
+    fn reset_goes_back_to_the_first_page() {
+        let cursor = Cursor::new(FIRST.to_string());
+        cursor.advance(FIRST, None);
+        cursor.reset();
+        assert_eq!(cursor.next_url().as_deref(), Some(FIRST));
+    }
+}
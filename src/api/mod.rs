@@ -1,54 +1,92 @@
 mod utils;
 mod models;
 mod calls;
+mod cursor;
+mod request;
 
 use std::sync::{Arc, Mutex};
 
 use crate::auth::Token;
+use crate::config::api_url;
 
 pub use calls::albums::fetch_album_tracks;
+pub use calls::comments::{fetch_track_comments, post_comment};
 pub use calls::engagement::{
-    follow_user, like_playlist, like_track, unfollow_user, unlike_playlist, unlike_track,
+    fetch_my_reposts, follow_user, like_playlist, like_track, repost_playlist, repost_track,
+    unfollow_user, unlike_playlist, unlike_track, unrepost_playlist, unrepost_track,
 };
 pub use calls::following::{fetch_following_liked_tracks, fetch_following_tracks};
-pub use calls::playlists::fetch_playlist_tracks;
+pub use calls::playlists::{
+    TrackEdit, create_playlist, delete_playlist, edit_playlist_tracks, fetch_playlist_tracks,
+    update_playlist,
+};
+pub use calls::radio::{fetch_artist_radio, fetch_related_tracks};
 pub use calls::search::{
     fetch_search_albums, fetch_search_people, fetch_search_playlists, fetch_search_tracks,
 };
-pub use models::{Album, Artist, Playlist, Track};
+pub use calls::users::{fetch_user, fetch_user_playlists, fetch_user_reposts};
+pub use cursor::Cursor;
+pub use request::ApiError;
+pub use models::{Activity, ActivityOrigin, Album, Artist, Comment, Playlist, Track};
 
+/// Handle on the user's library. Clones share the token and the cursors, so
+/// each collection can load on the runtime independently of the others.
+#[derive(Clone)]
 pub struct API {
     token: Arc<Mutex<Token>>,
-    liked_tracks_next_href: Option<String>,
-    first_liked_tracks_page_fetched: bool,
-    my_playlists_next_href: Option<String>,
-    my_first_playlist_page_fetched: bool,
-    others_playlists_next_href: Option<String>,
-    others_first_playlist_page_fetched: bool,
-    albums_next_href: Option<String>,
-    first_albums_page_fetched: bool,
-    following_next_href: Option<String>,
-    first_following_page_fetched: bool,
+    pub likes: Cursor,
+    pub my_playlists: Cursor,
+    pub liked_playlists: Cursor,
+    pub albums: Cursor,
+    pub following: Cursor,
+    pub feed: Cursor,
 }
 
 impl API {
     pub fn init(token: Arc<Mutex<Token>>) -> Self {
         Self {
             token,
-            liked_tracks_next_href: None,
-            first_liked_tracks_page_fetched: false,
-            my_playlists_next_href: None,
-            my_first_playlist_page_fetched: false,
-            others_playlists_next_href: None,
-            others_first_playlist_page_fetched: false,
-            albums_next_href: None,
-            first_albums_page_fetched: false,
-            following_next_href: None,
-            first_following_page_fetched: false,
+            likes: Cursor::new(api_url(
+                "/me/likes/tracks?limit=40&access=playable,preview,blocked&linked_partitioning=true",
+            )),
+            my_playlists: Cursor::new(api_url(
+                "/me/playlists?linked_partitioning=true&limit=40&show_tracks=false",
+            )),
+            liked_playlists: Cursor::new(api_url(
+                "/me/likes/playlists?limit=40&linked_partitioning=true",
+            )),
+            albums: Cursor::new(api_url("/me/likes/playlists?limit=40&linked_partitioning=true")),
+            following: Cursor::new(api_url("/me/followings?limit=40&linked_partitioning=true")),
+            feed: Cursor::new(api_url(
+                "/me/activities?limit=40&access=playable,preview,blocked&linked_partitioning=true",
+            )),
         }
     }
 
     pub fn token_clone(&self) -> Arc<Mutex<Token>> {
         Arc::clone(&self.token)
     }
+
+    /// Moves the client onto another token handle, keeping its paging state.
+    pub fn set_token(&mut self, token: Arc<Mutex<Token>>) {
+        self.token = token;
+    }
+
+    /// Moves every library cursor back to its first page.
+    pub fn rewind(&self) {
+        for cursor in [
+            &self.likes,
+            &self.my_playlists,
+            &self.liked_playlists,
+            &self.albums,
+            &self.following,
+            &self.feed,
+        ] {
+            cursor.reset();
+        }
+    }
+
+    pub fn playlists_exhausted(&self) -> bool {
+        self.my_playlists.is_exhausted() && self.liked_playlists.is_exhausted()
+    }
 }
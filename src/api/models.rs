@@ -1,15 +1,60 @@
 use chrono::{DateTime, FixedOffset};
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone)]
+// Models mirror the SoundCloud JSON objects and keep raw values, formatting is
+// left to the render layer. Nulls are stripped before deserializing (see
+// `utils::parse_items`) so every field falls back to its default, and fields of
+// an unexpected type are coerced or defaulted (see `lenient`) rather than
+// failing the whole item.
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
 pub struct Track {
+    #[serde(with = "lenient")]
     pub title: String,
-    pub artists: String,
-    pub duration: String,
+    #[serde(with = "lenient")]
+    pub metadata_artist: String,
+    #[serde(with = "lenient")]
+    pub user: Artist,
+    #[serde(rename = "duration", with = "lenient")]
     pub duration_ms: u64,
-    pub playback_count: String,
+    #[serde(with = "lenient")]
+    pub playback_count: u64,
+    #[serde(alias = "favoritings_count", with = "lenient")]
+    pub likes_count: u64,
+    #[serde(with = "lenient")]
+    pub reposts_count: u64,
+    #[serde(with = "lenient")]
+    pub comment_count: u64,
+    #[serde(with = "lenient")]
+    pub genre: String,
+    #[serde(with = "lenient")]
+    pub tag_list: String,
+    #[serde(with = "lenient")]
+    pub description: String,
+    #[serde(with = "sc_datetime")]
+    pub created_at: Option<DateTime<FixedOffset>>,
+    #[serde(with = "lenient")]
+    pub release_year: Option<u32>,
+    #[serde(with = "lenient")]
+    pub release_month: Option<u32>,
+    #[serde(with = "lenient")]
+    pub release_day: Option<u32>,
+    #[serde(with = "lenient")]
+    pub permalink_url: String,
+    #[serde(with = "lenient")]
     pub artwork_url: String,
+    #[serde(with = "lenient")]
+    pub waveform_url: String,
+    #[serde(with = "lenient")]
+    pub bpm: Option<f64>,
+    #[serde(with = "lenient")]
+    pub license: String,
+    #[serde(with = "lenient")]
     pub stream_url: String,
+    #[serde(with = "lenient")]
     pub access: String,
+    #[serde(rename = "urn", with = "lenient")]
     pub track_urn: String,
 }
 
@@ -17,32 +62,294 @@ impl Track {
     pub fn is_playable(&self) -> bool {
         self.access.is_empty() || self.access == "playable"
     }
+
+    /// `metadata_artist` when the uploader filled it in, otherwise the uploader's name.
+    pub fn artists(&self) -> &str {
+        if self.metadata_artist.is_empty() {
+            &self.user.name
+        } else {
+            &self.metadata_artist
+        }
+    }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
 pub struct Playlist {
+    #[serde(with = "lenient")]
     pub title: String,
-    pub track_count: String,
-    pub duration: String,
-    pub created_at: DateTime<FixedOffset>,
+    #[serde(with = "lenient")]
+    pub urn: String,
+    #[serde(with = "lenient")]
+    pub user: Artist,
+    #[serde(with = "lenient")]
+    pub track_count: u64,
+    #[serde(rename = "duration", with = "lenient")]
+    pub duration_ms: u64,
+    #[serde(with = "sc_datetime")]
+    pub created_at: Option<DateTime<FixedOffset>>,
+    #[serde(with = "lenient")]
+    pub playlist_type: String,
+    #[serde(with = "lenient")]
+    pub sharing: String,
+    #[serde(with = "lenient")]
+    pub genre: String,
+    #[serde(with = "lenient")]
+    pub tag_list: String,
+    #[serde(with = "lenient")]
+    pub description: String,
+    #[serde(with = "lenient")]
+    pub permalink_url: String,
+    #[serde(with = "lenient")]
+    pub artwork_url: String,
+    #[serde(with = "lenient")]
+    pub likes_count: u64,
+    #[serde(with = "lenient")]
+    pub reposts_count: u64,
+    #[serde(with = "lenient")]
     pub tracks_uri: String,
     /// True if this playlist comes from `/me/playlists` (owned by the logged-in user).
     /// False if it comes from `/me/likes/playlists` (liked playlists).
     pub is_owned: bool,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
 pub struct Album {
+    #[serde(with = "lenient")]
     pub title: String,
-    pub artists: String,
-    pub release_year: String,
-    pub duration: String,
-    pub track_count: String,
+    #[serde(with = "lenient")]
+    pub urn: String,
+    #[serde(with = "lenient")]
+    pub user: Artist,
+    #[serde(with = "lenient")]
+    pub release_year: Option<u32>,
+    #[serde(with = "lenient")]
+    pub release_month: Option<u32>,
+    #[serde(with = "lenient")]
+    pub release_day: Option<u32>,
+    #[serde(rename = "duration", with = "lenient")]
+    pub duration_ms: u64,
+    #[serde(with = "lenient")]
+    pub track_count: u64,
+    #[serde(with = "sc_datetime")]
+    pub created_at: Option<DateTime<FixedOffset>>,
+    #[serde(with = "lenient")]
+    pub genre: String,
+    #[serde(with = "lenient")]
+    pub tag_list: String,
+    #[serde(with = "lenient")]
+    pub description: String,
+    #[serde(with = "lenient")]
+    pub label_name: String,
+    #[serde(with = "lenient")]
+    pub permalink_url: String,
+    #[serde(with = "lenient")]
+    pub artwork_url: String,
+    #[serde(with = "lenient")]
+    pub likes_count: u64,
+    #[serde(with = "lenient")]
+    pub reposts_count: u64,
+    #[serde(with = "lenient")]
     pub tracks_uri: String,
 }
 
-#[derive(Debug, Clone)]
+impl From<&Playlist> for Album {
+    /// Albums in the activity stream arrive as playlists with `playlist_type` `ALBUM`.
+    fn from(playlist: &Playlist) -> Self {
+        Self {
+            title: playlist.title.clone(),
+            urn: playlist.urn.clone(),
+            user: playlist.user.clone(),
+            duration_ms: playlist.duration_ms,
+            track_count: playlist.track_count,
+            created_at: playlist.created_at,
+            genre: playlist.genre.clone(),
+            tag_list: playlist.tag_list.clone(),
+            description: playlist.description.clone(),
+            permalink_url: playlist.permalink_url.clone(),
+            artwork_url: playlist.artwork_url.clone(),
+            likes_count: playlist.likes_count,
+            reposts_count: playlist.reposts_count,
+            tracks_uri: playlist.tracks_uri.clone(),
+            ..Self::default()
+        }
+    }
+}
+
+impl Album {
+    pub fn artists(&self) -> &str {
+        &self.user.name
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
 pub struct Artist {
+    #[serde(rename = "username", with = "lenient")]
     pub name: String,
+    #[serde(with = "lenient")]
     pub urn: String,
+    #[serde(with = "lenient")]
+    pub avatar_url: String,
+    #[serde(with = "lenient")]
+    pub permalink_url: String,
+    #[serde(with = "lenient")]
+    pub full_name: String,
+    #[serde(with = "lenient")]
+    pub description: String,
+    #[serde(with = "lenient")]
+    pub city: String,
+    #[serde(with = "lenient")]
+    pub country: String,
+    #[serde(with = "lenient")]
+    pub followers_count: u64,
+    #[serde(with = "lenient")]
+    pub followings_count: u64,
+    #[serde(with = "lenient")]
+    pub track_count: u64,
+    #[serde(with = "lenient")]
+    pub playlist_count: u64,
+    #[serde(with = "lenient")]
+    pub public_favorites_count: u64,
+    #[serde(with = "lenient")]
+    pub reposts_count: u64,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Comment {
+    #[serde(with = "lenient")]
+    pub urn: String,
+    #[serde(with = "lenient")]
+    pub body: String,
+    /// Position in the track the comment is anchored to.
+    #[serde(rename = "timestamp", with = "lenient")]
+    pub timestamp_ms: u64,
+    #[serde(with = "lenient")]
+    pub user: Artist,
+    #[serde(with = "sc_datetime")]
+    pub created_at: Option<DateTime<FixedOffset>>,
+}
+
+/// An entry of the `/me/activities` stream, the tag is the activity `type`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", content = "origin")]
+pub enum ActivityOrigin {
+    #[serde(rename = "track")]
+    Track(Track),
+    #[serde(rename = "track-repost")]
+    TrackRepost(Track),
+    #[serde(rename = "playlist")]
+    Playlist(Playlist),
+    #[serde(rename = "playlist-repost")]
+    PlaylistRepost(Playlist),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Activity {
+    #[serde(flatten)]
+    pub origin: ActivityOrigin,
+    #[serde(default, with = "sc_datetime")]
+    pub created_at: Option<DateTime<FixedOffset>>,
+}
+
+impl Activity {
+    pub fn is_repost(&self) -> bool {
+        matches!(
+            self.origin,
+            ActivityOrigin::TrackRepost(_) | ActivityOrigin::PlaylistRepost(_)
+        )
+    }
+
+    pub fn track(&self) -> Option<&Track> {
+        match &self.origin {
+            ActivityOrigin::Track(track) | ActivityOrigin::TrackRepost(track) => Some(track),
+            _ => None,
+        }
+    }
+
+    pub fn playlist(&self) -> Option<&Playlist> {
+        match &self.origin {
+            ActivityOrigin::Playlist(playlist) | ActivityOrigin::PlaylistRepost(playlist) => {
+                Some(playlist)
+            }
+            _ => None,
+        }
+    }
+
+    /// The uploader of the posted or reposted item.
+    pub fn user(&self) -> &Artist {
+        match &self.origin {
+            ActivityOrigin::Track(track) | ActivityOrigin::TrackRepost(track) => &track.user,
+            ActivityOrigin::Playlist(playlist) | ActivityOrigin::PlaylistRepost(playlist) => {
+                &playlist.user
+            }
+        }
+    }
+}
+
+/// SoundCloud returns `2024/03/02 21:14:08 +0000` on most endpoints and RFC 3339
+/// on a few; both are accepted, unparseable values become `None`.
+mod sc_datetime {
+    use chrono::{DateTime, FixedOffset};
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    const SC_FORMAT: &str = "%Y/%m/%d %H:%M:%S %z";
+
+    pub fn serialize<S: Serializer>(
+        value: &Option<DateTime<FixedOffset>>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match value {
+            Some(dt) => serializer.serialize_str(&dt.to_rfc3339()),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<DateTime<FixedOffset>>, D::Error> {
+        let raw = serde_json::Value::deserialize(deserializer)?;
+        Ok(raw.as_str().and_then(|s| {
+            DateTime::parse_from_rfc3339(s)
+                .or_else(|_| DateTime::parse_from_str(s, SC_FORMAT))
+                .ok()
+        }))
+    }
+}
+
+/// Numbers sent as strings and the other way round are converted, anything else
+/// that doesn't fit the field becomes its default.
+mod lenient {
+    use serde::de::DeserializeOwned;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use serde_json::Value;
+
+    pub fn serialize<S: Serializer, T: Serialize>(
+        value: &T,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        value.serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+    where
+        D: Deserializer<'de>,
+        T: DeserializeOwned + Default,
+    {
+        let raw = Value::deserialize(deserializer)?;
+        if let Ok(value) = T::deserialize(&raw) {
+            return Ok(value);
+        }
+        let coerced = match &raw {
+            Value::String(s) => serde_json::from_str(s.trim()).ok(),
+            Value::Number(n) => Some(Value::String(n.to_string())),
+            Value::Bool(b) => Some(Value::String(b.to_string())),
+            _ => None,
+        };
+        Ok(coerced
+            .and_then(|value| T::deserialize(&value).ok())
+            .unwrap_or_default())
+    }
 }
@@ -0,0 +1,263 @@
+use std::fmt;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::Duration;
+
+use reqwest::header::{HeaderMap, RETRY_AFTER};
+use reqwest::{Method, StatusCode};
+
+use crate::auth::{Token, refresh_rejected_token, try_refresh_token};
+
+/// Tries per request before a 429 or 5xx is given back to the caller.
+const MAX_ATTEMPTS: u32 = 4;
+const BASE_BACKOFF: Duration = Duration::from_millis(500);
+const MAX_BACKOFF: Duration = Duration::from_secs(30);
+
+/// Why a request failed, for callers that treat these differently.
+/// Comes wrapped in `anyhow::Error`, see `ApiError::of`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ApiError {
+    /// Still rejected after refreshing the token, the user has to log in again.
+    Unauthorized,
+    /// Go+ only, geo-blocked or private.
+    Forbidden,
+    NotFound,
+    /// Still 429 after backing off.
+    RateLimited { retry_after: Option<Duration> },
+    /// SoundCloud could not be reached at all.
+    Offline,
+    Status(StatusCode),
+}
+
+impl ApiError {
+    pub fn of(err: &anyhow::Error) -> Option<&ApiError> {
+        err.downcast_ref()
+    }
+
+    /// Whether `err` came from not reaching the server, including requests
+    /// that don't go through `send` such as token refreshes.
+    pub fn is_offline(err: &anyhow::Error) -> bool {
+        Self::of(err) == Some(&ApiError::Offline)
+            || err
+                .downcast_ref::<reqwest::Error>()
+                .is_some_and(|e| e.is_connect() || e.is_timeout())
+    }
+}
+
+impl fmt::Display for ApiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ApiError::Unauthorized => write!(f, "not logged in"),
+            ApiError::Forbidden => write!(f, "not available (Go+ or region locked)"),
+            ApiError::NotFound => write!(f, "not found"),
+            ApiError::RateLimited { .. } => write!(f, "rate limited by SoundCloud"),
+            ApiError::Offline => write!(f, "can't reach SoundCloud"),
+            ApiError::Status(status) => write!(f, "SoundCloud returned {}", status),
+        }
+    }
+}
+
+impl std::error::Error for ApiError {}
+
+enum Next {
+    Done,
+    Refresh,
+    Wait(Duration),
+    Fail(ApiError),
+}
+
+/// Decides what to do with a response to the `attempt`th try (from 0).
+fn next_step(
+    method: &Method,
+    status: StatusCode,
+    headers: &HeaderMap,
+    attempt: u32,
+    refreshed: bool,
+) -> Next {
+    if !status.is_client_error() && !status.is_server_error() {
+        return Next::Done;
+    }
+    let retry_after = retry_after(headers);
+    match status {
+        StatusCode::UNAUTHORIZED if refreshed => Next::Fail(ApiError::Unauthorized),
+        StatusCode::UNAUTHORIZED => Next::Refresh,
+        StatusCode::FORBIDDEN => Next::Fail(ApiError::Forbidden),
+        StatusCode::NOT_FOUND => Next::Fail(ApiError::NotFound),
+        StatusCode::TOO_MANY_REQUESTS if attempt + 1 >= MAX_ATTEMPTS => {
+            Next::Fail(ApiError::RateLimited { retry_after })
+        }
+        s if is_retryable(method, s, retry_after.is_some()) && attempt + 1 < MAX_ATTEMPTS => {
+            let backoff = BASE_BACKOFF * 2u32.pow(attempt);
+            Next::Wait(retry_after.unwrap_or(backoff).min(MAX_BACKOFF))
+        }
+        s => Next::Fail(ApiError::Status(s)),
+    }
+}
+
+/// A 429, or a 503 with `Retry-After`, means the request wasn't handled, so any
+/// method can be sent again. Other 5xx only for methods that are safe to repeat,
+/// a retried POST could create a second comment or playlist.
+fn is_retryable(method: &Method, status: StatusCode, has_retry_after: bool) -> bool {
+    let idempotent = matches!(*method, Method::GET | Method::PUT | Method::DELETE);
+    status == StatusCode::TOO_MANY_REQUESTS
+        || (status == StatusCode::SERVICE_UNAVAILABLE && has_retry_after)
+        || (status.is_server_error() && idempotent)
+}
+
+/// `Retry-After` as either delay seconds or an HTTP date.
+fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+    let value = headers.get(RETRY_AFTER)?.to_str().ok()?.trim();
+    if let Ok(secs) = value.parse::<u64>() {
+        return Some(Duration::from_secs(secs));
+    }
+    let at = chrono::DateTime::parse_from_rfc2822(value).ok()?;
+    (at.with_timezone(&chrono::Utc) - chrono::Utc::now()).to_std().ok()
+}
+
+fn transport_error(e: reqwest::Error) -> anyhow::Error {
+    if e.is_connect() || e.is_timeout() {
+        ApiError::Offline.into()
+    } else {
+        e.into()
+    }
+}
+
+/// One pooled client for every API call, so connections are reused.
+fn client() -> &'static reqwest::Client {
+    static CLIENT: OnceLock<reqwest::Client> = OnceLock::new();
+    CLIENT.get_or_init(reqwest::Client::new)
+}
+
+fn access_token(token: &Arc<Mutex<Token>>) -> String {
+    token.lock().unwrap().access_token.clone()
+}
+
+/// Sends the request made by `build` with the current token, refreshing it
+/// and retrying once on 401 and backing off on 429 and 5xx (see `is_retryable`).
+/// Any other failure status comes back as an `ApiError`.
+pub(crate) async fn send<F>(
+    token: &Arc<Mutex<Token>>,
+    build: F,
+) -> anyhow::Result<reqwest::Response>
+where
+    F: Fn(&reqwest::Client) -> reqwest::RequestBuilder,
+{
+    // the refresh goes through the blocking client, which can't run on a runtime thread
+    let shared = Arc::clone(token);
+    let _ = tokio::task::spawn_blocking(move || try_refresh_token(&shared)).await;
+
+    let client = client();
+    let mut attempt = 0;
+    let mut refreshed = false;
+    loop {
+        let sent_with = access_token(token);
+        let request = build(client).bearer_auth(&sent_with).build()?;
+        let method = request.method().clone();
+        let resp = client.execute(request).await.map_err(transport_error)?;
+        match next_step(&method, resp.status(), resp.headers(), attempt, refreshed) {
+            Next::Done => return Ok(resp),
+            Next::Refresh => {
+                refreshed = true;
+                let shared = Arc::clone(token);
+                tokio::task::spawn_blocking(move || refresh_rejected_token(&shared, &sent_with))
+                    .await?
+                    .map_err(|_| ApiError::Unauthorized)?;
+            }
+            Next::Wait(delay) => {
+                attempt += 1;
+                tokio::time::sleep(delay).await;
+            }
+            Next::Fail(e) => return Err(e.into()),
+        }
+    }
+}
+
+pub(crate) async fn get_json(
+    token: &Arc<Mutex<Token>>,
+    url: &str,
+) -> anyhow::Result<serde_json::Value> {
+    Ok(send(token, |client| client.get(url)).await?.json().await?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::HeaderValue;
+
+    fn step(method: Method, status: u16, retry_after: Option<&str>, attempt: u32) -> Next {
+        let mut headers = HeaderMap::new();
+        if let Some(value) = retry_after {
+            headers.insert(RETRY_AFTER, HeaderValue::from_str(value).unwrap());
+        }
+        let status = StatusCode::from_u16(status).unwrap();
+        next_step(&method, status, &headers, attempt, false)
+    }
+
+    fn waits(next: Next) -> Option<Duration> {
+        match next {
+            Next::Wait(delay) => Some(delay),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn success_is_done() {
+        assert!(matches!(step(Method::GET, 200, None, 0), Next::Done));
+        assert!(matches!(step(Method::POST, 201, None, 0), Next::Done));
+    }
+
+    #[test]
+    fn unauthorized_refreshes_once() {
+        let headers = HeaderMap::new();
+        let status = StatusCode::UNAUTHORIZED;
+        assert!(matches!(next_step(&Method::GET, status, &headers, 0, false), Next::Refresh));
+        assert!(matches!(
+            next_step(&Method::GET, status, &headers, 0, true),
+            Next::Fail(ApiError::Unauthorized)
+        ));
+    }
+
+    #[test]
+    fn client_errors_fail() {
+        assert!(matches!(step(Method::GET, 403, None, 0), Next::Fail(ApiError::Forbidden)));
+        assert!(matches!(step(Method::GET, 404, None, 0), Next::Fail(ApiError::NotFound)));
+        assert!(matches!(
+            step(Method::GET, 422, None, 0),
+            Next::Fail(ApiError::Status(StatusCode::UNPROCESSABLE_ENTITY))
+        ));
+    }
+
+    #[test]
+    fn server_errors_retry_idempotent_methods_with_backoff() {
+        for method in [Method::GET, Method::PUT, Method::DELETE] {
+            assert_eq!(waits(step(method.clone(), 500, None, 0)), Some(BASE_BACKOFF));
+            assert_eq!(waits(step(method, 502, None, 2)), Some(BASE_BACKOFF * 4));
+        }
+        assert!(matches!(
+            step(Method::GET, 500, None, MAX_ATTEMPTS - 1),
+            Next::Fail(ApiError::Status(StatusCode::INTERNAL_SERVER_ERROR))
+        ));
+    }
+
+    #[test]
+    fn post_only_retries_when_the_request_was_not_handled() {
+        assert!(matches!(
+            step(Method::POST, 500, None, 0),
+            Next::Fail(ApiError::Status(StatusCode::INTERNAL_SERVER_ERROR))
+        ));
+        assert!(matches!(step(Method::POST, 503, None, 0), Next::Fail(_)));
+        let wait = Some(Duration::from_secs(2));
+        assert_eq!(waits(step(Method::POST, 503, Some("2"), 0)), wait);
+        assert_eq!(waits(step(Method::POST, 429, None, 0)), Some(BASE_BACKOFF));
+    }
+
+    #[test]
+    fn rate_limit_honours_retry_after_up_to_the_cap() {
+        let wait = Some(Duration::from_secs(7));
+        assert_eq!(waits(step(Method::GET, 429, Some("7"), 0)), wait);
+        assert_eq!(waits(step(Method::GET, 429, Some("3600"), 0)), Some(MAX_BACKOFF));
+        assert!(matches!(
+            step(Method::GET, 429, Some("7"), MAX_ATTEMPTS - 1),
+            Next::Fail(ApiError::RateLimited { retry_after }) if retry_after == wait
+        ));
+    }
+}
@@ -1,39 +1,42 @@
-pub(crate) fn format_playback_count(n: u64) -> String {
-    match n {
-        0..=999 => n.to_string(),
-        1_000..=999_999 => format!("{:.2}K", n as f64 / 1_000.0),
-        1_000_000..=999_999_999 => format!("{:.2}M", n as f64 / 1_000_000.0),
-        1_000_000_000..=999_999_999_999 => format!("{:.2}B", n as f64 / 1_000_000_000.0),
-        _ => format!("{:.2}T", n as f64 / 1_000_000_000_000.0),
-    }
-}
+use serde::de::DeserializeOwned;
 
-pub(crate) fn format_duration(duration_ms: u64) -> String {
-    let duration_sec = duration_ms / 1000;
-    let hours = duration_sec / 3600;
-    let minutes = (duration_sec % 3600) / 60;
-    let seconds = duration_sec % 60;
+pub(crate) fn parse_next_href(resp: &serde_json::Value) -> Option<String> {
+    resp.get("next_href")
+        .and_then(|v| v.as_str())
+        .map(|s| s.to_string())
+}
 
-    if hours > 0 {
-        format!("{:02}:{:02}:{:02}", hours, minutes, seconds)
+/// Items of a `linked_partitioning` response, or of a bare array response.
+pub(crate) fn collection_items(resp: &serde_json::Value) -> &[serde_json::Value] {
+    if let Some(collection) = resp.get("collection").and_then(|v| v.as_array()) {
+        collection
+    } else if let Some(array) = resp.as_array() {
+        array
     } else {
-        format!("{:02}:{:02}", minutes, seconds)
+        &[]
     }
 }
 
-pub(crate) fn parse_str(obj: &serde_json::Value, key: &str) -> String {
-    obj.get(key)
-        .and_then(|v| v.as_str())
-        .unwrap_or("")
-        .to_string()
+/// Deserializes every item of a response, skipping any that don't fit the model at all
+/// (not an object, or an activity of a kind we don't show).
+pub(crate) fn parse_items<T: DeserializeOwned>(resp: &serde_json::Value) -> Vec<T> {
+    collection_items(resp).iter().filter_map(parse_item).collect()
 }
 
-pub(crate) fn parse_u64(obj: &serde_json::Value, key: &str) -> u64 {
-    obj.get(key).and_then(|v| v.as_u64()).unwrap_or(0)
+pub(crate) fn parse_item<T: DeserializeOwned>(value: &serde_json::Value) -> Option<T> {
+    let mut value = value.clone();
+    strip_nulls(&mut value);
+    serde_json::from_value(value).ok()
 }
 
-pub(crate) fn parse_next_href(resp: &serde_json::Value) -> Option<String> {
-    resp.get("next_href")
-        .and_then(|v| v.as_str())
-        .map(|s| s.to_string())
+/// SoundCloud sends `null` for most unset fields, drop them so `#[serde(default)]` applies.
+fn strip_nulls(value: &mut serde_json::Value) {
+    match value {
+        serde_json::Value::Object(map) => {
+            map.retain(|_, v| !v.is_null());
+            map.values_mut().for_each(strip_nulls);
+        }
+        serde_json::Value::Array(items) => items.iter_mut().for_each(strip_nulls),
+        _ => {}
+    }
 }
@@ -0,0 +1,34 @@
+use anyhow::{Result, anyhow};
+
+use crate::config::{ConfigFile, config_file_path};
+
+pub struct Credentials {
+    pub client_id: String,
+    pub client_secret: String,
+}
+
+impl Credentials {
+    /// Environment (or `.env`) first, then the config file, per field.
+    pub fn load() -> Result<Self> {
+        dotenvy::dotenv().ok();
+        let file = ConfigFile::load()?;
+        let client_id = std::env::var("SOUNDCLOUD_CLIENT_ID").ok().or(file.client_id);
+        let client_secret = std::env::var("SOUNDCLOUD_CLIENT_SECRET")
+            .ok()
+            .or(file.client_secret);
+
+        match (client_id, client_secret) {
+            (Some(client_id), Some(client_secret)) => Ok(Self {
+                client_id,
+                client_secret,
+            }),
+            _ => Err(anyhow!(
+                "missing SoundCloud client credentials, set SOUNDCLOUD_CLIENT_ID and \
+                 SOUNDCLOUD_CLIENT_SECRET or add client_id / client_secret to {}",
+                config_file_path()
+                    .map(|p| p.display().to_string())
+                    .unwrap_or_else(|| "the config file".to_string())
+            )),
+        }
+    }
+}
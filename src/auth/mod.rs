@@ -1,8 +1,16 @@
+mod credentials;
 mod oauth;
 mod refresh;
+mod session;
 mod token;
 
-pub use oauth::authenticate;
+pub use oauth::{LoginOptions, authenticate, login};
 #[allow(unused_imports)]
-pub use refresh::{refresh_token, start_auto_refresh, try_refresh_token};
-pub use token::{load_token, Token};
+pub use refresh::{
+    refresh_rejected_token, refresh_token, start_auto_refresh, try_refresh_token,
+};
+pub use session::{
+    SessionStatus, mark_logged_in, mark_logging_in, mark_login_failed, retry_after_login,
+    session_status,
+};
+pub use token::{read_token, Token};
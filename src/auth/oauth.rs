@@ -2,16 +2,21 @@ use anyhow::{Result, anyhow};
 use base64::{Engine as _, engine::general_purpose::URL_SAFE_NO_PAD};
 use rand::{Rng, distributions::Alphanumeric};
 use sha2::{Digest, Sha256};
-use std::fs;
-use std::sync::{Arc, Mutex};
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::sync::Arc;
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::thread::JoinHandle;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use tiny_http::{Response, Server};
 use url::Url;
 
-use super::token::Token;
+use super::credentials::Credentials;
+use super::token::{Token, save_token};
+use crate::config::{ConfigFile, auth_url};
 
 static CODE_VERIFIER_LEN: usize = 64;
 static STATE_LEN: usize = 56;
+static DEFAULT_CALLBACK_PORT: u16 = 8080;
+static DEFAULT_LOGIN_TIMEOUT_SECS: u64 = 300;
 
 fn generate_code_verifier() -> String {
     rand::thread_rng()
@@ -34,92 +39,264 @@ fn generate_state() -> String {
         .collect()
 }
 
-pub fn authenticate() -> Result<Token> {
-    dotenvy::dotenv().ok();
-    let client_id = std::env::var("SOUNDCLOUD_CLIENT_ID")?;
-    let client_secret = std::env::var("SOUNDCLOUD_CLIENT_SECRET")?;
-    let redirect_uri = "http://127.0.0.1:8080/callback";
+/// How the authorization code gets back to us.
+pub struct LoginOptions {
+    /// Try to open the authorize URL in a browser, it is printed either way.
+    pub open_browser: bool,
+    /// Also accept the redirect URL pasted on stdin. Only safe
+    /// before the TUI owns the terminal.
+    pub allow_paste: bool,
+}
+
+impl LoginOptions {
+    /// Startup login, falls back to pasting when there is no usable browser.
+    pub fn interactive(no_browser: bool) -> Self {
+        Self {
+            open_browser: !no_browser && !is_headless(),
+            allow_paste: true,
+        }
+    }
+}
+
+/// An SSH session without a display would have `webbrowser` launch a text
+/// browser over the terminal, or nothing at all.
+fn is_headless() -> bool {
+    let over_ssh = std::env::var_os("SSH_CONNECTION").is_some() || std::env::var_os("SSH_TTY").is_some();
+    let has_display =
+        std::env::var_os("DISPLAY").is_some() || std::env::var_os("WAYLAND_DISPLAY").is_some();
+    over_ssh && !has_display
+}
+
+/// `SOUNDCLOUD_CALLBACK_PORT`, then `callback_port` in the config file, `0`
+/// picks any free port.
+fn callback_port(config: &ConfigFile) -> Result<u16> {
+    match std::env::var("SOUNDCLOUD_CALLBACK_PORT") {
+        Ok(port) => port
+            .trim()
+            .parse()
+            .map_err(|_| anyhow!("SOUNDCLOUD_CALLBACK_PORT is not a port number: {}", port)),
+        Err(_) => Ok(config.callback_port.unwrap_or(DEFAULT_CALLBACK_PORT)),
+    }
+}
+
+/// Browser login used while the TUI is running, to re-authenticate or add an
+/// account. Saves under `profile`.
+pub fn authenticate(profile: &str) -> Result<Token> {
+    login(
+        profile,
+        LoginOptions {
+            open_browser: true,
+            allow_paste: false,
+        },
+    )
+}
+
+/// Logs in and saves the token under `profile`.
+pub fn login(profile: &str, options: LoginOptions) -> Result<Token> {
+    let Credentials {
+        client_id,
+        client_secret,
+    } = Credentials::load()?;
+    let config = ConfigFile::load()?;
+    let timeout = Duration::from_secs(
+        config
+            .login_timeout_secs
+            .unwrap_or(DEFAULT_LOGIN_TIMEOUT_SECS),
+    );
+
+    let port = callback_port(&config)?;
+    let server = match Server::http(("127.0.0.1", port)) {
+        Ok(server) => Some(server),
+        Err(e) if options.allow_paste => {
+            eprintln!(
+                "Could not listen on 127.0.0.1:{} ({}), paste the redirect URL instead.",
+                port, e
+            );
+            None
+        }
+        Err(e) => {
+            return Err(anyhow!(
+                "Failed to start server, check port {} is open and/or free: {}",
+                port,
+                e
+            ));
+        }
+    };
+    let port = server
+        .as_ref()
+        .and_then(|s| s.server_addr().to_ip())
+        .map_or(port, |addr| addr.port());
+    let redirect_uri = format!("http://127.0.0.1:{}/callback", port);
+
     let code_verifier = generate_code_verifier();
     let code_challenge = generate_code_challenge(&code_verifier);
     let state = generate_state();
-    let auth_url = format!(
-        "https://secure.soundcloud.com/authorize?client_id={client_id}&redirect_uri={redirect_uri}&response_type=code&code_challenge={code_challenge}&code_challenge_method=S256&state={state}",
-        client_id = client_id,
-        redirect_uri = redirect_uri,
-        code_challenge = code_challenge,
-        state = state
-    );
-    webbrowser::open(&auth_url)?;
+    let mut authorize_url = Url::parse(&auth_url("/authorize"))?;
+    authorize_url
+        .query_pairs_mut()
+        .append_pair("client_id", &client_id)
+        .append_pair("redirect_uri", &redirect_uri)
+        .append_pair("response_type", "code")
+        .append_pair("code_challenge", &code_challenge)
+        .append_pair("code_challenge_method", "S256")
+        .append_pair("state", &state);
 
-    let server = Server::http("127.0.0.1:8080").map_err(|e| {
-        anyhow!(
-            "Failed to start server, check port 8080 is open and/or free: {}",
-            e
-        )
-    })?;
+    let browser_opened = options.open_browser && webbrowser::open(authorize_url.as_str()).is_ok();
+    let paste = options.allow_paste && (!browser_opened || server.is_none());
+    if paste {
+        eprintln!("Open this URL in a browser to log in to SoundCloud:\n\n{}\n", authorize_url);
+        eprintln!(
+            "After approving, paste the whole URL you were redirected to and press Enter:"
+        );
+    } else if !browser_opened {
+        return Err(anyhow!(
+            "could not open a browser for login, restart sctui with --no-browser to log in manually"
+        ));
+    }
 
-    let received_data = Arc::new(Mutex::new(None));
-    for request in server.incoming_requests() {
-        let url_str = format!("http://127.0.0.1:8080{}", request.url());
-        let parsed = Url::parse(&url_str)?;
-        if parsed.path() == "/callback" {
-            let query_pairs = parsed.query_pairs();
-            let code_opt = query_pairs
-                .clone()
-                .find(|(k, _)| k == "code")
-                .map(|(_, v)| v.into_owned());
-            let state_opt = query_pairs
-                .clone()
-                .find(|(k, _)| k == "state")
-                .map(|(_, v)| v.into_owned());
-            if let (Some(code), Some(returned_state)) = (code_opt, state_opt) {
-                if returned_state != state {
-                    let response =
-                        Response::from_string("Invalid state parameter").with_status_code(400);
-                    request.respond(response)?;
-                    return Err(anyhow!("CSRF state mismatch"));
-                }
-                {
-                    let mut data = received_data.lock().unwrap();
-                    *data = Some(code.clone());
-                }
-                let response =
-                    Response::from_string("Authentication successful! You can close this window.");
-                request.respond(response)?;
-                break;
-            } else {
-                let response = Response::from_string("Missing code or state").with_status_code(400);
-                request.respond(response)?;
-                return Err(anyhow!("Missing code or state in callback"));
+    let (tx, rx) = mpsc::channel::<Result<Callback>>();
+    let callback_server = server.map(|server| {
+        let server = Arc::new(server);
+        let tx = tx.clone();
+        let thread_server = Arc::clone(&server);
+        let thread = std::thread::spawn(move || {
+            if let Some(callback) = serve_callback(&thread_server) {
+                let _ = tx.send(callback);
             }
-        } else {
-            let response = Response::from_string("Not found").with_status_code(404);
-            request.respond(response)?;
+        });
+        CallbackServer {
+            server,
+            thread: Some(thread),
         }
-    }
+    });
+    let stdin_rx = paste.then(|| {
+        let (stdin_tx, stdin_rx) = mpsc::channel::<()>();
+        let tx = tx.clone();
+        std::thread::spawn(move || {
+            let mut line = String::new();
+            let read = std::io::stdin().read_line(&mut line);
+            let _ = stdin_tx.send(());
+            if read.is_ok() && !line.trim().is_empty() {
+                let _ = tx.send(parse_pasted(&line));
+            }
+        });
+        stdin_rx
+    });
+    drop(tx);
 
-    let code = {
-        let data = received_data.lock().unwrap();
-        data.clone()
-            .ok_or_else(|| anyhow!("No authorization code received"))?
+    let callback = match rx.recv_timeout(timeout) {
+        Ok(callback) => callback?,
+        Err(RecvTimeoutError::Timeout) => {
+            return Err(anyhow!(
+                "timed out after {}s waiting for SoundCloud login, run sctui again to retry",
+                timeout.as_secs()
+            ));
+        }
+        Err(RecvTimeoutError::Disconnected) => {
+            return Err(anyhow!("No authorization code received"));
+        }
     };
+    drop(callback_server);
+
+    // a reader still blocked on stdin would swallow the TUI's first keys
+    if let Some(stdin_rx) = stdin_rx
+        && stdin_rx.try_recv().is_err()
+    {
+        eprintln!("Login received, press Enter to continue.");
+        let _ = stdin_rx.recv();
+    }
+
+    if callback.state != state {
+        return Err(anyhow!("CSRF state mismatch"));
+    }
+
     let params = [
         ("client_id", client_id.as_str()),
         ("client_secret", client_secret.as_str()),
-        ("redirect_uri", redirect_uri),
+        ("redirect_uri", redirect_uri.as_str()),
         ("grant_type", "authorization_code"),
-        ("code", &code),
+        ("code", &callback.code),
         ("code_verifier", &code_verifier),
     ];
     let mut resp = reqwest::blocking::Client::new()
-        .post("https://secure.soundcloud.com/oauth/token")
+        .post(auth_url("/oauth/token"))
         .form(&params)
         .send()?
         .error_for_status()?
         .json::<Token>()?;
 
     resp.obtained_at = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
-    fs::write("token.json", serde_json::to_string_pretty(&resp)?)?;
+    resp.profile = profile.to_string();
+    save_token(&resp)?;
 
     Ok(resp)
 }
+
+/// Keeps the callback port bound only while `login` runs: dropping it wakes the
+/// server thread and waits for it, whichever way the login ended.
+struct CallbackServer {
+    server: Arc<Server>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl Drop for CallbackServer {
+    fn drop(&mut self) {
+        self.server.unblock();
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+/// An authorization code and the state it came back with.
+struct Callback {
+    code: String,
+    state: String,
+}
+
+fn callback_from_url(url: &Url) -> Result<Callback> {
+    let param = |name: &str| {
+        url.query_pairs()
+            .find(|(k, _)| k == name)
+            .map(|(_, v)| v.into_owned())
+    };
+    if let Some(error) = param("error") {
+        let description = param("error_description").unwrap_or_default();
+        return Err(anyhow!("SoundCloud login failed: {} {}", error, description));
+    }
+    match (param("code"), param("state")) {
+        (Some(code), Some(state)) => Ok(Callback { code, state }),
+        _ => Err(anyhow!("Missing code or state in callback")),
+    }
+}
+
+fn parse_pasted(line: &str) -> Result<Callback> {
+    let line = line.trim();
+    match Url::parse(line) {
+        Ok(url) => callback_from_url(&url),
+        Err(_) => Err(anyhow!("paste the whole URL you were redirected to, including its `state`")),
+    }
+}
+
+/// Answers requests until one hits `/callback`, ignoring stray ones.
+fn serve_callback(server: &Server) -> Option<Result<Callback>> {
+    for request in server.incoming_requests() {
+        let url_str = format!("http://127.0.0.1{}", request.url());
+        let Ok(parsed) = Url::parse(&url_str) else {
+            let _ = request.respond(Response::from_string("Bad request").with_status_code(400));
+            continue;
+        };
+        if parsed.path() != "/callback" {
+            let _ = request.respond(Response::from_string("Not found").with_status_code(404));
+            continue;
+        }
+        let callback = callback_from_url(&parsed);
+        let response = match &callback {
+            Ok(_) => Response::from_string("Authentication successful! You can close this window."),
+            Err(e) => Response::from_string(e.to_string()).with_status_code(400),
+        };
+        let _ = request.respond(response);
+        return Some(callback);
+    }
+    None
+}
@@ -1,16 +1,20 @@
 use anyhow::Result;
-use std::fs;
 use std::sync::{Arc, Mutex};
 use std::time::{SystemTime, UNIX_EPOCH};
 
-use super::token::{Token, REFRESH_TIME};
+use super::credentials::Credentials;
+use super::session::{SessionStatus, mark_session_expired, session_status};
+use super::token::{Token, REFRESH_TIME, save_token};
+use crate::api::ApiError;
+use crate::config::auth_url;
 
 static REFRESH_BUFFER: u64 = 300;
 
 pub fn refresh_token(old_token: &Token) -> Result<Token> {
-    dotenvy::dotenv().ok();
-    let client_id = std::env::var("SOUNDCLOUD_CLIENT_ID")?;
-    let client_secret = std::env::var("SOUNDCLOUD_CLIENT_SECRET")?;
+    let Credentials {
+        client_id,
+        client_secret,
+    } = Credentials::load()?;
     let params = [
         ("grant_type", "refresh_token"),
         ("client_id", &client_id),
@@ -18,7 +22,7 @@ pub fn refresh_token(old_token: &Token) -> Result<Token> {
         ("refresh_token", &old_token.refresh_token),
     ];
     let mut resp = reqwest::blocking::Client::new()
-        .post("https://secure.soundcloud.com/oauth/token")
+        .post(auth_url("/oauth/token"))
         .header("accept", "application/json; charset=utf-8")
         .header("Content-Type", "application/x-www-form-urlencoded")
         .form(&params)
@@ -27,12 +31,15 @@ pub fn refresh_token(old_token: &Token) -> Result<Token> {
         .json::<Token>()?;
 
     resp.obtained_at = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
-    fs::write("token.json", serde_json::to_string_pretty(&resp)?)?;
+    resp.profile = old_token.profile.clone();
+    save_token(&resp)?;
 
     Ok(resp)
 }
 
-pub fn start_auto_refresh(token: Arc<Mutex<Token>>, reauth_tx: std::sync::mpsc::Sender<()>) {
+/// Keeps the shared token fresh, a failed refresh expires the session so the
+/// TUI can ask the user to log in again.
+pub fn start_auto_refresh(token: Arc<Mutex<Token>>) {
     std::thread::spawn(move || {
         loop {
             let should_refresh = {
@@ -46,17 +53,17 @@ pub fn start_auto_refresh(token: Arc<Mutex<Token>>, reauth_tx: std::sync::mpsc::
                 time_until_expiry <= REFRESH_BUFFER
             };
 
-            if should_refresh {
+            if should_refresh && session_status() == SessionStatus::Active {
                 let mut token_guard = token.lock().unwrap();
                 match refresh_token(&*token_guard) {
                     Ok(new_token) => {
                         *token_guard = new_token;
                     }
+                    // offline, try again on the next round
+                    Err(e) if ApiError::is_offline(&e) => {}
                     Err(_) => {
                         drop(token_guard);
-                        let _ = reauth_tx.send(());
-                        std::thread::sleep(std::time::Duration::from_secs(60));
-                        continue;
+                        mark_session_expired();
                     }
                 }
             }
@@ -66,19 +73,23 @@ pub fn start_auto_refresh(token: Arc<Mutex<Token>>, reauth_tx: std::sync::mpsc::
     });
 }
 
+/// Refreshes an expired token. The lock is held throughout, so concurrent
+/// requests wait for the first refresh instead of each running their own.
 pub fn try_refresh_token(token: &Arc<Mutex<Token>>) -> Result<()> {
-    let token_guard = token.lock().unwrap();
+    let mut token_guard = token.lock().unwrap();
     if token_guard.is_expired() {
-        drop(token_guard);
-        let mut token_guard = token.lock().unwrap();
-        match refresh_token(&*token_guard) {
-            Ok(new_token) => {
-                *token_guard = new_token;
-                Ok(())
-            }
-            Err(e) => Err(e),
-        }
-    } else {
-        Ok(())
+        *token_guard = refresh_token(&token_guard)?;
+    }
+    Ok(())
+}
+
+/// Refreshes after the API rejected `rejected`, unless a concurrent request
+/// already swapped in a newer token.
+pub fn refresh_rejected_token(token: &Arc<Mutex<Token>>, rejected: &str) -> Result<()> {
+    let mut token_guard = token.lock().unwrap();
+    if token_guard.access_token != rejected {
+        return Ok(());
     }
+    *token_guard = refresh_token(&token_guard)?;
+    Ok(())
 }
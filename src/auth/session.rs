@@ -0,0 +1,100 @@
+use std::sync::Mutex;
+
+use tokio::sync::Notify;
+
+use crate::api::ApiError;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SessionStatus {
+    Active,
+    /// The token could not be refreshed, requests fail until the user logs in.
+    Expired,
+    LoggingIn,
+    LoginFailed(String),
+}
+
+struct Session {
+    status: SessionStatus,
+    /// Bumped on every successful login, lets a failed request tell whether a
+    /// newer token has arrived since it was sent.
+    logins: u64,
+}
+
+static SESSION: Mutex<Session> = Mutex::new(Session {
+    status: SessionStatus::Active,
+    logins: 0,
+});
+static LOGGED_IN: Notify = Notify::const_new();
+
+pub fn session_status() -> SessionStatus {
+    SESSION.lock().unwrap().status.clone()
+}
+
+/// Read before sending a request, pass it to `wait_for_login` if it comes back 401.
+pub fn login_count() -> u64 {
+    SESSION.lock().unwrap().logins
+}
+
+pub fn mark_session_expired() {
+    let mut session = SESSION.lock().unwrap();
+    if session.status == SessionStatus::Active {
+        session.status = SessionStatus::Expired;
+    }
+}
+
+pub fn mark_logging_in() {
+    SESSION.lock().unwrap().status = SessionStatus::LoggingIn;
+}
+
+pub fn mark_login_failed(error: String) {
+    SESSION.lock().unwrap().status = SessionStatus::LoginFailed(error);
+}
+
+pub fn mark_logged_in() {
+    let mut session = SESSION.lock().unwrap();
+    session.status = SessionStatus::Active;
+    session.logins += 1;
+    LOGGED_IN.notify_waiters();
+}
+
+/// Marks the session expired unless someone logged in after `since`, then
+/// waits for a login newer than `since`. Nothing is left waiting once the
+/// future is dropped, e.g. when its task is aborted.
+pub async fn wait_for_login(since: u64) {
+    {
+        let mut session = SESSION.lock().unwrap();
+        if session.logins == since && session.status == SessionStatus::Active {
+            session.status = SessionStatus::Expired;
+        }
+    }
+    loop {
+        // registered before the check, so a login in between still wakes it
+        let logged_in = LOGGED_IN.notified();
+        if SESSION.lock().unwrap().logins != since {
+            return;
+        }
+        logged_in.await;
+    }
+}
+
+/// Whether the request behind `err` was rejected for its token even after a refresh.
+pub fn is_unauthorized(err: &anyhow::Error) -> bool {
+    ApiError::of(err) == Some(&ApiError::Unauthorized)
+}
+
+/// Runs `request`, and if it is rejected for its token, waits for the user to
+/// log in again and runs it once more.
+pub async fn retry_after_login<T, F, Fut>(request: F) -> anyhow::Result<T>
+where
+    F: Fn() -> Fut,
+    Fut: Future<Output = anyhow::Result<T>>,
+{
+    let since = login_count();
+    match request().await {
+        Err(e) if is_unauthorized(&e) => {
+            wait_for_login(since).await;
+            request().await
+        }
+        result => result,
+    }
+}
@@ -1,16 +1,24 @@
+use anyhow::Result;
 use serde::{Deserialize, Serialize};
 use std::fs;
+use std::path::Path;
 use std::time::{SystemTime, UNIX_EPOCH};
 
+use crate::config::{DEFAULT_PROFILE, LEGACY_TOKEN_PATH, token_path, write_private};
+
 pub(crate) const REFRESH_TIME: u64 = 2700;
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Token {
     pub access_token: String,
     pub refresh_token: String,
 
     #[serde(default)]
     pub obtained_at: u64,
+
+    /// Profile the token is saved under, refreshed tokens keep it.
+    #[serde(skip)]
+    pub profile: String,
 }
 
 impl Token {
@@ -23,12 +31,35 @@ impl Token {
     }
 }
 
-pub fn load_token() -> Option<Token> {
-    let data = fs::read_to_string("token.json").ok()?;
-    let token: Token = serde_json::from_str(&data).ok()?;
-    if token.is_expired() {
-        None
-    } else {
-        Some(token)
+/// The saved token of `profile`, even if it needs refreshing.
+pub fn read_token(profile: &str) -> Option<Token> {
+    let path = token_path(profile);
+    if profile == DEFAULT_PROFILE {
+        migrate_legacy_token(&path);
+    }
+    let data = fs::read_to_string(&path).ok()?;
+    let mut token: Token = serde_json::from_str(&data).ok()?;
+    token.profile = profile.to_string();
+    Some(token)
+}
+
+pub fn save_token(token: &Token) -> Result<()> {
+    write_private(
+        &token_path(&token.profile),
+        &serde_json::to_string_pretty(token)?,
+    )
+}
+
+/// Moves a `./token.json` left by older versions into the state directory.
+fn migrate_legacy_token(path: &Path) {
+    let legacy = Path::new(LEGACY_TOKEN_PATH);
+    if path == legacy || path.exists() {
+        return;
+    }
+    let Ok(data) = fs::read_to_string(legacy) else {
+        return;
+    };
+    if serde_json::from_str::<Token>(&data).is_ok() && write_private(path, &data).is_ok() {
+        let _ = fs::remove_file(legacy);
     }
 }
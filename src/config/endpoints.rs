@@ -0,0 +1,65 @@
+use std::sync::OnceLock;
+
+const DEFAULT_API_BASE: &str = "https://api.soundcloud.com";
+const DEFAULT_AUTH_BASE: &str = "https://secure.soundcloud.com";
+
+static ENDPOINTS: OnceLock<Endpoints> = OnceLock::new();
+
+/// Base URLs for the SoundCloud API and its OAuth host.
+///
+/// Defaults to the public SoundCloud hosts, overridable through
+/// `SOUNDCLOUD_API_BASE` / `SOUNDCLOUD_AUTH_BASE` or by pointing both at the
+/// bundled mock server.
+#[derive(Debug, Clone)]
+pub struct Endpoints {
+    pub api_base: String,
+    pub auth_base: String,
+}
+
+impl Endpoints {
+    pub fn from_env() -> Self {
+        dotenvy::dotenv().ok();
+        let api_base = std::env::var("SOUNDCLOUD_API_BASE")
+            .unwrap_or_else(|_| DEFAULT_API_BASE.to_string());
+        let auth_base = std::env::var("SOUNDCLOUD_AUTH_BASE")
+            .unwrap_or_else(|_| DEFAULT_AUTH_BASE.to_string());
+        Self {
+            api_base: api_base.trim_end_matches('/').to_string(),
+            auth_base: auth_base.trim_end_matches('/').to_string(),
+        }
+    }
+
+    pub fn local(base: &str) -> Self {
+        let base = base.trim_end_matches('/').to_string();
+        Self {
+            api_base: base.clone(),
+            auth_base: base,
+        }
+    }
+}
+
+/// Must be called before the first request is made, later calls are ignored.
+pub fn init_endpoints(endpoints: Endpoints) {
+    let _ = ENDPOINTS.set(endpoints);
+}
+
+fn current() -> &'static Endpoints {
+    ENDPOINTS.get_or_init(Endpoints::from_env)
+}
+
+pub fn api_url(path: &str) -> String {
+    format!("{}{}", current().api_base, path)
+}
+
+pub fn auth_url(path: &str) -> String {
+    format!("{}{}", current().auth_base, path)
+}
+
+/// Strips the configured API host (and the public one) from an absolute URL,
+/// leaving the path and query.
+pub fn strip_api_base(url: &str) -> &str {
+    url.strip_prefix(current().api_base.as_str())
+        .or_else(|| url.strip_prefix(DEFAULT_API_BASE))
+        .or_else(|| url.strip_prefix("http://api.soundcloud.com"))
+        .unwrap_or(url)
+}
@@ -0,0 +1,50 @@
+/// Centre of each equalizer band, an octave apart.
+pub const EQ_BANDS_HZ: [u32; 10] = [31, 62, 125, 250, 500, 1_000, 2_000, 4_000, 8_000, 16_000];
+pub const EQ_BAND_COUNT: usize = EQ_BANDS_HZ.len();
+/// Furthest a band can be boosted or cut, in dB.
+pub const MAX_EQ_DB: i8 = 12;
+
+pub struct EqPreset {
+    pub name: &'static str,
+    pub gains_db: [i8; EQ_BAND_COUNT],
+}
+
+pub const EQ_PRESETS: [EqPreset; 8] = [
+    EqPreset {
+        name: "Flat",
+        gains_db: [0; EQ_BAND_COUNT],
+    },
+    EqPreset {
+        name: "Bass boost",
+        gains_db: [6, 5, 4, 2, 0, 0, 0, 0, 0, 0],
+    },
+    EqPreset {
+        name: "Bass cut",
+        gains_db: [-6, -5, -4, -2, 0, 0, 0, 0, 0, 0],
+    },
+    EqPreset {
+        name: "Treble boost",
+        gains_db: [0, 0, 0, 0, 0, 0, 2, 4, 5, 6],
+    },
+    EqPreset {
+        name: "Vocal",
+        gains_db: [-2, -2, -1, 0, 2, 3, 3, 2, 0, -1],
+    },
+    EqPreset {
+        name: "Loudness",
+        gains_db: [5, 4, 2, 0, -1, -1, 0, 2, 3, 4],
+    },
+    EqPreset {
+        name: "Headphones",
+        gains_db: [3, 2, 1, 0, 0, 0, -1, -2, 1, 2],
+    },
+    EqPreset {
+        name: "Laptop speakers",
+        gains_db: [-6, -4, -1, 2, 3, 2, 1, 1, 2, 2],
+    },
+];
+
+/// The preset `gains_db` are set to, `None` for a custom curve.
+pub fn eq_preset_index(gains_db: &[i8; EQ_BAND_COUNT]) -> Option<usize> {
+    EQ_PRESETS.iter().position(|p| p.gains_db == *gains_db)
+}
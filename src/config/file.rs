@@ -0,0 +1,44 @@
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::fs;
+
+use super::paths::config_file_path;
+use super::quality::StreamQuality;
+
+/// Optional `$XDG_CONFIG_HOME/sctui/config.toml`.
+///
+/// ```toml
+/// client_id = "..."
+/// client_secret = "..."
+/// callback_port = 8080       # 0 picks any free port
+/// login_timeout_secs = 300
+/// seek_step_secs = 10        # Alt + Left/Right
+/// seek_coarse_step_secs = 60 # Alt + Shift + Left/Right
+/// stream_quality = "high"    # normal, data-saver or mp3-only
+/// ```
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct ConfigFile {
+    pub client_id: Option<String>,
+    pub client_secret: Option<String>,
+    pub callback_port: Option<u16>,
+    pub login_timeout_secs: Option<u64>,
+    pub seek_step_secs: Option<u64>,
+    pub seek_coarse_step_secs: Option<u64>,
+    pub stream_quality: Option<StreamQuality>,
+}
+
+impl ConfigFile {
+    /// A missing file is the same as an empty one, a malformed one is an error.
+    pub fn load() -> Result<Self> {
+        let Some(path) = config_file_path() else {
+            return Ok(Self::default());
+        };
+        let data = match fs::read_to_string(&path) {
+            Ok(data) => data,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(e) => return Err(e).with_context(|| format!("failed to read {}", path.display())),
+        };
+        toml::from_str(&data).with_context(|| format!("failed to parse {}", path.display()))
+    }
+}
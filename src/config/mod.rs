@@ -0,0 +1,20 @@
+mod endpoints;
+mod equalizer;
+mod file;
+mod paths;
+mod profiles;
+mod quality;
+mod settings;
+
+pub use endpoints::{Endpoints, api_url, auth_url, init_endpoints, strip_api_base};
+pub use equalizer::{EQ_BAND_COUNT, EQ_BANDS_HZ, EQ_PRESETS, MAX_EQ_DB, eq_preset_index};
+pub use file::ConfigFile;
+pub use paths::{
+    LEGACY_TOKEN_PATH, config_file_path, library_cache_path, loudness_cache_path, token_path,
+    write_private,
+};
+pub use profiles::{
+    DEFAULT_PROFILE, active_profile, is_valid_profile_name, profile_names, set_active_profile,
+};
+pub use quality::{STREAM_QUALITIES, StreamQuality};
+pub use settings::{MAX_CROSSFADE_SECS, MAX_TARGET_LUFS, MIN_TARGET_LUFS, Settings};
@@ -0,0 +1,114 @@
+use anyhow::{Context, Result};
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use super::profiles::DEFAULT_PROFILE;
+
+const APP_DIR: &str = "sctui";
+
+/// Where tokens lived before they moved to the state directory.
+pub const LEGACY_TOKEN_PATH: &str = "token.json";
+
+/// `$XDG_<var>` when it is set to an absolute path, else `$HOME/<fallback>`.
+fn xdg_dir(var: &str, fallback: &str) -> Option<PathBuf> {
+    std::env::var_os(var)
+        .map(PathBuf::from)
+        .filter(|p| p.is_absolute())
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(fallback)))
+        .map(|dir| dir.join(APP_DIR))
+}
+
+/// `$XDG_CONFIG_HOME/sctui`, for files the user edits.
+pub fn config_dir() -> Option<PathBuf> {
+    xdg_dir("XDG_CONFIG_HOME", ".config")
+}
+
+/// `$XDG_STATE_HOME/sctui`, for files sctui writes itself.
+pub fn state_dir() -> Option<PathBuf> {
+    xdg_dir("XDG_STATE_HOME", ".local/state")
+}
+
+/// `$XDG_CACHE_HOME/sctui`, for data that can always be fetched again.
+pub fn cache_dir() -> Option<PathBuf> {
+    xdg_dir("XDG_CACHE_HOME", ".cache")
+}
+
+pub fn config_file_path() -> Option<PathBuf> {
+    config_dir().map(|dir| dir.join("config.toml"))
+}
+
+/// Falls back to the working directory when there is no home to resolve.
+pub fn token_path(profile: &str) -> PathBuf {
+    let Some(dir) = state_dir() else {
+        return if profile == DEFAULT_PROFILE {
+            PathBuf::from(LEGACY_TOKEN_PATH)
+        } else {
+            PathBuf::from(format!("token-{}.json", profile))
+        };
+    };
+    if profile == DEFAULT_PROFILE {
+        dir.join("token.json")
+    } else {
+        dir.join("profiles").join(profile).join("token.json")
+    }
+}
+
+/// Settings changed from inside the TUI, shared by every profile.
+pub fn settings_path() -> Option<PathBuf> {
+    state_dir().map(|dir| dir.join("settings.json"))
+}
+
+/// Loudness measured for tracks played before, shared by every profile.
+pub fn loudness_cache_path() -> Option<PathBuf> {
+    cache_dir().map(|dir| dir.join("loudness.json"))
+}
+
+/// Snapshot of the library `profile` last loaded, `None` without a home to keep it in.
+pub fn library_cache_path(profile: &str) -> Option<PathBuf> {
+    let dir = cache_dir()?;
+    Some(if profile == DEFAULT_PROFILE {
+        dir.join("library.json")
+    } else {
+        dir.join("profiles").join(profile).join("library.json")
+    })
+}
+
+/// Writes `contents` readable only by the current user, creating parent
+/// directories as needed and tightening the mode of an existing file.
+pub fn write_private(path: &Path, contents: &str) -> Result<()> {
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        create_private_dir(parent)?;
+    }
+
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let mut file = options
+        .open(path)
+        .with_context(|| format!("failed to open {}", path.display()))?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        file.set_permissions(fs::Permissions::from_mode(0o600))?;
+    }
+    file.write_all(contents.as_bytes())?;
+    Ok(())
+}
+
+fn create_private_dir(dir: &Path) -> Result<()> {
+    let mut builder = fs::DirBuilder::new();
+    builder.recursive(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::DirBuilderExt;
+        builder.mode(0o700);
+    }
+    builder
+        .create(dir)
+        .with_context(|| format!("failed to create {}", dir.display()))
+}
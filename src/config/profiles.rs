@@ -0,0 +1,49 @@
+use std::fs;
+use std::sync::Mutex;
+
+use super::paths::state_dir;
+
+/// Profile whose token lives at the top of the state directory, as it did
+/// before profiles existed.
+pub const DEFAULT_PROFILE: &str = "default";
+
+static ACTIVE_PROFILE: Mutex<String> = Mutex::new(String::new());
+
+pub fn active_profile() -> String {
+    let name = ACTIVE_PROFILE.lock().unwrap();
+    if name.is_empty() {
+        DEFAULT_PROFILE.to_string()
+    } else {
+        name.clone()
+    }
+}
+
+/// Selects the profile new logins are saved under.
+pub fn set_active_profile(name: &str) {
+    *ACTIVE_PROFILE.lock().unwrap() = name.to_string();
+}
+
+/// Names end up in paths, so keep them to letters, digits, `-` and `_`.
+pub fn is_valid_profile_name(name: &str) -> bool {
+    !name.is_empty()
+        && name.len() <= 64
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+/// The default profile followed by every profile with a saved token, sorted.
+pub fn profile_names() -> Vec<String> {
+    let mut names: Vec<String> = state_dir()
+        .and_then(|dir| fs::read_dir(dir.join("profiles")).ok())
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.path().join("token.json").is_file())
+        .filter_map(|entry| entry.file_name().into_string().ok())
+        .filter(|name| name != DEFAULT_PROFILE && is_valid_profile_name(name))
+        .collect();
+    names.sort();
+    names.insert(0, DEFAULT_PROFILE.to_string());
+    names
+}
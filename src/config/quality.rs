@@ -0,0 +1,34 @@
+use serde::{Deserialize, Serialize};
+
+/// Which of the streams SoundCloud offers to prefer.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum StreamQuality {
+    /// AAC 160 kbps where there is one.
+    #[default]
+    High,
+    /// Up to 128 kbps, a track only offered above that is not played.
+    Normal,
+    /// The lowest bitrate on offer.
+    DataSaver,
+    /// MP3 only, nothing else is played.
+    Mp3Only,
+}
+
+pub const STREAM_QUALITIES: [StreamQuality; 4] = [
+    StreamQuality::High,
+    StreamQuality::Normal,
+    StreamQuality::DataSaver,
+    StreamQuality::Mp3Only,
+];
+
+impl StreamQuality {
+    pub fn label(self) -> &'static str {
+        match self {
+            StreamQuality::High => "high",
+            StreamQuality::Normal => "normal",
+            StreamQuality::DataSaver => "data saver",
+            StreamQuality::Mp3Only => "MP3 only",
+        }
+    }
+}
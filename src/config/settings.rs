@@ -0,0 +1,83 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::time::Duration;
+
+use super::equalizer::{EQ_BAND_COUNT, MAX_EQ_DB};
+use super::paths::{settings_path, write_private};
+use super::quality::StreamQuality;
+
+pub const MAX_CROSSFADE_SECS: u8 = 12;
+pub const MIN_TARGET_LUFS: i8 = -23;
+pub const MAX_TARGET_LUFS: i8 = -8;
+
+/// Playback settings changed from the settings overlay, kept between runs.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    /// How long the end of a track overlaps the start of the next, 0 for none.
+    pub crossfade_secs: u8,
+    /// Play every track at `target_lufs`.
+    pub normalize: bool,
+    pub target_lufs: i8,
+    /// Boost or cut of each equalizer band in dB.
+    pub eq_gains_db: [i8; EQ_BAND_COUNT],
+    /// Keep the pitch when playing faster or slower.
+    pub preserve_pitch: bool,
+    /// Once picked here it wins over `stream_quality` in `config.toml`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stream_quality: Option<StreamQuality>,
+    /// `stream_quality` from `config.toml`, played by until one is picked here.
+    #[serde(skip)]
+    pub default_stream_quality: StreamQuality,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            crossfade_secs: 0,
+            normalize: false,
+            target_lufs: -14,
+            eq_gains_db: [0; EQ_BAND_COUNT],
+            preserve_pitch: true,
+            stream_quality: None,
+            default_stream_quality: StreamQuality::default(),
+        }
+    }
+}
+
+impl Settings {
+    /// A missing or unreadable file gives the defaults.
+    pub fn load() -> Self {
+        settings_path()
+            .and_then(|path| fs::read_to_string(path).ok())
+            .and_then(|data| serde_json::from_str(&data).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let Some(path) = settings_path() else {
+            return Ok(());
+        };
+        write_private(&path, &serde_json::to_string_pretty(self)?)
+    }
+
+    pub fn crossfade(&self) -> Duration {
+        Duration::from_secs(self.crossfade_secs.min(MAX_CROSSFADE_SECS).into())
+    }
+
+    /// Target loudness in LUFS, `None` with normalization off.
+    pub fn normalization(&self) -> Option<f32> {
+        self.normalize
+            .then(|| self.target_lufs.clamp(MIN_TARGET_LUFS, MAX_TARGET_LUFS).into())
+    }
+
+    pub fn stream_quality(&self) -> StreamQuality {
+        self.stream_quality.unwrap_or(self.default_stream_quality)
+    }
+
+    pub fn equalizer(&self) -> [f32; EQ_BAND_COUNT] {
+        self.eq_gains_db
+            .map(|db| db.clamp(-MAX_EQ_DB, MAX_EQ_DB).into())
+    }
+}
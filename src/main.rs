@@ -1,46 +1,73 @@
-use std::sync::{Arc, Mutex, mpsc};
+use std::sync::{Arc, Mutex};
 mod api;
 mod auth;
+mod config;
+mod mock;
 mod player;
 mod tui;
 use player::Player;
 
 fn main() -> anyhow::Result<()> {
-    // try to load token, else start auth
-    let token = match auth::load_token() {
-        Some(token) => token,
-        None => auth::authenticate()?,
+    let args: Vec<String> = std::env::args().skip(1).collect();
+
+    // `--mock-server [ADDR]` only runs the fake API, for pointing other clients at it
+    if let Some(i) = args.iter().position(|a| a == "--mock-server") {
+        let addr = args
+            .get(i + 1)
+            .map(String::as_str)
+            .filter(|a| !a.starts_with("--"))
+            .unwrap_or("127.0.0.1:8787");
+        return mock::run(addr);
+    }
+
+    // `--profile NAME` picks which saved account to use, logging in if it has none
+    if let Some(i) = args.iter().position(|a| a == "--profile") {
+        let name = args
+            .get(i + 1)
+            .ok_or_else(|| anyhow::anyhow!("--profile needs a name"))?;
+        if !config::is_valid_profile_name(name) {
+            return Err(anyhow::anyhow!(
+                "profile names may only contain letters, digits, '-' and '_'"
+            ));
+        }
+        config::set_active_profile(name);
+    }
+
+    let token = if args.iter().any(|a| a == "--mock") {
+        let base = mock::spawn("127.0.0.1:0")?;
+        config::init_endpoints(config::Endpoints::local(&base));
+        mock::token()
+    } else {
+        let login = || {
+            let no_browser = args.iter().any(|a| a == "--no-browser");
+            auth::login(
+                &config::active_profile(),
+                auth::LoginOptions::interactive(no_browser),
+            )
+        };
+        // try to load token, else start auth
+        match auth::read_token(&config::active_profile()) {
+            Some(token) if !token.is_expired() => token,
+            Some(token) => match auth::refresh_token(&token) {
+                Ok(token) => token,
+                // start offline on the stale token, the TUI shows the saved library
+                Err(e) if api::ApiError::is_offline(&e) => token,
+                Err(_) => login()?,
+            },
+            None => login()?,
+        }
     };
 
     let token = Arc::new(Mutex::new(token));
-    let (reauth_tx, reauth_rx) = mpsc::channel();
 
-    auth::start_auto_refresh(Arc::clone(&token), reauth_tx.clone());
+    // a failed refresh expires the session, the TUI asks for a new login
+    auth::start_auto_refresh(Arc::clone(&token));
 
-    let mut api = Arc::new(Mutex::new(api::API::init(Arc::clone(&token))));
+    let api = api::API::init(Arc::clone(&token));
 
     let player = Player::new(Arc::clone(&token));
 
-    // spawn a thread to handle re-authentication requests
-    let token_clone = Arc::clone(&token);
-    let api_clone = Arc::clone(&api);
-    std::thread::spawn(move || {
-        for _ in reauth_rx {
-            // re-authenticate (this will block waiting for user interaction)
-            match auth::authenticate() {
-                Ok(new_token) => {
-                    *token_clone.lock().unwrap() = new_token;
-                    // update API with new token
-                    *api_clone.lock().unwrap() = api::API::init(Arc::clone(&token_clone));
-                }
-                Err(_) => {
-                    // re-authentication failed, will try again on next refresh failure
-                }
-            }
-        }
-    });
-
-    tui::run(&mut api, player).map_err(|e| anyhow::anyhow!(e))?;
+    tui::run(api, player).map_err(|e| anyhow::anyhow!(e))?;
 
     Ok(())
 }
@@ -0,0 +1,57 @@
+use std::f32::consts::PI;
+
+const SAMPLE_RATE: u32 = 22_050;
+
+pub(crate) const SEGMENT_MS: u64 = 5_000;
+
+/// Synthesises a mono 16-bit WAV sine tone so segments decode without any codec.
+pub(crate) fn tone_segment(freq_hz: f32, duration_ms: u64) -> Vec<u8> {
+    let frames = (SAMPLE_RATE as u64 * duration_ms / 1000) as u32;
+    let data_len = frames * 2;
+
+    let mut out = Vec::with_capacity(44 + data_len as usize);
+    out.extend_from_slice(b"RIFF");
+    out.extend_from_slice(&(36 + data_len).to_le_bytes());
+    out.extend_from_slice(b"WAVE");
+    out.extend_from_slice(b"fmt ");
+    out.extend_from_slice(&16u32.to_le_bytes());
+    out.extend_from_slice(&1u16.to_le_bytes()); // PCM
+    out.extend_from_slice(&1u16.to_le_bytes()); // mono
+    out.extend_from_slice(&SAMPLE_RATE.to_le_bytes());
+    out.extend_from_slice(&(SAMPLE_RATE * 2).to_le_bytes());
+    out.extend_from_slice(&2u16.to_le_bytes());
+    out.extend_from_slice(&16u16.to_le_bytes());
+    out.extend_from_slice(b"data");
+    out.extend_from_slice(&data_len.to_le_bytes());
+
+    for i in 0..frames {
+        let t = i as f32 / SAMPLE_RATE as f32;
+        let sample = (2.0 * PI * freq_hz * t).sin() * 0.25;
+        out.extend_from_slice(&((sample * i16::MAX as f32) as i16).to_le_bytes());
+    }
+
+    out
+}
+
+/// Media playlist splitting `duration_ms` into `SEGMENT_MS` chunks, no init segment.
+pub(crate) fn media_playlist(duration_ms: u64) -> String {
+    let mut out = String::from(
+        "#EXTM3U\n#EXT-X-VERSION:3\n#EXT-X-TARGETDURATION:5\n#EXT-X-MEDIA-SEQUENCE:0\n",
+    );
+    for (i, len) in segment_lengths(duration_ms).into_iter().enumerate() {
+        out.push_str(&format!("#EXTINF:{:.3},\n{}.wav\n", len as f64 / 1000.0, i));
+    }
+    out.push_str("#EXT-X-ENDLIST\n");
+    out
+}
+
+pub(crate) fn segment_lengths(duration_ms: u64) -> Vec<u64> {
+    let mut lengths = Vec::new();
+    let mut remaining = duration_ms.max(1);
+    while remaining > 0 {
+        let len = remaining.min(SEGMENT_MS);
+        lengths.push(len);
+        remaining -= len;
+    }
+    lengths
+}
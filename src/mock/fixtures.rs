@@ -0,0 +1,225 @@
+use serde_json::{Value, json};
+
+const USERS: &str = include_str!("fixtures/users.json");
+const TRACKS: &str = include_str!("fixtures/tracks.json");
+const PLAYLISTS: &str = include_str!("fixtures/playlists.json");
+
+pub(crate) const ME_URN: &str = "soundcloud:users:9000";
+
+/// Fixture data expanded into the shapes the real API returns, with every
+/// absolute URL pointing back at the mock server.
+pub(crate) struct Fixtures {
+    pub users: Vec<Value>,
+    pub tracks: Vec<Value>,
+    pub playlists: Vec<Value>,
+}
+
+impl Fixtures {
+    pub(crate) fn load(base: &str) -> Self {
+        let mut users: Vec<Value> = serde_json::from_str(USERS).expect("invalid users fixture");
+        users.push(json!({
+            "urn": ME_URN,
+            "username": "you",
+            "permalink_url": "https://soundcloud.com/you",
+            "avatar_url": null,
+            "city": "",
+            "country": "",
+            "description": "",
+            "followers_count": 3,
+            "followings_count": 3,
+            "track_count": 0,
+            "playlist_count": 2,
+            "public_favorites_count": 5,
+            "reposts_count": 0
+        }));
+        for user in &mut users {
+            user["kind"] = json!("user");
+        }
+
+        let mut tracks: Vec<Value> = serde_json::from_str(TRACKS).expect("invalid tracks fixture");
+        for track in &mut tracks {
+            let urn = track["urn"].as_str().unwrap_or_default().to_string();
+            track["kind"] = json!("track");
+            track["user"] = find_urn(&users, track["user_urn"].as_str().unwrap_or_default())
+                .cloned()
+                .unwrap_or(Value::Null);
+            track["stream_url"] = json!(format!("{base}/tracks/{urn}/stream"));
+        }
+
+        let mut playlists: Vec<Value> =
+            serde_json::from_str(PLAYLISTS).expect("invalid playlists fixture");
+        for playlist in &mut playlists {
+            let urn = playlist["urn"].as_str().unwrap_or_default().to_string();
+            let track_urns: Vec<String> = playlist["track_urns"]
+                .as_array()
+                .map(|a| a.iter().filter_map(|v| v.as_str().map(String::from)).collect())
+                .unwrap_or_default();
+            let duration: u64 = track_urns
+                .iter()
+                .filter_map(|u| find_urn(&tracks, u))
+                .filter_map(|t| t["duration"].as_u64())
+                .sum();
+
+            playlist["kind"] = json!("playlist");
+            playlist["user"] = find_urn(&users, playlist["user_urn"].as_str().unwrap_or_default())
+                .cloned()
+                .unwrap_or(Value::Null);
+            playlist["track_count"] = json!(track_urns.len());
+            playlist["duration"] = json!(duration);
+            playlist["tracks_uri"] = json!(format!("{base}/playlists/{urn}/tracks"));
+        }
+
+        Self {
+            users,
+            tracks,
+            playlists,
+        }
+    }
+
+    pub(crate) fn track(&self, urn: &str) -> Option<&Value> {
+        find_urn(&self.tracks, urn)
+    }
+
+    pub(crate) fn playlist(&self, urn: &str) -> Option<&Value> {
+        find_urn(&self.playlists, urn)
+    }
+
+    pub(crate) fn user(&self, urn: &str) -> Option<&Value> {
+        find_urn(&self.users, urn)
+    }
+
+    pub(crate) fn playlist_tracks(&self, urn: &str) -> Vec<Value> {
+        self.playlist(urn)
+            .and_then(|p| p["track_urns"].as_array())
+            .map(|urns| {
+                urns.iter()
+                    .filter_map(|u| u.as_str().and_then(|u| self.track(u)).cloned())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    pub(crate) fn user_tracks(&self, urn: &str) -> Vec<Value> {
+        self.tracks
+            .iter()
+            .filter(|t| t["user_urn"] == urn)
+            .cloned()
+            .collect()
+    }
+
+    /// Every other fixture track, starting with the seed artist's own.
+    pub(crate) fn related_tracks(&self, urn: &str) -> Vec<Value> {
+        let Some(seed) = self.track(urn) else {
+            return Vec::new();
+        };
+        let mut related: Vec<Value> = self
+            .tracks
+            .iter()
+            .filter(|t| t["urn"] != seed["urn"])
+            .cloned()
+            .collect();
+        related.sort_by_key(|t| t["user_urn"] != seed["user_urn"]);
+        related
+    }
+
+    /// Posts of every track and liked playlist, with the first track also reposted,
+    /// newest first.
+    pub(crate) fn activities(&self) -> Vec<Value> {
+        let tracks = self.tracks.iter().map(|t| ("track", t));
+        let playlists = self.liked_playlists();
+        let playlists = playlists.iter().map(|p| ("playlist", p));
+        let reposts = self.tracks.iter().take(1).map(|t| ("track-repost", t));
+        let mut activities: Vec<Value> = tracks
+            .chain(playlists)
+            .chain(reposts)
+            .map(|(kind, origin)| {
+                json!({
+                    "type": kind,
+                    "created_at": origin["created_at"],
+                    "origin": origin,
+                })
+            })
+            .collect();
+        activities.sort_by(|a, b| b["created_at"].as_str().cmp(&a["created_at"].as_str()));
+        activities
+    }
+
+    pub(crate) fn user_playlists(&self, urn: &str) -> Vec<Value> {
+        self.playlists
+            .iter()
+            .filter(|p| p["user_urn"] == urn)
+            .cloned()
+            .collect()
+    }
+
+    /// Playlists owned by the logged-in user.
+    pub(crate) fn my_playlists(&self) -> Vec<Value> {
+        self.playlists
+            .iter()
+            .filter(|p| p["owned"].as_bool().unwrap_or(false))
+            .cloned()
+            .collect()
+    }
+
+    /// Playlists and albums the logged-in user has liked.
+    pub(crate) fn liked_playlists(&self) -> Vec<Value> {
+        self.playlists
+            .iter()
+            .filter(|p| !p["owned"].as_bool().unwrap_or(false))
+            .cloned()
+            .collect()
+    }
+
+    /// A few timed comments spread over the track, posted by the other fixture users.
+    pub(crate) fn track_comments(&self, urn: &str) -> Vec<Value> {
+        let Some(track) = self.track(urn) else {
+            return Vec::new();
+        };
+        let duration = track["duration"].as_u64().unwrap_or(0);
+        let bodies = ["love this intro", "the drop here!!", "perfect ending"];
+        self.followings()
+            .into_iter()
+            .zip(bodies)
+            .zip([10, 45, 85])
+            .enumerate()
+            .map(|(i, ((user, body), percent))| {
+                json!({
+                    "kind": "comment",
+                    "urn": format!("soundcloud:comments:{}{}", urn.rsplit(':').next().unwrap_or(""), i),
+                    "body": body,
+                    "timestamp": duration * percent / 100,
+                    "created_at": "2024/06/02 12:00:00 +0000",
+                    "user": user,
+                })
+            })
+            .collect()
+    }
+
+    pub(crate) fn followings(&self) -> Vec<Value> {
+        self.users
+            .iter()
+            .filter(|u| u["urn"] != ME_URN)
+            .cloned()
+            .collect()
+    }
+}
+
+/// Matches a full urn or just its numeric id, the API accepts both in paths.
+fn find_urn<'a>(items: &'a [Value], urn: &str) -> Option<&'a Value> {
+    items.iter().find(|v| {
+        v["urn"]
+            .as_str()
+            .is_some_and(|u| u == urn || u.rsplit(':').next() == Some(urn))
+    })
+}
+
+/// Case-insensitive match against the given string fields, used for search routes.
+pub(crate) fn matches_query(item: &Value, query: &str, fields: &[&str]) -> bool {
+    let query = query.to_lowercase();
+    query.is_empty()
+        || fields.iter().any(|f| {
+            item[*f]
+                .as_str()
+                .is_some_and(|s| s.to_lowercase().contains(&query))
+        })
+}
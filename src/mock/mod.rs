@@ -0,0 +1,358 @@
+//! A local stand-in for the SoundCloud API, serving fixture data and
+//! synthesised HLS audio so the TUI can be run without credentials.
+
+mod audio;
+mod fixtures;
+
+use std::io::Cursor;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anyhow::anyhow;
+use serde_json::{Value, json};
+use tiny_http::{Header, Method, Request, Response, Server};
+use url::Url;
+
+use crate::auth::Token;
+use crate::config::DEFAULT_PROFILE;
+use fixtures::{Fixtures, ME_URN, matches_query};
+
+const DEFAULT_PAGE_LIMIT: usize = 50;
+
+/// Ids handed out to created playlists and comments.
+static NEXT_ID: AtomicU64 = AtomicU64::new(3100);
+
+/// Starts the mock server on a background thread and returns its base url.
+pub fn spawn(addr: &str) -> anyhow::Result<String> {
+    let (server, base) = bind(addr)?;
+    let thread_base = base.clone();
+    std::thread::spawn(move || serve(server, &thread_base));
+    Ok(base)
+}
+
+/// Runs the mock server on the current thread until the process is killed.
+pub fn run(addr: &str) -> anyhow::Result<()> {
+    let (server, base) = bind(addr)?;
+    println!("mock SoundCloud API listening on {}", base);
+    println!("point sctui at it with SOUNDCLOUD_API_BASE={base} SOUNDCLOUD_AUTH_BASE={base}");
+    serve(server, &base);
+    Ok(())
+}
+
+/// A token accepted by the mock server, never written to disk.
+pub fn token() -> Token {
+    Token {
+        access_token: "mock".to_string(),
+        refresh_token: "mock".to_string(),
+        obtained_at: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0),
+        profile: DEFAULT_PROFILE.to_string(),
+    }
+}
+
+fn bind(addr: &str) -> anyhow::Result<(Server, String)> {
+    let server = Server::http(addr).map_err(|e| anyhow!("failed to start mock server: {}", e))?;
+    let local = server
+        .server_addr()
+        .to_ip()
+        .ok_or_else(|| anyhow!("mock server is not listening on an ip address"))?;
+    Ok((server, format!("http://{}", local)))
+}
+
+fn serve(server: Server, base: &str) {
+    let fixtures = Fixtures::load(base);
+    for mut request in server.incoming_requests() {
+        // read the whole body so keep-alive connections stay in sync
+        let mut body = String::new();
+        let _ = request.as_reader().read_to_string(&mut body);
+        let response = route(&fixtures, base, &request, &body);
+        let _ = request.respond(response);
+    }
+}
+
+fn route(
+    fixtures: &Fixtures,
+    base: &str,
+    request: &Request,
+    body: &str,
+) -> Response<Cursor<Vec<u8>>> {
+    let Ok(url) = Url::parse(&format!("{}{}", base, request.url())) else {
+        return not_found();
+    };
+    let segments: Vec<&str> = url.path().trim_matches('/').split('/').collect();
+    let method = request.method();
+
+    match (method, segments.as_slice()) {
+        (Method::Get, ["authorize"]) => authorize(&url),
+        (Method::Post, ["oauth", "token"]) => json_response(&json!({
+            "access_token": "mock",
+            "refresh_token": "mock",
+            "expires_in": 3599,
+            "scope": "",
+            "token_type": "bearer"
+        })),
+
+        (Method::Get, ["me"]) => fixtures.user(ME_URN).map(json_response).unwrap_or_else(not_found),
+        (Method::Get, ["me", "likes", "tracks"]) => page(base, &url, fixtures.tracks.clone()),
+        (Method::Get, ["me", "playlists"]) => page(base, &url, fixtures.my_playlists()),
+        (Method::Get, ["me", "likes", "playlists"]) => page(base, &url, fixtures.liked_playlists()),
+        (Method::Get, ["me", "followings"]) => page(base, &url, fixtures.followings()),
+        (Method::Get, ["me", "activities"]) => page(base, &url, fixtures.activities()),
+        (Method::Get, ["me", "reposts", "tracks"]) => {
+            page(base, &url, fixtures.tracks.iter().skip(2).take(1).cloned().collect())
+        }
+        (Method::Get, ["me", "reposts", "playlists"]) => page(base, &url, fixtures.liked_playlists()),
+
+        (Method::Get, ["playlists", urn, "tracks"]) => {
+            page(base, &url, fixtures.playlist_tracks(urn))
+        }
+        (Method::Get, ["playlists", urn]) => {
+            fixtures.playlist(urn).map(json_response).unwrap_or_else(not_found)
+        }
+        (Method::Get, ["users", urn, "tracks"]) => page(base, &url, fixtures.user_tracks(urn)),
+        (Method::Get, ["users", urn, "playlists"]) => {
+            page(base, &url, fixtures.user_playlists(urn))
+        }
+        (Method::Get, ["users", _, "reposts", "tracks"]) => {
+            page(base, &url, fixtures.tracks.iter().skip(1).take(2).cloned().collect())
+        }
+        (Method::Get, ["users", _, "likes", "tracks"]) => {
+            page(base, &url, fixtures.tracks.iter().take(2).cloned().collect())
+        }
+        (Method::Get, ["users", urn]) => {
+            fixtures.user(urn).map(json_response).unwrap_or_else(not_found)
+        }
+
+        (Method::Get, ["tracks", urn, "streams"]) => match fixtures.track(urn) {
+            Some(_) => {
+                let playlist = format!("{base}/hls/{urn}/playlist.m3u8");
+                json_response(&json!({
+                    "hls_aac_160_url": playlist,
+                    "hls_aac_96_url": playlist,
+                    "hls_mp3_128_url": playlist,
+                }))
+            }
+            None => not_found(),
+        },
+        (Method::Get, ["tracks", urn, "comments"]) => {
+            page(base, &url, fixtures.track_comments(urn))
+        }
+        (Method::Post, ["tracks", urn, "comments"]) => post_comment(fixtures, urn, body),
+        (Method::Get, ["tracks", urn, "related"]) => {
+            page(base, &url, fixtures.related_tracks(urn))
+        }
+        (Method::Get, ["tracks", urn]) => {
+            fixtures.track(urn).map(json_response).unwrap_or_else(not_found)
+        }
+
+        (Method::Get, ["tracks"]) => {
+            let q = query_param(&url, "q").unwrap_or_default();
+            let items = fixtures
+                .tracks
+                .iter()
+                .filter(|t| matches_query(t, &q, &["title", "metadata_artist", "genre"]))
+                .cloned()
+                .collect();
+            page(base, &url, items)
+        }
+        (Method::Get, ["playlists"]) => {
+            let q = query_param(&url, "q").unwrap_or_default();
+            let items = fixtures
+                .playlists
+                .iter()
+                .filter(|p| matches_query(p, &q, &["title"]))
+                .cloned()
+                .collect();
+            page(base, &url, items)
+        }
+        (Method::Get, ["users"]) => {
+            let q = query_param(&url, "q").unwrap_or_default();
+            let items = fixtures
+                .users
+                .iter()
+                .filter(|u| u["urn"] != ME_URN && matches_query(u, &q, &["username"]))
+                .cloned()
+                .collect();
+            page(base, &url, items)
+        }
+
+        (Method::Get, ["hls", urn, "playlist.m3u8"]) => match fixtures.track(urn) {
+            Some(track) => {
+                let duration_ms = track["duration"].as_u64().unwrap_or(audio::SEGMENT_MS);
+                Response::from_string(audio::media_playlist(duration_ms)).with_header(
+                    content_type("application/vnd.apple.mpegurl"),
+                )
+            }
+            None => not_found(),
+        },
+        (Method::Get, ["hls", urn, segment]) => {
+            let Some(track) = fixtures.track(urn) else {
+                return not_found();
+            };
+            let Some(index) = segment
+                .strip_suffix(".wav")
+                .and_then(|s| s.parse::<usize>().ok())
+            else {
+                return not_found();
+            };
+            let duration_ms = track["duration"].as_u64().unwrap_or(audio::SEGMENT_MS);
+            match audio::segment_lengths(duration_ms).get(index) {
+                Some(&len) => Response::from_data(audio::tone_segment(tone_for(fixtures, urn), len))
+                    .with_header(content_type("audio/wav")),
+                None => not_found(),
+            }
+        }
+
+        (Method::Post, ["playlists"]) => create_playlist(fixtures, base, body),
+        (Method::Put, ["playlists", urn]) => match fixtures.playlist(urn) {
+            Some(playlist) => json_response(&edited_playlist(fixtures, playlist.clone(), body)),
+            None => not_found(),
+        },
+
+        (Method::Get, _) => page(base, &url, Vec::new()),
+        // other writes (likes, follows, deletes) are accepted and ignored
+        _ => json_response(&json!({})),
+    }
+}
+
+/// Echoes the comment back as if the logged-in user had posted it.
+fn post_comment(fixtures: &Fixtures, urn: &str, body: &str) -> Response<Cursor<Vec<u8>>> {
+    let body: Value = serde_json::from_str(body).unwrap_or(Value::Null);
+    let comment = &body["comment"];
+    json_response(&json!({
+        "kind": "comment",
+        "urn": format!("soundcloud:comments:{}", NEXT_ID.fetch_add(1, Ordering::Relaxed)),
+        "body": comment["body"].as_str().unwrap_or_default(),
+        "timestamp": comment["timestamp"].as_u64().unwrap_or(0),
+        "created_at": now(),
+        "track_urn": urn,
+        "user": fixtures.user(ME_URN).cloned().unwrap_or(Value::Null),
+    }))
+}
+
+/// Echoes the new playlist back with a fresh urn, nothing is stored.
+fn create_playlist(fixtures: &Fixtures, base: &str, body: &str) -> Response<Cursor<Vec<u8>>> {
+    let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+    let urn = format!("soundcloud:playlists:{}", id);
+    let playlist = json!({
+        "kind": "playlist",
+        "urn": urn,
+        "title": "Untitled",
+        "playlist_type": "PLAYLIST",
+        "sharing": "public",
+        "user": fixtures.user(ME_URN).cloned().unwrap_or(Value::Null),
+        "created_at": now(),
+        "artwork_url": null,
+        "permalink_url": format!("https://soundcloud.com/you/sets/{}", id),
+        "track_count": 0,
+        "duration": 0,
+        "tracks_uri": format!("{base}/playlists/{urn}/tracks"),
+    });
+    json_response(&edited_playlist(fixtures, playlist, body))
+}
+
+/// Applies the title, sharing and tracks of a create or update body to a playlist,
+/// the way the API echoes them back.
+fn edited_playlist(fixtures: &Fixtures, mut playlist: Value, body: &str) -> Value {
+    let body: Value = serde_json::from_str(body).unwrap_or(Value::Null);
+    let changes = &body["playlist"];
+    for field in ["title", "sharing"] {
+        if let Some(value) = changes[field].as_str() {
+            playlist[field] = json!(value);
+        }
+    }
+    if let Some(tracks) = changes["tracks"].as_array() {
+        let tracks: Vec<&Value> = tracks
+            .iter()
+            .filter_map(|t| fixtures.track(t["urn"].as_str()?))
+            .collect();
+        let duration: u64 = tracks.iter().filter_map(|t| t["duration"].as_u64()).sum();
+        playlist["track_urns"] = tracks.iter().map(|t| t["urn"].clone()).collect();
+        playlist["track_count"] = json!(tracks.len());
+        playlist["duration"] = json!(duration);
+    }
+    playlist
+}
+
+/// The current time in the API's date format.
+fn now() -> String {
+    chrono::Utc::now().format("%Y/%m/%d %H:%M:%S +0000").to_string()
+}
+
+/// Each fixture track gets its own pitch so track changes are audible.
+fn tone_for(fixtures: &Fixtures, urn: &str) -> f32 {
+    let index = fixtures
+        .tracks
+        .iter()
+        .position(|t| t["urn"] == urn)
+        .unwrap_or(0);
+    220.0 * 2f32.powf(index as f32 * 3.0 / 12.0)
+}
+
+fn authorize(url: &Url) -> Response<Cursor<Vec<u8>>> {
+    let Some(redirect_uri) = query_param(url, "redirect_uri") else {
+        return Response::from_string("missing redirect_uri").with_status_code(400);
+    };
+    let state = query_param(url, "state").unwrap_or_default();
+    let Ok(mut location) = Url::parse(&redirect_uri) else {
+        return Response::from_string("invalid redirect_uri").with_status_code(400);
+    };
+    location
+        .query_pairs_mut()
+        .append_pair("code", "mock")
+        .append_pair("state", &state);
+
+    Response::from_string("")
+        .with_status_code(302)
+        .with_header(Header::from_bytes("Location", location.as_str()).unwrap())
+}
+
+/// Serves `items` with the `linked_partitioning` shape, honouring `limit` and `offset`.
+fn page(base: &str, url: &Url, items: Vec<Value>) -> Response<Cursor<Vec<u8>>> {
+    let limit = query_param(url, "limit")
+        .and_then(|v| v.parse::<usize>().ok())
+        .filter(|&v| v > 0)
+        .unwrap_or(DEFAULT_PAGE_LIMIT);
+    let offset = query_param(url, "offset")
+        .and_then(|v| v.parse::<usize>().ok())
+        .unwrap_or(0);
+
+    let end = (offset + limit).min(items.len());
+    let collection: Vec<Value> = items.get(offset..end).map(<[Value]>::to_vec).unwrap_or_default();
+
+    let next_href = if end < items.len() {
+        let mut next = Url::parse(&format!("{}{}", base, url.path())).unwrap();
+        {
+            let mut pairs = next.query_pairs_mut();
+            for (k, v) in url.query_pairs().filter(|(k, _)| k != "offset") {
+                pairs.append_pair(&k, &v);
+            }
+            pairs.append_pair("offset", &end.to_string());
+        }
+        Value::String(next.to_string())
+    } else {
+        Value::Null
+    };
+
+    json_response(&json!({ "collection": collection, "next_href": next_href }))
+}
+
+fn query_param(url: &Url, key: &str) -> Option<String> {
+    url.query_pairs()
+        .find(|(k, _)| k == key)
+        .map(|(_, v)| v.into_owned())
+}
+
+fn json_response(value: &Value) -> Response<Cursor<Vec<u8>>> {
+    Response::from_string(value.to_string()).with_header(content_type("application/json"))
+}
+
+fn not_found() -> Response<Cursor<Vec<u8>>> {
+    json_response(&json!({ "error": "not found" })).with_status_code(404)
+}
+
+fn content_type(value: &str) -> Header {
+    Header::from_bytes("Content-Type", value).unwrap()
+}
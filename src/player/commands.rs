@@ -1,15 +1,69 @@
 use crate::api::Track;
+use crate::config::StreamQuality;
 
 pub enum PlayerCommand {
     Play(Track),
     PlayFromPosition(Track, u64),
     PreloadNext(Track),
+    /// Forgets the preloaded track, nothing is to follow the playing one.
+    ClearPreload,
     Pause,
     Resume,
     VolumeUp,
     VolumeDown,
-    NextSong,
-    PrevSong,
-    FastForward,
-    Rewind,
+    Seek(SeekTarget),
+    /// Applies the rate and pitch mode set on `Player` to the playing sink.
+    SetSpeed,
+    /// Picks streams by this preference, replaying what is playing if it changed.
+    SetStreamQuality(StreamQuality),
+}
+
+/// Where a seek lands in the current track.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SeekTarget {
+    /// Milliseconds from the start.
+    To(u64),
+    /// Milliseconds from the current position, negative to go back.
+    By(i64),
+    /// Percent of the track's length.
+    Percent(u8),
+}
+
+impl SeekTarget {
+    /// Position in ms this lands on when at `elapsed_ms` of a `duration_ms` long track.
+    pub(crate) fn resolve(self, elapsed_ms: u64, duration_ms: u64) -> u64 {
+        match self {
+            SeekTarget::To(ms) => ms,
+            SeekTarget::By(delta) => elapsed_ms.saturating_add_signed(delta),
+            SeekTarget::Percent(p) => duration_ms * u64::from(p.min(100)) / 100,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SeekTarget;
+
+    #[test]
+    fn absolute_targets_land_where_asked() {
+        assert_eq!(SeekTarget::To(42_000).resolve(10_000, 180_000), 42_000);
+        assert_eq!(SeekTarget::To(0).resolve(10_000, 180_000), 0);
+    }
+
+    #[test]
+    fn relative_targets_move_from_the_position() {
+        assert_eq!(SeekTarget::By(5_000).resolve(10_000, 180_000), 15_000);
+        assert_eq!(SeekTarget::By(-5_000).resolve(10_000, 180_000), 5_000);
+        // going back past the start stops at it
+        assert_eq!(SeekTarget::By(-30_000).resolve(10_000, 180_000), 0);
+    }
+
+    #[test]
+    fn percent_targets_scale_the_duration() {
+        assert_eq!(SeekTarget::Percent(50).resolve(0, 180_000), 90_000);
+        assert_eq!(SeekTarget::Percent(0).resolve(60_000, 180_000), 0);
+        assert_eq!(SeekTarget::Percent(100).resolve(0, 180_000), 180_000);
+        assert_eq!(SeekTarget::Percent(250).resolve(0, 180_000), 180_000);
+        assert_eq!(SeekTarget::Percent(50).resolve(0, 0), 0);
+    }
 }
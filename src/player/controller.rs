@@ -1,47 +1,64 @@
 use crate::api::Track;
 use crate::auth::Token;
-use rodio::Sink;
+use crate::config::{EQ_BAND_COUNT, StreamQuality};
 use std::collections::VecDeque;
 use std::sync::{
     Arc, Mutex,
-    atomic::{AtomicBool, Ordering},
-    mpsc::{self, Sender},
+    atomic::{AtomicBool, AtomicU64, Ordering},
+    mpsc::{self, Receiver, Sender},
 };
 use std::thread;
-use std::time::{Duration, Instant};
+use std::time::Duration;
 
-use super::commands::PlayerCommand;
+use super::PlayerEvent;
+use super::commands::{PlayerCommand, SeekTarget};
+use super::stream::{
+    EqualizerControl, LoudnessControl, PlaybackClock, SpeedControl, StreamVariant,
+};
 use super::worker::player_loop;
 
 pub struct Player {
     tx: Sender<PlayerCommand>,
+    events: Receiver<PlayerEvent>,
     is_playing_flag: Arc<AtomicBool>,
     is_seeking_flag: Arc<AtomicBool>,
-    elapsed_time: Arc<Mutex<Duration>>,
-    last_start: Arc<Mutex<Option<Instant>>>,
+    clock: Arc<PlaybackClock>,
+    crossfade_ms: Arc<AtomicU64>,
+    loudness: Arc<LoudnessControl>,
+    equalizer: Arc<EqualizerControl>,
+    speed: Arc<SpeedControl>,
     current_track: Arc<Mutex<Option<Track>>>,
-    sink: Arc<Mutex<Option<Sink>>>,
+    stream_quality: Mutex<StreamQuality>,
+    stream_variant: Arc<Mutex<Option<StreamVariant>>>,
     wave_buffer: Arc<Mutex<VecDeque<f32>>>,
 }
 
 impl Player {
     pub fn new(token: Arc<Mutex<Token>>) -> Self {
         let (tx, rx) = mpsc::channel();
+        let (tx_events, events) = mpsc::channel();
         let is_playing_flag = Arc::new(AtomicBool::new(false));
         let is_seeking_flag = Arc::new(AtomicBool::new(false));
         let sink = Arc::new(Mutex::new(None));
-        let elapsed_time = Arc::new(Mutex::new(Duration::ZERO));
-        let last_start = Arc::new(Mutex::new(None));
+        let clock = Arc::new(PlaybackClock::new());
+        let crossfade_ms = Arc::new(AtomicU64::new(0));
+        let loudness = Arc::new(LoudnessControl::new());
+        let equalizer = Arc::new(EqualizerControl::new());
+        let speed = Arc::new(SpeedControl::new());
         let current_track = Arc::new(Mutex::new(None));
+        let stream_variant = Arc::new(Mutex::new(None));
         let wave_buffer = Arc::new(Mutex::new(VecDeque::new()));
 
         {
             let flag_clone = Arc::clone(&is_playing_flag);
-            let sink_clone = Arc::clone(&sink);
             let token_clone = Arc::clone(&token);
-            let elapsed_clone = Arc::clone(&elapsed_time);
-            let last_start_clone = Arc::clone(&last_start);
+            let clock_clone = Arc::clone(&clock);
+            let crossfade_clone = Arc::clone(&crossfade_ms);
+            let loudness_clone = Arc::clone(&loudness);
+            let equalizer_clone = Arc::clone(&equalizer);
+            let speed_clone = Arc::clone(&speed);
             let track_clone = Arc::clone(&current_track);
+            let variant_clone = Arc::clone(&stream_variant);
             let seeking_clone = Arc::clone(&is_seeking_flag);
             let wave_buffer_clone = Arc::clone(&wave_buffer);
 
@@ -51,10 +68,15 @@ impl Player {
                     token_clone,
                     flag_clone,
                     seeking_clone,
-                    sink_clone,
-                    elapsed_clone,
-                    last_start_clone,
+                    sink,
+                    clock_clone,
+                    crossfade_clone,
+                    loudness_clone,
+                    equalizer_clone,
+                    speed_clone,
+                    tx_events,
                     track_clone,
+                    variant_clone,
                     wave_buffer_clone,
                 );
             });
@@ -62,12 +84,17 @@ impl Player {
 
         Self {
             tx,
+            events,
             is_playing_flag,
             is_seeking_flag,
-            elapsed_time,
-            last_start,
+            clock,
+            crossfade_ms,
+            loudness,
+            equalizer,
+            speed,
             current_track,
-            sink,
+            stream_quality: Mutex::new(StreamQuality::default()),
+            stream_variant,
             wave_buffer,
         }
     }
@@ -92,26 +119,70 @@ impl Player {
         let _ = self.tx.send(PlayerCommand::VolumeDown);
     }
 
-    pub fn next_song(&self) {
-        let _ = self.tx.send(PlayerCommand::NextSong);
+    pub fn seek(&self, target: SeekTarget) {
+        let _ = self.tx.send(PlayerCommand::Seek(target));
+    }
+
+    /// Overlap for track changes near the end of a track, zero for a hard cut.
+    pub fn set_crossfade(&self, crossfade: Duration) {
+        self.crossfade_ms
+            .store(crossfade.as_millis() as u64, Ordering::Relaxed);
+    }
+
+    /// Target loudness every track is brought to, `None` to play them as they are.
+    pub fn set_normalization(&self, target_lufs: Option<f32>) {
+        self.loudness.set_target(target_lufs);
+    }
+
+    /// Boost or cut of each equalizer band in dB, applied to what is playing.
+    pub fn set_equalizer(&self, gains_db: [f32; EQ_BAND_COUNT]) {
+        self.equalizer.set_gains(gains_db);
+    }
+
+    /// Playback rate, clamped to 0.5x-2x. Positions stay in track time.
+    pub fn set_speed(&self, speed: f32) {
+        self.speed.set_speed(speed);
+        let _ = self.tx.send(PlayerCommand::SetSpeed);
     }
 
-    pub fn prev_song(&self) {
-        let _ = self.tx.send(PlayerCommand::PrevSong);
+    pub fn speed(&self) -> f32 {
+        self.speed.speed()
     }
 
-    pub fn fast_forward(&self) {
-        let _ = self.tx.send(PlayerCommand::FastForward);
+    /// Whether a speed other than 1x keeps the pitch instead of shifting it.
+    pub fn set_preserve_pitch(&self, preserve_pitch: bool) {
+        if self.speed.set_preserve_pitch(preserve_pitch) {
+            let _ = self.tx.send(PlayerCommand::SetSpeed);
+        }
     }
 
-    pub fn rewind(&self) {
-        let _ = self.tx.send(PlayerCommand::Rewind);
+    /// Which of a track's streams to prefer. Switching replays what is playing.
+    pub fn set_stream_quality(&self, quality: StreamQuality) {
+        let mut current = self.stream_quality.lock().unwrap();
+        if *current != quality {
+            *current = quality;
+            let _ = self.tx.send(PlayerCommand::SetStreamQuality(quality));
+        }
+    }
+
+    /// Codec and bitrate of the stream playing, e.g. "AAC 160 kbps".
+    pub fn stream_label(&self) -> Option<&'static str> {
+        self.stream_variant.lock().unwrap().map(|v| v.label())
     }
 
     pub fn preload_next(&self, track: Track) {
         let _ = self.tx.send(PlayerCommand::PreloadNext(track));
     }
 
+    pub fn clear_preload(&self) {
+        let _ = self.tx.send(PlayerCommand::ClearPreload);
+    }
+
+    /// The next thing the player did on its own, if any.
+    pub fn next_event(&self) -> Option<PlayerEvent> {
+        self.events.try_recv().ok()
+    }
+
     pub fn is_playing(&self) -> bool {
         self.is_playing_flag.load(Ordering::SeqCst)
     }
@@ -120,14 +191,14 @@ impl Player {
         self.is_seeking_flag.load(Ordering::SeqCst)
     }
 
+    /// Position in the current track in ms, as far as the output has played it.
     pub fn elapsed(&self) -> u64 {
-        let mut elapsed = *self.elapsed_time.lock().unwrap();
-        if self.is_playing() {
-            if let Some(start) = *self.last_start.lock().unwrap() {
-                elapsed += start.elapsed();
-            }
-        }
-        elapsed.as_millis().try_into().unwrap()
+        self.clock.position_ms()
+    }
+
+    /// Downloads keep failing and are retried, what was already loaded plays on.
+    pub fn is_reconnecting(&self) -> bool {
+        self.is_playing() && self.clock.is_reconnecting()
     }
 
     pub fn current_track(&self) -> Track {
@@ -137,25 +208,13 @@ impl Player {
             .clone()
             .unwrap_or_else(|| Track {
                 title: "No Track Playing - Press <ENTER> on Something to Play!".to_string(),
-                artists: "N/A".to_string(),
-                duration: "0:00".to_string(),
+                metadata_artist: "N/A".to_string(),
                 duration_ms: 1,
-                playback_count: "0".to_string(),
-                artwork_url: "".to_string(),
-                stream_url: "".to_string(),
                 access: "playable".to_string(),
-                track_urn: "".to_string(),
+                ..Default::default()
             })
     }
 
-    pub fn get_volume(&self) -> f32 {
-        if let Some(ref s) = *self.sink.lock().unwrap() {
-            s.volume()
-        } else {
-            1.0
-        }
-    }
-
     pub fn wave_buffer(&self) -> Arc<Mutex<VecDeque<f32>>> {
         Arc::clone(&self.wave_buffer)
     }
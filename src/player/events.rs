@@ -0,0 +1,18 @@
+/// Something the player did on its own, for the UI to catch up with.
+pub enum PlayerEvent {
+    /// A track began playing, not sent for seeks within it.
+    Started,
+    /// Playback ran from the end of one track straight into the preloaded one.
+    TrackAdvanced,
+    /// The output ran out of audio before the track was fully loaded.
+    Buffering,
+    /// Audio is coming out again after `Buffering`.
+    Resumed,
+    /// Time to preload the next track, early enough for a crossfade into it.
+    NearEnd,
+    /// The track played out, or got to where the crossfade into the next starts.
+    Ended,
+    /// A track or position could not be played.
+    Error { reason: String },
+    VolumeChanged(f32),
+}
@@ -1,8 +1,10 @@
 mod commands;
 mod controller;
+mod events;
 mod stream;
 mod worker;
 
 #[allow(unused_imports)]
-pub use commands::PlayerCommand;
+pub use commands::{PlayerCommand, SeekTarget};
 pub use controller::Player;
+pub use events::PlayerEvent;
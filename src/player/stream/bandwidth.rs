@@ -0,0 +1,39 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+/// Downloads it takes before the estimate is trusted.
+const MIN_SAMPLES: u64 = 3;
+/// A stream needs this much more bandwidth than its bitrate to stay ahead.
+const HEADROOM: f64 = 1.5;
+
+/// How fast segments come in, smoothed over the last few downloads. Shared
+/// by every pump so the next track starts at a bitrate the connection can take.
+pub(crate) struct Bandwidth {
+    kbps: AtomicU64,
+    samples: AtomicU64,
+}
+
+impl Bandwidth {
+    pub(crate) fn new() -> Self {
+        Self {
+            kbps: AtomicU64::new(0),
+            samples: AtomicU64::new(0),
+        }
+    }
+
+    pub(crate) fn record(&self, bytes: usize, elapsed: Duration) {
+        let ms = elapsed.as_millis().max(1) as u64;
+        let sample = bytes as u64 * 8 / ms;
+        let estimate = match self.samples.fetch_add(1, Ordering::Relaxed) {
+            0 => sample,
+            _ => (self.kbps.load(Ordering::Relaxed) * 2 + sample) / 3,
+        };
+        self.kbps.store(estimate, Ordering::Relaxed);
+    }
+
+    /// Whether the connection can't be relied on to keep up with `kbps`.
+    pub(crate) fn too_slow_for(&self, kbps: u64) -> bool {
+        self.samples.load(Ordering::Relaxed) >= MIN_SAMPLES
+            && (self.kbps.load(Ordering::Relaxed) as f64) < kbps as f64 * HEADROOM
+    }
+}
@@ -0,0 +1,59 @@
+use std::f64::consts::PI;
+
+/// One second-order section, direct form I.
+#[derive(Clone)]
+pub(crate) struct Biquad {
+    b: [f64; 3],
+    /// `a1` and `a2`, everything already divided by `a0`.
+    a: [f64; 2],
+    x: [f64; 2],
+    y: [f64; 2],
+}
+
+impl Biquad {
+    pub(crate) fn new(b: [f64; 3], a: [f64; 2]) -> Self {
+        Self {
+            b,
+            a,
+            x: [0.0; 2],
+            y: [0.0; 2],
+        }
+    }
+
+    /// Boosts or cuts `gain_db` around `freq`, as in the RBJ audio EQ cookbook.
+    pub(crate) fn peaking(rate: f64, freq: f64, q: f64, gain_db: f64) -> Self {
+        let a = 10f64.powf(gain_db / 40.0);
+        let w0 = 2.0 * PI * freq / rate;
+        let alpha = w0.sin() / (2.0 * q);
+        let cos = w0.cos();
+        let a0 = 1.0 + alpha / a;
+        Self::new(
+            [
+                (1.0 + alpha * a) / a0,
+                -2.0 * cos / a0,
+                (1.0 - alpha * a) / a0,
+            ],
+            [-2.0 * cos / a0, (1.0 - alpha / a) / a0],
+        )
+    }
+
+    /// Takes the response of `other` but keeps its own state, so the change doesn't click.
+    pub(crate) fn retune(&mut self, other: &Biquad) {
+        self.b = other.b;
+        self.a = other.a;
+    }
+
+    pub(crate) fn reset(&mut self) {
+        self.x = [0.0; 2];
+        self.y = [0.0; 2];
+    }
+
+    pub(crate) fn process(&mut self, x: f64) -> f64 {
+        let y = self.b[0] * x + self.b[1] * self.x[0] + self.b[2] * self.x[1]
+            - self.a[0] * self.y[0]
+            - self.a[1] * self.y[1];
+        self.x = [x, self.x[0]];
+        self.y = [y, self.y[0]];
+        y
+    }
+}
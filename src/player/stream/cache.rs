@@ -1,9 +1,10 @@
 use std::collections::{HashMap, VecDeque};
 use std::sync::{Arc, Mutex};
 use std::time::Instant;
+use url::Url;
 
 use crate::api::Track;
-use crate::player::stream::hls::HlsManifest;
+use crate::player::stream::hls::{HlsManifest, StreamVariant};
 
 pub(crate) const SEGMENT_CACHE_CAP: usize = 12;
 pub(crate) const HLS_CACHE_TTL: std::time::Duration = std::time::Duration::from_secs(30 * 60);
@@ -33,6 +34,11 @@ impl SegmentCache {
         bytes
     }
 
+    pub(crate) fn remove(&mut self, idx: usize) {
+        self.order.retain(|&i| i != idx);
+        self.map.remove(&idx);
+    }
+
     pub(crate) fn insert(&mut self, idx: usize, bytes: Arc<Vec<u8>>) {
         if self.map.contains_key(&idx) {
             self.order.retain(|&i| i != idx);
@@ -48,9 +54,13 @@ impl SegmentCache {
     }
 }
 
+#[derive(Clone)]
 pub(crate) struct CachedHls {
     pub track_urn: String,
     pub fetched_at: Instant,
+    pub variant: StreamVariant,
+    /// Lower bitrate streams of the same track, best first.
+    pub fallbacks: Vec<(StreamVariant, Url)>,
     pub manifest: Arc<HlsManifest>,
     pub init_bytes: Arc<Vec<u8>>,
     pub segment_cache: Arc<Mutex<SegmentCache>>,
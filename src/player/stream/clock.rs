@@ -0,0 +1,112 @@
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+
+/// Generation no `TapSource` carries, for while nothing should be counted.
+const IDLE: u64 = 0;
+
+/// Playback position counted from the samples the output actually pulled
+/// through `TapSource`, so it stands still when the stream stalls or is paused
+/// instead of running on with the wall clock.
+///
+/// Only sources of the current generation count, a sink fading out after a
+/// seek doesn't move it. Within a generation the sink can run from one track
+/// into the next, each its own `part`, and the clock starts over from zero
+/// when the first sample of the next part plays.
+pub(crate) struct PlaybackClock {
+    generation: AtomicU64,
+    part: AtomicU64,
+    start_ms: AtomicU64,
+    samples: AtomicU64,
+    /// Samples per second over all channels.
+    rate: AtomicU64,
+    /// Every segment of the track has been handed to the sink.
+    complete: AtomicBool,
+    /// Downloads are failing and being retried.
+    reconnecting: AtomicBool,
+}
+
+impl PlaybackClock {
+    pub(crate) fn new() -> Self {
+        Self {
+            generation: AtomicU64::new(IDLE),
+            part: AtomicU64::new(0),
+            start_ms: AtomicU64::new(0),
+            samples: AtomicU64::new(0),
+            rate: AtomicU64::new(0),
+            complete: AtomicBool::new(false),
+            reconnecting: AtomicBool::new(false),
+        }
+    }
+
+    /// Starts counting the sources of `generation` from `position_ms`.
+    pub(crate) fn start(&self, generation: u64, position_ms: u64) {
+        self.generation.store(IDLE, Ordering::SeqCst);
+        self.part.store(0, Ordering::SeqCst);
+        self.samples.store(0, Ordering::SeqCst);
+        self.start_ms.store(position_ms, Ordering::SeqCst);
+        self.complete.store(false, Ordering::SeqCst);
+        self.reconnecting.store(false, Ordering::SeqCst);
+        self.generation.store(generation, Ordering::SeqCst);
+    }
+
+    /// Stops counting and stays at `position_ms`, e.g. after seeking past the end.
+    pub(crate) fn hold(&self, position_ms: u64) {
+        self.generation.store(IDLE, Ordering::SeqCst);
+        self.samples.store(0, Ordering::SeqCst);
+        self.start_ms.store(position_ms, Ordering::SeqCst);
+        self.complete.store(true, Ordering::SeqCst);
+        self.reconnecting.store(false, Ordering::SeqCst);
+    }
+
+    /// Called by `TapSource` for each sample the output pulled.
+    pub(crate) fn count(&self, generation: u64, part: u64, rate: u64) {
+        if self.generation.load(Ordering::Relaxed) != generation {
+            return;
+        }
+        let current = self.part.load(Ordering::Relaxed);
+        if part == current + 1 {
+            // first sample of the next track
+            self.samples.store(0, Ordering::SeqCst);
+            self.start_ms.store(0, Ordering::SeqCst);
+            self.complete.store(false, Ordering::SeqCst);
+            self.part.store(part, Ordering::SeqCst);
+        } else if part != current {
+            return;
+        }
+        self.rate.store(rate, Ordering::Relaxed);
+        self.samples.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// The part whose audio is playing now.
+    pub(crate) fn part(&self) -> u64 {
+        self.part.load(Ordering::SeqCst)
+    }
+
+    pub(crate) fn mark_complete(&self, generation: u64, part: u64) {
+        if self.generation.load(Ordering::SeqCst) == generation && self.part() == part {
+            self.complete.store(true, Ordering::SeqCst);
+        }
+    }
+
+    pub(crate) fn set_reconnecting(&self, generation: u64, reconnecting: bool) {
+        if self.generation.load(Ordering::SeqCst) == generation {
+            self.reconnecting.store(reconnecting, Ordering::SeqCst);
+        }
+    }
+
+    pub(crate) fn is_reconnecting(&self) -> bool {
+        self.reconnecting.load(Ordering::SeqCst)
+    }
+
+    pub(crate) fn is_complete(&self) -> bool {
+        self.complete.load(Ordering::SeqCst)
+    }
+
+    pub(crate) fn position_ms(&self) -> u64 {
+        let start_ms = self.start_ms.load(Ordering::SeqCst);
+        let rate = self.rate.load(Ordering::Relaxed);
+        if rate == 0 {
+            return start_ms;
+        }
+        start_ms + self.samples.load(Ordering::Relaxed) * 1000 / rate
+    }
+}
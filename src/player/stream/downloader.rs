@@ -2,30 +2,97 @@ use std::io::Cursor;
 use std::sync::{
     Arc, Mutex,
     atomic::{AtomicU64, Ordering},
+    mpsc::Sender,
 };
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
-use rodio::{Decoder, Sink};
+use reqwest::StatusCode;
+use rodio::source::Zero;
+use rodio::{Decoder, Sink, Source};
+use url::Url;
 
-use crate::player::stream::cache::SegmentCache;
-use crate::player::stream::hls::HlsManifest;
+use crate::api::Track;
+use crate::auth::{Token, try_refresh_token};
+use crate::player::PlayerEvent;
+use crate::player::stream::bandwidth::Bandwidth;
+use crate::player::stream::cache::{CachedHls, SEGMENT_CACHE_CAP, SegmentCache};
+use crate::player::stream::clock::PlaybackClock;
+use crate::player::stream::equalizer::EqualizerChain;
+use crate::player::stream::hls::{HlsManifest, StreamVariant, StreamsResponse};
+use crate::player::stream::loudness::{Normalizer, TrackLoudness};
 use crate::player::stream::sample::TapSource;
+use crate::player::stream::speed::{SpeedControl, StretchChain};
 
 pub(crate) const PREFETCH_SEGMENTS: usize = 3;
+/// Extra audio queued beyond a crossfade, so its segments are in before it starts.
+const CROSSFADE_PREFETCH_MARGIN_MS: u64 = 10_000;
+/// How close to the end of a track the pump gives up waiting for the next one.
+const GAPLESS_WAIT_MARGIN_MS: u64 = 2_000;
+/// Wait before retrying a failed download, doubled each time it fails again.
+const FIRST_RETRY_DELAY: Duration = Duration::from_millis(500);
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(8);
+/// Times a segment that won't decode is downloaded again before it is skipped.
+const DECODE_ATTEMPTS: u32 = 3;
+/// Times the server may refuse a segment before the track is cut short there.
+/// Requests that don't get through at all are retried for as long as it takes.
+const DOWNLOAD_ATTEMPTS: u32 = 5;
+
+/// Track for the pump to run into once the current one is fully queued.
+pub(crate) type GaplessNext = Arc<Mutex<Option<(Track, CachedHls)>>>;
+
+/// What each segment of one generation runs through on its way to the sink,
+/// with the filter and stretch state its segments hand on to each other.
+pub(crate) struct SegmentChain {
+    pub generation: u64,
+    pub wave_buffer: Arc<Mutex<std::collections::VecDeque<f32>>>,
+    pub clock: Arc<PlaybackClock>,
+    pub equalizer: Arc<EqualizerChain>,
+    pub stretch: Arc<StretchChain>,
+}
+
+impl SegmentChain {
+    /// Queues `source`, a segment of the `part`th track of the generation.
+    pub(crate) fn append<S>(&self, sink: &Sink, source: S, loudness: &Arc<TrackLoudness>, part: u64)
+    where
+        S: Source + Send + 'static,
+    {
+        let tapped = TapSource::new(
+            loudness.wrap(self.equalizer.wrap(source)),
+            Arc::clone(&self.wave_buffer),
+            Arc::clone(&self.clock),
+            self.generation,
+            part,
+        );
+        sink.append(self.stretch.wrap(tapped));
+    }
+}
 
 pub(crate) struct SegmentPumpParams {
     pub client: reqwest::blocking::Client,
     pub generation: Arc<AtomicU64>,
     pub generation_value: u64,
-    pub manifest: Arc<HlsManifest>,
-    pub init_bytes: Arc<Vec<u8>>,
-    pub segment_cache: Arc<Mutex<SegmentCache>>,
+    pub token: Arc<Mutex<Token>>,
+    pub hls: CachedHls,
     pub start_segment_index: usize,
     pub sink_arc: Arc<Mutex<Option<Sink>>>,
-    pub wave_buffer: Arc<Mutex<std::collections::VecDeque<f32>>>,
-    pub is_playing_flag: Arc<std::sync::atomic::AtomicBool>,
-    pub elapsed_time: Arc<Mutex<Duration>>,
-    pub last_start: Arc<Mutex<Option<std::time::Instant>>>,
+    pub clock: Arc<PlaybackClock>,
+    /// The crossfade, whose length of audio is kept queued so a fade never runs dry.
+    pub crossfade_ms: Arc<AtomicU64>,
+    pub gapless_next: GaplessNext,
+    pub current_track: Arc<Mutex<Option<Track>>>,
+    /// Stream the current track plays from, updated when it changes.
+    pub stream_variant: Arc<Mutex<Option<StreamVariant>>>,
+    pub bandwidth: Arc<Bandwidth>,
+    /// Where a switch to another stream is left for the engine to seek in.
+    pub switched: Arc<Mutex<Option<CachedHls>>>,
+    /// Urn of the track the pump ran into, until the UI asks to play it.
+    pub crossed_into: Arc<Mutex<Option<String>>>,
+    pub events: Sender<PlayerEvent>,
+    pub normalizer: Normalizer,
+    /// Meter of the track being appended.
+    pub loudness: Arc<TrackLoudness>,
+    pub chain: SegmentChain,
+    pub speed: Arc<SpeedControl>,
 }
 
 pub(crate) fn spawn_segment_pump(params: SegmentPumpParams) {
@@ -34,72 +101,196 @@ pub(crate) fn spawn_segment_pump(params: SegmentPumpParams) {
             client,
             generation,
             generation_value,
-            manifest,
-            init_bytes,
-            segment_cache,
+            token,
+            mut hls,
             start_segment_index,
             sink_arc,
-            wave_buffer,
-            is_playing_flag,
-            elapsed_time,
-            last_start,
+            clock,
+            crossfade_ms,
+            gapless_next,
+            current_track,
+            stream_variant,
+            bandwidth,
+            switched,
+            crossed_into,
+            events,
+            normalizer,
+            mut loudness,
+            chain,
+            speed,
         } = params;
 
         let mut next_index = start_segment_index.saturating_add(1);
-        while next_index < manifest.segments.len() {
+        // audio to drop from the next segment, after switching to a stream
+        // whose segments start elsewhere
+        let mut skip_ms = 0;
+        let mut retry_delay = FIRST_RETRY_DELAY;
+        let mut decode_failures = 0;
+        let mut download_failures = 0;
+        // format of the last segment decoded, for silence standing in for one that won't
+        let mut format = (2, 44_100);
+        // the server keeps refusing the current track, nothing more of it is queued
+        let mut failed = false;
+        // track being appended, counted from the one playback started with
+        let mut part = 0;
+        // appended track that hasn't started playing yet
+        let mut pending: Option<Track> = None;
+        loop {
             if generation.load(Ordering::SeqCst) != generation_value {
                 break;
             }
 
-            let approx_pos_ms = {
-                let base = *elapsed_time.lock().unwrap();
-                if is_playing_flag.load(Ordering::SeqCst) {
-                    if let Some(start) = *last_start.lock().unwrap() {
-                        (base + start.elapsed()).as_millis() as u64
-                    } else {
-                        base.as_millis() as u64
-                    }
+            if let Some(track) = pending.take_if(|_| clock.part() == part) {
+                *crossed_into.lock().unwrap() = Some(track.track_urn.clone());
+                *current_track.lock().unwrap() = Some(track);
+                *stream_variant.lock().unwrap() = Some(hls.variant);
+                normalizer.set_current(&loudness);
+                let _ = events.send(PlayerEvent::TrackAdvanced);
+            }
+            let position_ms = if clock.part() == part { clock.position_ms() } else { 0 };
+
+            if next_index >= hls.manifest.segments.len() {
+                if pending.is_some() {
+                    std::thread::sleep(Duration::from_millis(50));
+                    continue;
+                }
+                let crossfade = crossfade_ms.load(Ordering::Relaxed) > 0;
+                let next = if crossfade || failed {
+                    None
                 } else {
-                    base.as_millis() as u64
+                    gapless_next.lock().unwrap().take()
+                };
+                if let Some((track, next)) = next {
+                    hls = next;
+                    next_index = 0;
+                    part += 1;
+                    loudness = normalizer.track(&track.track_urn);
+                    pending = Some(track);
+                    continue;
+                }
+                // the next track may still be preloading
+                let remaining_ms = hls.manifest.total_duration_ms.saturating_sub(position_ms);
+                if !crossfade && !failed && remaining_ms > GAPLESS_WAIT_MARGIN_MS {
+                    std::thread::sleep(Duration::from_millis(50));
+                    continue;
                 }
+                clock.mark_complete(generation_value, part);
+                chain.stretch.close();
+                break;
+            }
+
+            let (current_seg, _) = hls.manifest.locate_position(position_ms);
+            let ahead_ms = hls.manifest.segment_start_ms[next_index].saturating_sub(position_ms);
+            let lookahead_ms = match crossfade_ms.load(Ordering::Relaxed) {
+                0 => 0,
+                ms => (ms as f32 * speed.speed()) as u64 + CROSSFADE_PREFETCH_MARGIN_MS,
             };
-            let (current_seg, _) = manifest.locate_position(approx_pos_ms);
-            if next_index > current_seg.saturating_add(PREFETCH_SEGMENTS) {
+            if next_index > current_seg.saturating_add(PREFETCH_SEGMENTS)
+                && ahead_ms > lookahead_ms
+            {
                 std::thread::sleep(Duration::from_millis(50));
                 continue;
             }
 
-            let media_bytes = {
-                let mut cache_guard = segment_cache.lock().unwrap();
-                if let Some(bytes) = cache_guard.get(next_index) {
-                    bytes
-                } else {
-                    drop(cache_guard);
-                    let url = &manifest.segments[next_index].url;
-                    let bytes = match client.get(url.as_str()).send() {
-                        Ok(resp) => match resp.error_for_status() {
-                            Ok(ok) => match ok.bytes() {
-                                Ok(b) => b.to_vec(),
-                                Err(_) => break,
-                            },
-                            Err(_) => break,
-                        },
-                        Err(_) => break,
+            let cached = hls.segment_cache.lock().unwrap().get(next_index);
+            let media_bytes = match cached {
+                Some(bytes) => bytes,
+                None => {
+                    if bandwidth.too_slow_for(hls.variant.kbps()) && !hls.fallbacks.is_empty() {
+                        match drop_bitrate(&client, &token, &hls, next_index) {
+                            Some((lower, index, skip)) => {
+                                hls = lower;
+                                next_index = index;
+                                skip_ms = skip;
+                                *switched.lock().unwrap() = Some(hls.clone());
+                                if pending.is_none() {
+                                    *stream_variant.lock().unwrap() = Some(hls.variant);
+                                }
+                            }
+                            None => {
+                                hls.fallbacks.remove(0);
+                            }
+                        }
+                        continue;
+                    }
+                    let started = Instant::now();
+                    let url = &hls.manifest.segments[next_index].url;
+                    let bytes = match download(&client, url) {
+                        Ok(bytes) => bytes,
+                        Err(error) => {
+                            if error != DownloadError::Transport {
+                                download_failures += 1;
+                            }
+                            if download_failures >= DOWNLOAD_ATTEMPTS {
+                                let _ = events.send(PlayerEvent::Error {
+                                    reason: "couldn't download the rest of the track".to_string(),
+                                });
+                                clock.set_reconnecting(generation_value, false);
+                                download_failures = 0;
+                                failed = true;
+                                next_index = hls.manifest.segments.len();
+                                continue;
+                            }
+                            clock.set_reconnecting(generation_value, true);
+                            // the signed segment links ran out, get new ones
+                            if error == DownloadError::Expired
+                                && let Some((fresh, index, skip)) =
+                                    refresh_stream(&client, &token, &hls, next_index)
+                            {
+                                hls = fresh;
+                                next_index = index;
+                                skip_ms = skip;
+                                *switched.lock().unwrap() = Some(hls.clone());
+                            }
+                            let retry_at = Instant::now() + retry_delay;
+                            while Instant::now() < retry_at
+                                && generation.load(Ordering::SeqCst) == generation_value
+                            {
+                                std::thread::sleep(Duration::from_millis(50));
+                            }
+                            retry_delay = (retry_delay * 2).min(MAX_RETRY_DELAY);
+                            continue;
+                        }
                     };
+                    clock.set_reconnecting(generation_value, false);
+                    retry_delay = FIRST_RETRY_DELAY;
+                    download_failures = 0;
+                    bandwidth.record(bytes.len(), started.elapsed());
                     let arc = Arc::new(bytes);
-                    let mut cache_guard = segment_cache.lock().unwrap();
-                    cache_guard.insert(next_index, Arc::clone(&arc));
+                    hls.segment_cache.lock().unwrap().insert(next_index, Arc::clone(&arc));
                     arc
                 }
             };
 
-            let combined = combine_init_and_segment(&init_bytes, &media_bytes);
+            let combined = combine_init_and_segment(&hls.init_bytes, &media_bytes);
             let decoder = match Decoder::new(Cursor::new(combined)) {
-                Ok(d) => d,
-                Err(_) => break,
+                Ok(d) => d.skip_duration(Duration::from_millis(std::mem::take(&mut skip_ms))),
+                Err(_) if decode_failures + 1 < DECODE_ATTEMPTS => {
+                    // may have come in damaged, download it again
+                    decode_failures += 1;
+                    hls.segment_cache.lock().unwrap().remove(next_index);
+                    continue;
+                }
+                Err(_) => {
+                    // a gap beats ending the track here, silent so the clock runs through it
+                    decode_failures = 0;
+                    let duration_ms = hls.manifest.segments[next_index].duration_ms;
+                    let gap_ms = duration_ms.saturating_sub(std::mem::take(&mut skip_ms));
+                    let (channels, rate) = format;
+                    let silence =
+                        Zero::new(channels, rate).take_duration(Duration::from_millis(gap_ms));
+                    if let Some(ref sink) = *sink_arc.lock().unwrap()
+                        && generation.load(Ordering::SeqCst) == generation_value
+                    {
+                        chain.append(sink, silence, &loudness, part);
+                    }
+                    next_index += 1;
+                    continue;
+                }
             };
+            decode_failures = 0;
+            format = (decoder.channels(), decoder.sample_rate());
 
-            let tapped = TapSource::new(decoder, Arc::clone(&wave_buffer));
             if generation.load(Ordering::SeqCst) != generation_value {
                 break;
             }
@@ -108,7 +299,7 @@ pub(crate) fn spawn_segment_pump(params: SegmentPumpParams) {
                 break;
             }
             if let Some(ref sink) = *guard {
-                sink.append(tapped);
+                chain.append(sink, decoder, &loudness, part);
             } else {
                 break;
             }
@@ -118,6 +309,95 @@ pub(crate) fn spawn_segment_pump(params: SegmentPumpParams) {
     });
 }
 
+#[derive(Debug, PartialEq, Eq)]
+enum DownloadError {
+    /// The link was refused as no longer valid.
+    Expired,
+    /// The server answered with an error.
+    Failed,
+    /// The request didn't get through, e.g. while offline.
+    Transport,
+}
+
+fn download(client: &reqwest::blocking::Client, url: &Url) -> Result<Vec<u8>, DownloadError> {
+    let resp = client.get(url.as_str()).send().map_err(|_| DownloadError::Transport)?;
+    match resp.status() {
+        StatusCode::FORBIDDEN | StatusCode::GONE => return Err(DownloadError::Expired),
+        status if !status.is_success() => return Err(DownloadError::Failed),
+        _ => {}
+    }
+    let bytes = resp.bytes().map_err(|_| DownloadError::Transport)?;
+    Ok(bytes.to_vec())
+}
+
+/// Switches `hls` to its best fallback, picking up where segment
+/// `next_index` starts. Also returns the segment that is in the new stream
+/// and how far into it that is.
+fn drop_bitrate(
+    client: &reqwest::blocking::Client,
+    token: &Arc<Mutex<Token>>,
+    hls: &CachedHls,
+    next_index: usize,
+) -> Option<(CachedHls, usize, u64)> {
+    let (variant, url) = hls.fallbacks.first()?;
+    let access_token = token.lock().unwrap().access_token.clone();
+    let fallbacks = hls.fallbacks[1..].to_vec();
+    let lower = switch_stream(client, &access_token, hls, *variant, url, fallbacks)?;
+    let resume_ms = hls.manifest.segment_start_ms[next_index];
+    let (index, skip_ms) = lower.manifest.locate_position(resume_ms);
+    Some((lower, index, skip_ms))
+}
+
+/// `hls` with newly signed links for the same stream, like `drop_bitrate`.
+fn refresh_stream(
+    client: &reqwest::blocking::Client,
+    token: &Arc<Mutex<Token>>,
+    hls: &CachedHls,
+    next_index: usize,
+) -> Option<(CachedHls, usize, u64)> {
+    let _ = try_refresh_token(token);
+    let access_token = token.lock().unwrap().access_token.clone();
+    let streams = StreamsResponse::fetch(client, &hls.track_urn, &access_token).ok()?;
+    let stream_url = |variant| Url::parse(streams.url(variant)?).ok();
+
+    let url = stream_url(hls.variant)?;
+    let fallbacks = hls
+        .fallbacks
+        .iter()
+        .filter_map(|&(variant, _)| Some((variant, stream_url(variant)?)))
+        .collect();
+    let mut fresh = switch_stream(client, &access_token, hls, hls.variant, &url, fallbacks)?;
+    // same stream, so the segments already in are still good
+    fresh.segment_cache = Arc::clone(&hls.segment_cache);
+    let resume_ms = hls.manifest.segment_start_ms[next_index];
+    let (index, skip_ms) = fresh.manifest.locate_position(resume_ms);
+    Some((fresh, index, skip_ms))
+}
+
+fn switch_stream(
+    client: &reqwest::blocking::Client,
+    access_token: &str,
+    hls: &CachedHls,
+    variant: StreamVariant,
+    url: &Url,
+    fallbacks: Vec<(StreamVariant, Url)>,
+) -> Option<CachedHls> {
+    let manifest = HlsManifest::fetch(client, url, access_token).ok()?;
+    let init_bytes = match &manifest.init_url {
+        Some(init_url) => download(client, init_url).ok()?,
+        None => Vec::new(),
+    };
+    Some(CachedHls {
+        track_urn: hls.track_urn.clone(),
+        fetched_at: Instant::now(),
+        variant,
+        fallbacks,
+        manifest: Arc::new(manifest),
+        init_bytes: Arc::new(init_bytes),
+        segment_cache: Arc::new(Mutex::new(SegmentCache::new(SEGMENT_CACHE_CAP))),
+    })
+}
+
 fn combine_init_and_segment(init_bytes: &[u8], segment_bytes: &[u8]) -> Vec<u8> {
     let mut combined = Vec::with_capacity(init_bytes.len() + segment_bytes.len());
     combined.extend_from_slice(init_bytes);
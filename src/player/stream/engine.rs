@@ -3,20 +3,36 @@ use rodio::{Decoder, OutputStream, OutputStreamBuilder, Sink, Source};
 use std::io::Cursor;
 use std::sync::{
     Arc, Mutex,
-    atomic::{AtomicU64, Ordering},
+    atomic::{AtomicBool, AtomicU64, Ordering},
+    mpsc::Sender,
 };
 use std::time::{Duration, Instant};
 use url::Url;
 
 use crate::api::Track;
 use crate::auth::{Token, try_refresh_token};
+use crate::config::StreamQuality;
+use crate::player::stream::bandwidth::Bandwidth;
 use crate::player::stream::cache::{CachedHls, SegmentCache, SEGMENT_CACHE_CAP};
-use crate::player::stream::hls::{HlsManifest, StreamsResponse};
-use crate::player::stream::downloader::spawn_segment_pump;
-use crate::player::stream::sample::TapSource;
+use crate::player::stream::clock::PlaybackClock;
+use crate::player::stream::equalizer::{EqualizerChain, EqualizerControl};
+use crate::player::stream::hls::{HlsManifest, StreamVariant, StreamsResponse};
+use crate::player::stream::loudness::{LoudnessControl, Normalizer};
+use crate::player::PlayerEvent;
+use crate::player::stream::downloader::{GaplessNext, SegmentChain, spawn_segment_pump};
+use crate::player::stream::speed::{SpeedControl, StretchChain};
 
 pub(crate) const CROSSFADE_DURATION: Duration = Duration::from_millis(35);
 const CROSSFADE_STEPS: usize = 7;
+/// Volume step interval when fading out the previous track.
+const TRACK_FADE_STEP: Duration = Duration::from_millis(50);
+/// A track change this close to the end of a crossfade window still fades,
+/// the UI only checks the position once per tick.
+const TRACK_FADE_SLACK: Duration = Duration::from_secs(1);
+/// How long before a crossfade starts the next track is asked for.
+const PRELOAD_LEAD: Duration = Duration::from_secs(10);
+/// A track is over this close to its end.
+const END_SLACK_MS: u64 = 50;
 
 pub(crate) fn open_output_stream() -> Arc<Mutex<OutputStream>> {
     let output_stream = OutputStreamBuilder::open_default_stream().unwrap();
@@ -27,12 +43,45 @@ pub(crate) struct PlaybackEngine {
     stream: Arc<Mutex<OutputStream>>,
     client: reqwest::blocking::Client,
     generation: Arc<AtomicU64>,
+    clock: Arc<PlaybackClock>,
+    /// Track change overlap set by `Player::set_crossfade`.
+    crossfade_ms: Arc<AtomicU64>,
     cache: Option<CachedHls>,
     preload_next: Option<CachedHls>,
+    /// The preloaded track, for the segment pump to run into without a gap.
+    gapless_next: GaplessNext,
+    crossed_into: Arc<Mutex<Option<String>>>,
+    events: Sender<PlayerEvent>,
+    normalizer: Normalizer,
+    equalizer: Arc<EqualizerControl>,
+    speed: Arc<SpeedControl>,
+    quality: StreamQuality,
+    bandwidth: Arc<Bandwidth>,
+    /// Stream a pump moved the playing track to, a lower bitrate or freshly
+    /// signed links.
+    switched: Arc<Mutex<Option<CachedHls>>>,
+    watched: Watched,
+}
+
+/// What `watch` already reported about the generation and part in `key`.
+#[derive(Default)]
+struct Watched {
+    key: (u64, u64),
+    buffering: bool,
+    near_end: bool,
+    ended: bool,
 }
 
 impl PlaybackEngine {
-    pub(crate) fn new(stream: Arc<Mutex<OutputStream>>) -> anyhow::Result<Self> {
+    pub(crate) fn new(
+        stream: Arc<Mutex<OutputStream>>,
+        clock: Arc<PlaybackClock>,
+        crossfade_ms: Arc<AtomicU64>,
+        loudness: Arc<LoudnessControl>,
+        equalizer: Arc<EqualizerControl>,
+        speed: Arc<SpeedControl>,
+        events: Sender<PlayerEvent>,
+    ) -> anyhow::Result<Self> {
         let client = reqwest::blocking::Client::builder()
             .user_agent("sctui")
             .timeout(Duration::from_secs(15))
@@ -43,8 +92,20 @@ impl PlaybackEngine {
             stream,
             client,
             generation: Arc::new(AtomicU64::new(0)),
+            clock,
+            crossfade_ms,
             cache: None,
             preload_next: None,
+            gapless_next: Arc::new(Mutex::new(None)),
+            crossed_into: Arc::new(Mutex::new(None)),
+            events,
+            normalizer: Normalizer::new(loudness),
+            equalizer,
+            speed,
+            quality: StreamQuality::default(),
+            bandwidth: Arc::new(Bandwidth::new()),
+            switched: Arc::new(Mutex::new(None)),
+            watched: Watched::default(),
         })
     }
 
@@ -56,26 +117,62 @@ impl PlaybackEngine {
         self.generation.load(Ordering::SeqCst)
     }
 
-    fn get_hls_url(&self, track_urn: &str, access_token: &str) -> anyhow::Result<Url> {
-        let streams_url = format!("https://api.soundcloud.com/tracks/{}/streams", track_urn);
-        let streams_response: StreamsResponse = self
-            .client
-            .get(&streams_url)
-            .bearer_auth(access_token)
-            .send()
-            .context("failed to fetch streams endpoint")?
-            .error_for_status()
-            .context("streams endpoint returned error status")?
-            .json()
-            .context("failed to parse streams response json")?;
+    /// The stream to play `track_urn` from, then the lower bitrates to drop
+    /// to if the connection can't keep up, best first.
+    fn choose_stream(
+        &self,
+        track_urn: &str,
+        access_token: &str,
+    ) -> anyhow::Result<Vec<(StreamVariant, Url)>> {
+        let streams = StreamsResponse::fetch(&self.client, track_urn, access_token)?;
+        let mut ranked = streams
+            .ranked(self.quality)
+            .into_iter()
+            .map(|(variant, url)| Ok((variant, Url::parse(url).context("invalid HLS URL")?)))
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        if ranked.is_empty() {
+            anyhow::bail!(
+                "No HLS stream URL available for stream quality {}",
+                self.quality.label()
+            );
+        }
+
+        // the first preferred stream the connection keeps up with, else the lightest
+        let chosen = ranked
+            .iter()
+            .position(|(variant, _)| !self.bandwidth.too_slow_for(variant.kbps()))
+            .or_else(|| (0..ranked.len()).min_by_key(|&i| ranked[i].0.kbps()))
+            .unwrap_or(0);
+        let chosen = ranked.remove(chosen);
+        ranked.retain(|(variant, _)| variant.kbps() < chosen.0.kbps());
+        ranked.sort_by_key(|(variant, _)| std::cmp::Reverse(variant.kbps()));
+        ranked.insert(0, chosen);
+        Ok(ranked)
+    }
 
-        let hls_url = streams_response
-            .hls_aac_160_url
-            .or(streams_response.hls_aac_96_url)
-            .or(streams_response.hls_mp3_128_url)
-            .ok_or_else(|| anyhow::anyhow!("No HLS stream URL available (tried AAC 160, AAC 96, MP3 128)"))?;
+    fn fetch_hls(&self, track: &Track, token: &Arc<Mutex<Token>>) -> anyhow::Result<CachedHls> {
+        let _ = try_refresh_token(token);
+        let access_token = { token.lock().unwrap().access_token.clone() };
+
+        let mut streams = self.choose_stream(&track.track_urn, &access_token)?;
+        let (variant, playlist_url) = streams.remove(0);
+        let manifest = HlsManifest::fetch(&self.client, &playlist_url, &access_token)?;
+
+        let init_bytes = if let Some(init_url) = &manifest.init_url {
+            Arc::new(self.download_bytes(init_url)?)
+        } else {
+            Arc::new(Vec::new())
+        };
 
-        Url::parse(&hls_url).context("invalid HLS URL")
+        Ok(CachedHls {
+            track_urn: track.track_urn.clone(),
+            fetched_at: Instant::now(),
+            variant,
+            fallbacks: streams,
+            manifest: Arc::new(manifest),
+            init_bytes,
+            segment_cache: Arc::new(Mutex::new(SegmentCache::new(SEGMENT_CACHE_CAP))),
+        })
     }
 
     fn download_bytes(&self, url: &Url) -> anyhow::Result<Vec<u8>> {
@@ -95,55 +192,28 @@ impl PlaybackEngine {
         &mut self,
         track: &Track,
         token: &Arc<Mutex<Token>>,
-    ) -> anyhow::Result<(Arc<HlsManifest>, Arc<Vec<u8>>, Arc<Mutex<SegmentCache>>)> {
+    ) -> anyhow::Result<CachedHls> {
         let now = Instant::now();
-        
+
+        if let Some(switched) = self.switched.lock().unwrap().take()
+            && switched.track_urn == track.track_urn
+        {
+            self.cache = Some(switched);
+        }
+
         if let Some(ref preload) = self.preload_next {
             if preload.track_urn == track.track_urn {
-                let preload = self.preload_next.take().unwrap();
-                self.cache = Some(preload);
-                let cached = self.cache.as_ref().unwrap();
-                return Ok((
-                    Arc::clone(&cached.manifest),
-                    Arc::clone(&cached.init_bytes),
-                    Arc::clone(&cached.segment_cache),
-                ));
+                self.cache = self.preload_next.take();
             }
         }
-        
+
         let cache_valid = self.cache.as_ref().is_some_and(|c| c.is_valid_for(track, now));
 
         if !cache_valid {
-            let _ = try_refresh_token(token);
-            let access_token = { token.lock().unwrap().access_token.clone() };
-
-            let playlist_url = self.get_hls_url(&track.track_urn, &access_token)?;
-            let manifest = HlsManifest::fetch(&self.client, &playlist_url, &access_token)?;
-
-            let init_bytes = if let Some(init_url) = &manifest.init_url {
-                Arc::new(self.download_bytes(init_url)?)
-            } else {
-                Arc::new(Vec::new())
-            };
-
-            self.cache = Some(CachedHls {
-                track_urn: track.track_urn.clone(),
-                fetched_at: now,
-                manifest: Arc::new(manifest),
-                init_bytes,
-                segment_cache: Arc::new(Mutex::new(SegmentCache::new(SEGMENT_CACHE_CAP))),
-            });
+            self.cache = Some(self.fetch_hls(track, token)?);
         }
 
-        let cached = self
-            .cache
-            .as_ref()
-            .expect("cache must be set by now");
-        Ok((
-            Arc::clone(&cached.manifest),
-            Arc::clone(&cached.init_bytes),
-            Arc::clone(&cached.segment_cache),
-        ))
+        Ok(self.cache.clone().expect("cache must be set by now"))
     }
 
     pub(crate) fn preload_next_track(
@@ -155,44 +225,70 @@ impl PlaybackEngine {
             return Ok(());
         }
 
-        let _ = try_refresh_token(token);
-        let access_token = { token.lock().unwrap().access_token.clone() };
+        let preload = self.fetch_hls(track, token)?;
+        if let Some(first) = preload.manifest.segments.first()
+            && let Ok(bytes) = self.download_bytes(&first.url)
+        {
+            preload.segment_cache.lock().unwrap().insert(0, Arc::new(bytes));
+        }
 
-        let playlist_url = self.get_hls_url(&track.track_urn, &access_token)?;
-        let manifest = HlsManifest::fetch(&self.client, &playlist_url, &access_token)?;
+        self.preload_next = Some(preload);
+        *self.gapless_next.lock().unwrap() =
+            self.preload_next.clone().map(|hls| (track.clone(), hls));
 
-        let init_bytes = if let Some(init_url) = &manifest.init_url {
-            Arc::new(self.download_bytes(init_url)?)
-        } else {
-            Arc::new(Vec::new())
-        };
+        Ok(())
+    }
 
-        let first_segment_bytes = if !manifest.segments.is_empty() {
-            match self.download_bytes(&manifest.segments[0].url) {
-                Ok(bytes) => {
-                    let mut cache = SegmentCache::new(SEGMENT_CACHE_CAP);
-                    cache.insert(0, Arc::new(bytes));
-                    Some(Arc::new(Mutex::new(cache)))
-                }
-                Err(_) => {
-                    None
-                }
-            }
-        } else {
-            None
-        };
+    pub(crate) fn clear_preload(&mut self) {
+        self.preload_next = None;
+        *self.gapless_next.lock().unwrap() = None;
+    }
 
-        self.preload_next = Some(CachedHls {
-            track_urn: track.track_urn.clone(),
-            fetched_at: Instant::now(),
-            manifest: Arc::new(manifest),
-            init_bytes,
-            segment_cache: first_segment_bytes.unwrap_or_else(|| {
-                Arc::new(Mutex::new(SegmentCache::new(SEGMENT_CACHE_CAP)))
-            }),
-        });
+    /// Picks streams by `quality` from now on. Returns whether that changed
+    /// anything, in which case nothing fetched so far is reused.
+    pub(crate) fn set_stream_quality(&mut self, quality: StreamQuality) -> bool {
+        if self.quality == quality {
+            return false;
+        }
+        self.quality = quality;
+        self.cache = None;
+        self.preload_next = None;
+        *self.switched.lock().unwrap() = None;
+        *self.gapless_next.lock().unwrap() = None;
+        true
+    }
 
-        Ok(())
+    /// Whether playback already ran into `track` on its own, so asking to
+    /// play it only catches the caller up. Only answers yes once.
+    pub(crate) fn claim_gapless(&self, track: &Track) -> bool {
+        self.crossed_into.lock().unwrap().take().as_deref() == Some(track.track_urn.as_str())
+    }
+
+    /// Length of `track` according to its cached manifest.
+    pub(crate) fn duration_ms(&self, track: &Track) -> Option<u64> {
+        self.cache
+            .as_ref()
+            .filter(|c| c.track_urn == track.track_urn)
+            .map(|c| c.manifest.total_duration_ms)
+    }
+
+    /// How long to fade the playing track out under the next one, `None` to
+    /// cut. Only fades when the change comes within the crossfade of the end.
+    fn track_change_fade(&self, old_track: Option<&Track>, is_playing: bool) -> Option<Duration> {
+        let crossfade = Duration::from_millis(self.crossfade_ms.load(Ordering::Relaxed));
+        if crossfade.is_zero() || !is_playing {
+            return None;
+        }
+        let old_track = old_track?;
+        let duration_ms = self.duration_ms(old_track).unwrap_or(old_track.duration_ms);
+        // the clock is in track time, the fade in wall time
+        let speed = self.speed.speed();
+        let longest = Duration::from_millis(max_crossfade_ms(duration_ms)).div_f32(speed);
+        let crossfade = crossfade.min(longest);
+        let remaining = Duration::from_millis(duration_ms.saturating_sub(self.clock.position_ms()))
+            .div_f32(speed);
+        (!crossfade.is_zero() && !remaining.is_zero() && remaining <= crossfade + TRACK_FADE_SLACK)
+            .then(|| remaining.min(crossfade))
     }
 
     pub(crate) fn play_from_position(
@@ -202,17 +298,17 @@ impl PlaybackEngine {
         token: &Arc<Mutex<Token>>,
         sink_arc: &Arc<Mutex<Option<Sink>>>,
         is_playing_flag: &Arc<std::sync::atomic::AtomicBool>,
-        elapsed_time: &Arc<Mutex<Duration>>,
-        last_start: &Arc<Mutex<Option<Instant>>>,
         current_track: &Arc<Mutex<Option<Track>>>,
+        stream_variant: &Arc<Mutex<Option<StreamVariant>>>,
         wave_buffer: &Arc<Mutex<std::collections::VecDeque<f32>>>,
     ) {
-        let old_track_urn = current_track
-            .lock()
-            .unwrap()
-            .as_ref()
-            .map(|t| t.track_urn.clone());
-        let is_seek = old_track_urn.as_deref() == Some(&track.track_urn);
+        let old_track = current_track.lock().unwrap().clone();
+        let is_seek = old_track.as_ref().map(|t| t.track_urn.as_str()) == Some(&track.track_urn);
+        let fade = if is_seek {
+            None
+        } else {
+            self.track_change_fade(old_track.as_ref(), is_playing_flag.load(Ordering::SeqCst))
+        };
 
         let (has_old_sink, target_volume) = {
             let guard = sink_arc.lock().unwrap();
@@ -224,69 +320,54 @@ impl PlaybackEngine {
         if !is_seek {
             let generation_id = self.bump_generation();
             debug_assert_eq!(generation_id, planned_generation);
-            if let Some(ref s) = *sink_arc.lock().unwrap() {
+            *self.gapless_next.lock().unwrap() = None;
+            // a fading track keeps playing while the next one loads
+            if fade.is_none()
+                && let Some(ref s) = *sink_arc.lock().unwrap()
+            {
                 s.stop();
             }
         }
 
-        let (manifest, init_bytes, segment_cache) = match self.ensure_cached_hls(track, token) {
-            Ok(v) => v,
-            Err(_) => {
-                if !is_seek {
-                    is_playing_flag.store(false, Ordering::SeqCst);
-                    *last_start.lock().unwrap() = None;
+        let (hls, segment_index, offset_within_segment_ms, media_bytes) =
+            match self.first_segment(track, token, position_ms) {
+                Ok(v) => v,
+                Err(error) => {
+                    fail_track_change(is_seek, sink_arc, is_playing_flag, &self.events, error);
+                    return;
                 }
+            };
+
+        let first_bytes = combine_init_and_segment(&hls.init_bytes, &media_bytes);
+        let decoder = match Decoder::new(Cursor::new(first_bytes)) {
+            Ok(decoder) => decoder,
+            Err(error) => {
+                let error = anyhow::Error::new(error).context("rodio decoder init failed");
+                fail_track_change(is_seek, sink_arc, is_playing_flag, &self.events, error);
                 return;
             }
         };
-
-        let (segment_index, offset_within_segment_ms) = manifest.locate_position(position_ms);
-
-        let media_bytes = {
-            let mut cache_guard = segment_cache.lock().unwrap();
-            if let Some(bytes) = cache_guard.get(segment_index) {
-                bytes
-            } else {
-                drop(cache_guard);
-                match self.download_bytes(&manifest.segments[segment_index].url) {
-                    Ok(bytes) => {
-                        let arc = Arc::new(bytes);
-                        let mut cache_guard = segment_cache.lock().unwrap();
-                        cache_guard.insert(segment_index, Arc::clone(&arc));
-                        arc
-                    }
-                    Err(_) => {
-                        if !is_seek {
-                            is_playing_flag.store(false, Ordering::SeqCst);
-                            *last_start.lock().unwrap() = None;
-                        }
-                        return;
-                    }
-                }
-            }
+        let loudness = self.normalizer.track(&track.track_urn);
+        let chain = SegmentChain {
+            generation: planned_generation,
+            wave_buffer: Arc::clone(wave_buffer),
+            clock: Arc::clone(&self.clock),
+            equalizer: EqualizerChain::new(Arc::clone(&self.equalizer)),
+            stretch: StretchChain::new(Arc::clone(&self.speed)),
         };
 
-        let first_bytes = combine_init_and_segment(&init_bytes, &media_bytes);
-
         let new_sink = {
             let stream_guard = self.stream.lock().unwrap();
             Sink::connect_new(stream_guard.mixer())
         };
 
         new_sink.set_volume(target_volume);
+        new_sink.set_speed(self.speed.sink_speed());
+        // held until the clock counts this generation, so no sample goes uncounted
+        new_sink.pause();
 
-        if append_segment_to_sink(
-            &new_sink,
-            first_bytes,
-            wave_buffer,
-            offset_within_segment_ms,
-        ).is_err() {
-            if !is_seek {
-                is_playing_flag.store(false, Ordering::SeqCst);
-                *last_start.lock().unwrap() = None;
-            }
-            return;
-        }
+        let skipped = decoder.skip_duration(Duration::from_millis(offset_within_segment_ms));
+        chain.append(&new_sink, skipped, &loudness, 0);
 
         let gen_for_pump = if is_seek {
             let generation_id = self.bump_generation();
@@ -296,39 +377,154 @@ impl PlaybackEngine {
             planned_generation
         };
 
-        let old_sink_for_fade = if is_seek && has_old_sink {
+        let old_sink_for_fade = if (is_seek || fade.is_some()) && has_old_sink {
             sink_arc.lock().unwrap().take()
         } else {
             None
         };
 
+        self.normalizer.set_current(&loudness);
+        self.clock.start(gen_for_pump, position_ms);
+        new_sink.play();
         *sink_arc.lock().unwrap() = Some(new_sink);
 
         if let Some(old_sink) = old_sink_for_fade {
-            crossfade_and_stop(old_sink, target_volume);
+            match fade {
+                Some(duration) => {
+                    std::thread::spawn(move || {
+                        fade_out_and_stop(old_sink, target_volume, duration)
+                    });
+                }
+                None => crossfade_and_stop(old_sink, target_volume),
+            }
         }
 
         *current_track.lock().unwrap() = Some(track.clone());
-        *elapsed_time.lock().unwrap() = Duration::from_millis(position_ms);
-        *last_start.lock().unwrap() = Some(Instant::now());
+        *stream_variant.lock().unwrap() = Some(hls.variant);
         is_playing_flag.store(true, Ordering::SeqCst);
+        if !is_seek {
+            let _ = self.events.send(PlayerEvent::Started);
+        }
 
         use crate::player::stream::downloader::SegmentPumpParams;
         spawn_segment_pump(SegmentPumpParams {
             client: self.client.clone(),
             generation: Arc::clone(&self.generation),
             generation_value: gen_for_pump,
-            manifest,
-            init_bytes,
-            segment_cache,
+            token: Arc::clone(token),
+            hls,
             start_segment_index: segment_index,
             sink_arc: Arc::clone(sink_arc),
-            wave_buffer: Arc::clone(wave_buffer),
-            is_playing_flag: Arc::clone(is_playing_flag),
-            elapsed_time: Arc::clone(elapsed_time),
-            last_start: Arc::clone(last_start),
+            clock: Arc::clone(&self.clock),
+            crossfade_ms: Arc::clone(&self.crossfade_ms),
+            gapless_next: Arc::clone(&self.gapless_next),
+            current_track: Arc::clone(current_track),
+            stream_variant: Arc::clone(stream_variant),
+            bandwidth: Arc::clone(&self.bandwidth),
+            switched: Arc::clone(&self.switched),
+            crossed_into: Arc::clone(&self.crossed_into),
+            events: self.events.clone(),
+            normalizer: self.normalizer.clone(),
+            loudness,
+            chain,
+            speed: Arc::clone(&self.speed),
         });
     }
+
+    /// The stream of `track`, the segment `position_ms` falls in with how far
+    /// into it that is, and the segment's bytes.
+    fn first_segment(
+        &mut self,
+        track: &Track,
+        token: &Arc<Mutex<Token>>,
+        position_ms: u64,
+    ) -> anyhow::Result<(CachedHls, usize, u64, Arc<Vec<u8>>)> {
+        let hls = self.ensure_cached_hls(track, token)?;
+        let (index, offset_ms) = hls.manifest.locate_position(position_ms);
+        let cached = hls.segment_cache.lock().unwrap().get(index);
+        if let Some(bytes) = cached {
+            return Ok((hls, index, offset_ms, bytes));
+        }
+
+        let (hls, index, offset_ms, bytes) =
+            match self.download_bytes(&hls.manifest.segments[index].url) {
+                Ok(bytes) => (hls, index, offset_ms, bytes),
+                Err(_) => {
+                    // signed links can run out before the cache does
+                    self.cache = None;
+                    let hls = self.ensure_cached_hls(track, token)?;
+                    let (index, offset_ms) = hls.manifest.locate_position(position_ms);
+                    let bytes = self.download_bytes(&hls.manifest.segments[index].url)?;
+                    (hls, index, offset_ms, bytes)
+                }
+            };
+        let bytes = Arc::new(bytes);
+        hls.segment_cache.lock().unwrap().insert(index, Arc::clone(&bytes));
+        Ok((hls, index, offset_ms, bytes))
+    }
+
+    /// Reports how the playing track is getting on, each thing once. Called
+    /// by the worker between commands.
+    pub(crate) fn watch(
+        &mut self,
+        sink_arc: &Arc<Mutex<Option<Sink>>>,
+        is_playing_flag: &AtomicBool,
+        current_track: &Arc<Mutex<Option<Track>>>,
+    ) {
+        let key = (self.current_generation(), self.clock.part());
+        if self.watched.key != key {
+            self.watched = Watched {
+                key,
+                ..Watched::default()
+            };
+        }
+        if !is_playing_flag.load(Ordering::SeqCst) {
+            return;
+        }
+        let Some(track) = current_track.lock().unwrap().clone() else {
+            return;
+        };
+        let drained = sink_arc.lock().unwrap().as_ref().is_none_or(|s| s.empty());
+        let complete = self.clock.is_complete();
+
+        let stalled = drained && !complete;
+        if stalled != self.watched.buffering {
+            self.watched.buffering = stalled;
+            let _ = self.events.send(if stalled {
+                PlayerEvent::Buffering
+            } else {
+                PlayerEvent::Resumed
+            });
+        }
+
+        let duration_ms = self.duration_ms(&track).unwrap_or(track.duration_ms);
+        let position_ms = self.clock.position_ms();
+        // positions are in track time, which runs faster than the crossfade when sped up
+        let speed = self.speed.speed();
+        let crossfade_ms = (self.crossfade_ms.load(Ordering::Relaxed) as f32 * speed) as u64;
+        let crossfade_ms = crossfade_ms.min(max_crossfade_ms(duration_ms));
+        let lead_ms = (PRELOAD_LEAD.as_millis() as f32 * speed) as u64;
+
+        let near_end_ms =
+            (duration_ms / 5 * 4).min(duration_ms.saturating_sub(crossfade_ms + lead_ms));
+        if !self.watched.near_end
+            && position_ms >= near_end_ms
+            && position_ms + 2 * END_SLACK_MS < duration_ms
+        {
+            self.watched.near_end = true;
+            let _ = self.events.send(PlayerEvent::NearEnd);
+        }
+
+        // with crossfade off the pump runs into a preloaded track by itself
+        let ended = (drained && complete)
+            || (crossfade_ms > 0
+                && duration_ms > 0
+                && position_ms + crossfade_ms + END_SLACK_MS >= duration_ms);
+        if ended && !self.watched.ended {
+            self.watched.ended = true;
+            let _ = self.events.send(PlayerEvent::Ended);
+        }
+    }
 }
 
 fn combine_init_and_segment(init_bytes: &[u8], segment_bytes: &[u8]) -> Vec<u8> {
@@ -338,25 +534,44 @@ fn combine_init_and_segment(init_bytes: &[u8], segment_bytes: &[u8]) -> Vec<u8>
     combined
 }
 
-fn append_segment_to_sink(
-    sink: &Sink,
-    bytes: Vec<u8>,
-    wave_buffer: &Arc<Mutex<std::collections::VecDeque<f32>>>,
-    skip_ms: u64,
-) -> anyhow::Result<()> {
-    let cursor = Cursor::new(bytes);
-    let decoder = Decoder::new(cursor).context("rodio decoder init failed")?;
-
-    if skip_ms > 0 {
-        let skipped = decoder.skip_duration(Duration::from_millis(skip_ms));
-        let tapped = TapSource::new(skipped, Arc::clone(wave_buffer));
-        sink.append(tapped);
-    } else {
-        let tapped = TapSource::new(decoder, Arc::clone(wave_buffer));
-        sink.append(tapped);
+/// Stops the sink of a track that failed to replace the playing one and
+/// ends it, so the UI moves on. A seek leaves the old sink playing.
+fn fail_track_change(
+    is_seek: bool,
+    sink_arc: &Arc<Mutex<Option<Sink>>>,
+    is_playing_flag: &AtomicBool,
+    events: &Sender<PlayerEvent>,
+    error: anyhow::Error,
+) {
+    if !is_seek {
+        if let Some(ref s) = *sink_arc.lock().unwrap() {
+            s.stop();
+        }
+        is_playing_flag.store(false, Ordering::SeqCst);
+    }
+    let _ = events.send(PlayerEvent::Error {
+        reason: format!("{:#}", error),
+    });
+    if !is_seek {
+        let _ = events.send(PlayerEvent::Ended);
+    }
+}
+
+/// Lowers the previous track to silence over `duration` while the next plays.
+fn fade_out_and_stop(old_sink: Sink, target_volume: f32, duration: Duration) {
+    let steps = (duration.as_millis() / TRACK_FADE_STEP.as_millis()).max(1) as u32;
+    for i in 1..=steps {
+        let t = i as f32 / steps as f32;
+        old_sink.set_volume(target_volume * (1.0 - t));
+        std::thread::sleep(duration / steps);
     }
+    old_sink.stop();
+}
 
-    Ok(())
+/// Longest crossfade out of a track `duration_ms` long, in track time. Half of
+/// it, so a track shorter than the crossfade still plays before `Ended`.
+fn max_crossfade_ms(duration_ms: u64) -> u64 {
+    (duration_ms / 2).saturating_sub(END_SLACK_MS)
 }
 
 fn crossfade_and_stop(old_sink: Sink, target_volume: f32) {
@@ -0,0 +1,212 @@
+use rodio::Source;
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
+use std::time::Duration;
+
+use super::biquad::Biquad;
+use crate::config::{EQ_BAND_COUNT, EQ_BANDS_HZ};
+
+/// Width of each band, about an octave so neighbours meet.
+const BAND_Q: f64 = 1.41;
+/// Bands this close to Nyquist are left alone, the filter would misbehave there.
+const MAX_BAND_SHARE_OF_RATE: f64 = 0.45;
+
+/// Band gains the equalizer reads live, shared with `Player`.
+pub(crate) struct EqualizerControl {
+    /// dB per band, as `f32` bits.
+    gains_db: [AtomicU32; EQ_BAND_COUNT],
+    /// Bumped on every change, so sources only retune when something moved.
+    version: AtomicU64,
+}
+
+impl EqualizerControl {
+    pub(crate) fn new() -> Self {
+        Self {
+            gains_db: [const { AtomicU32::new(0) }; EQ_BAND_COUNT],
+            version: AtomicU64::new(0),
+        }
+    }
+
+    pub(crate) fn set_gains(&self, gains_db: [f32; EQ_BAND_COUNT]) {
+        if self.gains() == gains_db {
+            return;
+        }
+        for (band, gain) in self.gains_db.iter().zip(gains_db) {
+            band.store(gain.to_bits(), Ordering::Relaxed);
+        }
+        self.version.fetch_add(1, Ordering::Release);
+    }
+
+    fn gains(&self) -> [f32; EQ_BAND_COUNT] {
+        std::array::from_fn(|i| f32::from_bits(self.gains_db[i].load(Ordering::Relaxed)))
+    }
+}
+
+/// The filters of one generation, handed from each segment to the next so
+/// they run on as if the segments were one stream.
+pub(crate) struct EqualizerChain {
+    control: Arc<EqualizerControl>,
+    /// Where the last segment to run out left the filters.
+    filters: Mutex<Option<Filters>>,
+}
+
+impl EqualizerChain {
+    pub(crate) fn new(control: Arc<EqualizerControl>) -> Arc<Self> {
+        Arc::new(Self {
+            control,
+            filters: Mutex::new(None),
+        })
+    }
+
+    /// Puts the equalizer in front of `inner`, one segment of this generation.
+    pub(crate) fn wrap<S: Source>(self: &Arc<Self>, inner: S) -> EqualizerSource<S> {
+        EqualizerSource::new(inner, Arc::clone(self))
+    }
+}
+
+#[derive(Clone)]
+struct Filters {
+    rate: f64,
+    channels: usize,
+    /// Control version the filters are tuned to.
+    version: Option<u64>,
+    bands: Vec<[Biquad; EQ_BAND_COUNT]>,
+    flat: bool,
+    /// Cut by the largest boost, so boosted bands don't clip.
+    preamp: f64,
+}
+
+impl Filters {
+    fn new(rate: f64, channels: usize, control: &EqualizerControl) -> Self {
+        let mut filters = Self {
+            rate,
+            channels,
+            version: None,
+            bands: Vec::new(),
+            flat: true,
+            preamp: 1.0,
+        };
+        filters.retune(control);
+        filters.bands = vec![filters.band_filters(&control.gains()); channels];
+        filters
+    }
+
+    fn band_filters(&self, gains_db: &[f32; EQ_BAND_COUNT]) -> [Biquad; EQ_BAND_COUNT] {
+        std::array::from_fn(|i| {
+            let freq = f64::from(EQ_BANDS_HZ[i]);
+            let gain_db = if freq < self.rate * MAX_BAND_SHARE_OF_RATE {
+                f64::from(gains_db[i])
+            } else {
+                0.0
+            };
+            Biquad::peaking(self.rate, freq, BAND_Q, gain_db)
+        })
+    }
+
+    /// Picks up changed gains, at most once per frame.
+    fn retune(&mut self, control: &EqualizerControl) {
+        let version = control.version.load(Ordering::Acquire);
+        if self.version == Some(version) {
+            return;
+        }
+        self.version = Some(version);
+        let gains_db = control.gains();
+        let was_flat = self.flat;
+        self.flat = gains_db.iter().all(|&g| g == 0.0);
+        let boost = gains_db.iter().fold(0.0f32, |max, &g| max.max(g));
+        self.preamp = 10f64.powf(-f64::from(boost) / 20.0);
+
+        let tuned = self.band_filters(&gains_db);
+        for filters in &mut self.bands {
+            for (filter, tuned) in filters.iter_mut().zip(&tuned) {
+                filter.retune(tuned);
+                // state from before a flat stretch has nothing to do with the audio now
+                if was_flat {
+                    filter.reset();
+                }
+            }
+        }
+    }
+}
+
+/// Graphic equalizer, a peaking filter per band and channel. Passes the audio
+/// straight through while every band is flat.
+pub(crate) struct EqualizerSource<S> {
+    inner: S,
+    chain: Arc<EqualizerChain>,
+    filters: Filters,
+    /// Channel of the next sample.
+    channel: usize,
+    /// Whether the filters the segment before left behind were taken over yet.
+    resumed: bool,
+}
+
+impl<S: Source> EqualizerSource<S> {
+    fn new(inner: S, chain: Arc<EqualizerChain>) -> Self {
+        let channels = usize::from(inner.channels()).max(1);
+        let rate = f64::from(inner.sample_rate()).max(1.0);
+        Self {
+            filters: Filters::new(rate, channels, &chain.control),
+            inner,
+            chain,
+            channel: 0,
+            resumed: false,
+        }
+    }
+
+    /// Carries on from the segment before, unless the format changed with it.
+    fn resume(&mut self) {
+        self.resumed = true;
+        let before = self.chain.filters.lock().unwrap().take();
+        if let Some(before) = before
+            && before.rate == self.filters.rate
+            && before.channels == self.filters.channels
+        {
+            self.filters = before;
+        }
+    }
+}
+
+impl<S: Source> Iterator for EqualizerSource<S> {
+    type Item = f32;
+
+    fn next(&mut self) -> Option<f32> {
+        if !self.resumed {
+            self.resume();
+        }
+        let Some(sample) = self.inner.next() else {
+            *self.chain.filters.lock().unwrap() = Some(self.filters.clone());
+            return None;
+        };
+        if self.channel == 0 {
+            self.filters.retune(&self.chain.control);
+        }
+        let channel = self.channel;
+        self.channel = (self.channel + 1) % self.filters.channels;
+        if self.filters.flat {
+            return Some(sample);
+        }
+        let out = self.filters.bands[channel]
+            .iter_mut()
+            .fold(f64::from(sample) * self.filters.preamp, |x, filter| filter.process(x));
+        Some(out as f32)
+    }
+}
+
+impl<S: Source> Source for EqualizerSource<S> {
+    fn current_span_len(&self) -> Option<usize> {
+        self.inner.current_span_len()
+    }
+
+    fn channels(&self) -> u16 {
+        self.inner.channels()
+    }
+
+    fn sample_rate(&self) -> u32 {
+        self.inner.sample_rate()
+    }
+
+    fn total_duration(&self) -> Option<Duration> {
+        self.inner.total_duration()
+    }
+}
@@ -3,6 +3,8 @@ use m3u8_rs::Playlist;
 use serde::Deserialize;
 use url::Url;
 
+use crate::config::{StreamQuality, api_url};
+
 #[derive(Debug, Deserialize)]
 pub(crate) struct StreamsResponse {
     pub hls_aac_160_url: Option<String>,
@@ -11,6 +13,73 @@ pub(crate) struct StreamsResponse {
     pub hls_mp3_128_url: Option<String>,
 }
 
+impl StreamsResponse {
+    pub(crate) fn fetch(
+        client: &reqwest::blocking::Client,
+        track_urn: &str,
+        access_token: &str,
+    ) -> anyhow::Result<Self> {
+        let streams_url = api_url(&format!("/tracks/{}/streams", track_urn));
+        client
+            .get(&streams_url)
+            .bearer_auth(access_token)
+            .send()
+            .context("failed to fetch streams endpoint")?
+            .error_for_status()
+            .context("streams endpoint returned error status")?
+            .json()
+            .context("failed to parse streams response json")
+    }
+
+    pub(crate) fn url(&self, variant: StreamVariant) -> Option<&str> {
+        match variant {
+            StreamVariant::Aac160 => self.hls_aac_160_url.as_deref(),
+            StreamVariant::Aac96 => self.hls_aac_96_url.as_deref(),
+            StreamVariant::Mp3 => self.hls_mp3_128_url.as_deref(),
+        }
+    }
+
+    /// The streams on offer, in the order `quality` prefers them.
+    pub(crate) fn ranked(&self, quality: StreamQuality) -> Vec<(StreamVariant, &str)> {
+        use StreamVariant::*;
+        let order: &[StreamVariant] = match quality {
+            StreamQuality::High => &[Aac160, Mp3, Aac96],
+            StreamQuality::Normal => &[Mp3, Aac96],
+            StreamQuality::DataSaver => &[Aac96, Mp3, Aac160],
+            StreamQuality::Mp3Only => &[Mp3],
+        };
+        order
+            .iter()
+            .filter_map(|&variant| self.url(variant).map(|url| (variant, url)))
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum StreamVariant {
+    Aac160,
+    Aac96,
+    Mp3,
+}
+
+impl StreamVariant {
+    pub(crate) fn kbps(self) -> u64 {
+        match self {
+            StreamVariant::Aac160 => 160,
+            StreamVariant::Aac96 => 96,
+            StreamVariant::Mp3 => 128,
+        }
+    }
+
+    pub(crate) fn label(self) -> &'static str {
+        match self {
+            StreamVariant::Aac160 => "AAC 160 kbps",
+            StreamVariant::Aac96 => "AAC 96 kbps",
+            StreamVariant::Mp3 => "MP3 128 kbps",
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub(crate) struct HlsSegment {
     pub url: Url,
@@ -0,0 +1,578 @@
+use rodio::Source;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::f64::consts::PI;
+use std::fs;
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use super::biquad::Biquad;
+use crate::config::{loudness_cache_path, write_private};
+
+/// The meter is fed in steps this long, four of them make a 400 ms gating block.
+const STEP_MS: u64 = 100;
+const BLOCK_STEPS: usize = 4;
+/// BS.1770 gates: blocks below -70 LUFS never count, and of the rest only
+/// those within 10 LU of their average.
+const ABSOLUTE_GATE_LUFS: f64 = -70.0;
+const RELATIVE_GATE_LU: f64 = 10.0;
+/// Gated blocks are kept as a histogram in 0.1 LU bins from -70 to +5 LUFS,
+/// so the estimate costs the same however long the track runs.
+const BIN_LU: f64 = 0.1;
+const BINS: usize = 750;
+/// Audio measured before the running estimate is trusted.
+const MIN_MEASURED_MS: u64 = 3_000;
+/// Shorter measurements aren't worth remembering.
+const MIN_REMEMBERED_MS: u64 = 20_000;
+/// Tracks remembered at most, the ones played least recently are forgotten first.
+const MAX_REMEMBERED: usize = 4_000;
+/// A save waits this long, so tracks ending close together are written once.
+const SAVE_DELAY: Duration = Duration::from_secs(10);
+const MAX_BOOST_DB: f64 = 12.0;
+const MAX_CUT_DB: f64 = -24.0;
+/// How fast the gain follows the estimate, slow enough not to be heard.
+const GAIN_TIME_CONSTANT_SECS: f64 = 2.0;
+/// -1 dBTP.
+const LIMITER_CEILING: f32 = 0.891;
+/// The limiter starts soft at this share of the ceiling.
+const LIMITER_KNEE: f32 = 0.9;
+const LIMITER_LOOKAHEAD: Duration = Duration::from_millis(2);
+const LIMITER_RELEASE_SECS: f64 = 0.15;
+
+/// Normalization settings the audio reads live, shared with `Player`.
+pub(crate) struct LoudnessControl {
+    enabled: AtomicBool,
+    /// Target loudness in LUFS, as `f32` bits.
+    target: AtomicU32,
+    /// Sink volume as `f32` bits, the limiter leaves room for anything above 1.0.
+    volume: AtomicU32,
+}
+
+impl LoudnessControl {
+    pub(crate) fn new() -> Self {
+        Self {
+            enabled: AtomicBool::new(false),
+            target: AtomicU32::new((-14.0f32).to_bits()),
+            volume: AtomicU32::new(1.0f32.to_bits()),
+        }
+    }
+
+    /// `None` turns normalization off.
+    pub(crate) fn set_target(&self, target_lufs: Option<f32>) {
+        if let Some(target) = target_lufs {
+            self.target.store(target.to_bits(), Ordering::Relaxed);
+        }
+        self.enabled.store(target_lufs.is_some(), Ordering::Relaxed);
+    }
+
+    fn target(&self) -> Option<f32> {
+        self.enabled
+            .load(Ordering::Relaxed)
+            .then(|| f32::from_bits(self.target.load(Ordering::Relaxed)))
+    }
+
+    pub(crate) fn set_volume(&self, volume: f32) {
+        self.volume.store(volume.to_bits(), Ordering::Relaxed);
+    }
+
+    fn volume(&self) -> f32 {
+        f32::from_bits(self.volume.load(Ordering::Relaxed))
+    }
+}
+
+#[derive(Clone, Copy, Serialize, Deserialize)]
+struct Measurement {
+    lufs: f64,
+    measured_ms: u64,
+    /// When the track was last looked up or measured, on the memory's own clock.
+    #[serde(default)]
+    used: u64,
+}
+
+/// Integrated loudness of tracks heard before, so a replay starts at the right level.
+struct LoudnessMemory {
+    tracks: Mutex<HashMap<String, Measurement>>,
+    /// Ticks once per lookup or measurement, for `Measurement::used`.
+    clock: AtomicU64,
+    save_pending: AtomicBool,
+}
+
+impl LoudnessMemory {
+    /// A missing or unreadable file starts empty.
+    fn load() -> Self {
+        let tracks = loudness_cache_path()
+            .and_then(|path| fs::read_to_string(path).ok())
+            .and_then(|data| serde_json::from_str(&data).ok())
+            .unwrap_or_default();
+        Self::new(tracks)
+    }
+
+    fn new(tracks: HashMap<String, Measurement>) -> Self {
+        let clock = tracks.values().map(|m| m.used).max().unwrap_or(0);
+        Self {
+            tracks: Mutex::new(tracks),
+            clock: AtomicU64::new(clock),
+            save_pending: AtomicBool::new(false),
+        }
+    }
+
+    fn tick(&self) -> u64 {
+        self.clock.fetch_add(1, Ordering::Relaxed) + 1
+    }
+
+    fn get(&self, urn: &str) -> Option<Measurement> {
+        let mut tracks = self.tracks.lock().unwrap();
+        let known = tracks.get_mut(urn)?;
+        known.used = self.tick();
+        Some(*known)
+    }
+
+    /// Keeps whichever measurement of the track heard more of it.
+    fn record(&self, urn: &str, measurement: Measurement) -> bool {
+        let mut tracks = self.tracks.lock().unwrap();
+        if tracks
+            .get(urn)
+            .is_some_and(|known| known.measured_ms >= measurement.measured_ms)
+        {
+            return false;
+        }
+        let used = self.tick();
+        tracks.insert(urn.to_string(), Measurement { used, ..measurement });
+        if tracks.len() > MAX_REMEMBERED
+            && let Some(oldest) = tracks.iter().min_by_key(|(_, m)| m.used).map(|(u, _)| u.clone())
+        {
+            tracks.remove(&oldest);
+        }
+        true
+    }
+
+    /// Saves a little later, off the audio thread the last segment may be dropped on.
+    fn schedule_save(self: &Arc<Self>) {
+        if self.save_pending.swap(true, Ordering::AcqRel) {
+            return;
+        }
+        let memory = Arc::clone(self);
+        std::thread::spawn(move || {
+            std::thread::sleep(SAVE_DELAY);
+            memory.save_pending.store(false, Ordering::Release);
+            memory.save();
+        });
+    }
+
+    fn save(&self) {
+        let Some(path) = loudness_cache_path() else {
+            return;
+        };
+        let data = serde_json::to_string(&*self.tracks.lock().unwrap());
+        if let Ok(data) = data {
+            let _ = write_private(&path, &data);
+        }
+    }
+}
+
+/// Hands out the meter of each track, for its segments to share.
+#[derive(Clone)]
+pub(crate) struct Normalizer {
+    control: Arc<LoudnessControl>,
+    memory: Arc<LoudnessMemory>,
+    /// The track playing, whose meter carries on across seeks.
+    current: Arc<Mutex<Option<Arc<TrackLoudness>>>>,
+}
+
+impl Normalizer {
+    pub(crate) fn new(control: Arc<LoudnessControl>) -> Self {
+        Self {
+            control,
+            memory: Arc::new(LoudnessMemory::load()),
+            current: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    /// The playing track's meter if `urn` is that track, else a fresh one.
+    pub(crate) fn track(&self, urn: &str) -> Arc<TrackLoudness> {
+        if let Some(current) = self.current.lock().unwrap().as_ref()
+            && current.urn == urn
+        {
+            return Arc::clone(current);
+        }
+        Arc::new(TrackLoudness {
+            urn: urn.to_string(),
+            remembered: self.memory.get(urn),
+            meter: Mutex::new(Meter::new()),
+            gain: AtomicU32::new(f32::NAN.to_bits()),
+            control: Arc::clone(&self.control),
+            memory: Arc::clone(&self.memory),
+        })
+    }
+
+    /// Called once `track` is the one heard.
+    pub(crate) fn set_current(&self, track: &Arc<TrackLoudness>) {
+        *self.current.lock().unwrap() = Some(Arc::clone(track));
+    }
+}
+
+/// Loudness of one track as measured so far, shared by all its segments.
+/// What it learned is remembered once nothing plays the track any more.
+pub(crate) struct TrackLoudness {
+    urn: String,
+    remembered: Option<Measurement>,
+    meter: Mutex<Meter>,
+    /// Gain the last segment ended on, as `f32` bits, NaN before the first.
+    gain: AtomicU32,
+    control: Arc<LoudnessControl>,
+    memory: Arc<LoudnessMemory>,
+}
+
+impl TrackLoudness {
+    /// Puts the normalization stage for this track in front of `inner`.
+    pub(crate) fn wrap<S: Source>(self: &Arc<Self>, inner: S) -> NormalizeSource<S> {
+        NormalizeSource::new(inner, Arc::clone(self))
+    }
+
+    /// Linear gain that brings the track to `target_lufs`, unity while
+    /// nothing is known about it yet.
+    fn gain_for(&self, target_lufs: f32) -> f32 {
+        let meter = self.meter.lock().unwrap();
+        let measured = meter
+            .integrated()
+            .filter(|_| meter.measured_ms >= MIN_MEASURED_MS);
+        // a remembered value covers more of the track until this play catches up
+        let lufs = match self.remembered {
+            Some(known) if known.measured_ms >= meter.measured_ms => Some(known.lufs),
+            known => measured.or(known.map(|k| k.lufs)),
+        };
+        lufs.map(|lufs| {
+            let db = (f64::from(target_lufs) - lufs).clamp(MAX_CUT_DB, MAX_BOOST_DB);
+            10f64.powf(db / 20.0) as f32
+        })
+        .unwrap_or(1.0)
+    }
+}
+
+impl Drop for TrackLoudness {
+    fn drop(&mut self) {
+        let meter = self.meter.get_mut().unwrap();
+        if meter.measured_ms < MIN_REMEMBERED_MS {
+            return;
+        }
+        let Some(lufs) = meter.integrated() else {
+            return;
+        };
+        let measurement = Measurement {
+            lufs,
+            measured_ms: meter.measured_ms,
+            used: 0,
+        };
+        if self.memory.record(&self.urn, measurement) {
+            self.memory.schedule_save();
+        }
+    }
+}
+
+/// Running BS.1770 integrated loudness.
+struct Meter {
+    steps: VecDeque<f64>,
+    counts: Vec<u32>,
+    energy: Vec<f64>,
+    measured_ms: u64,
+}
+
+impl Meter {
+    fn new() -> Self {
+        Self {
+            steps: VecDeque::with_capacity(BLOCK_STEPS),
+            counts: vec![0; BINS],
+            energy: vec![0.0; BINS],
+            measured_ms: 0,
+        }
+    }
+
+    /// Adds the mean square of the next step of K-weighted audio.
+    fn push_step(&mut self, power: f64) {
+        self.measured_ms += STEP_MS;
+        if self.steps.len() == BLOCK_STEPS {
+            self.steps.pop_front();
+        }
+        self.steps.push_back(power);
+        if self.steps.len() < BLOCK_STEPS {
+            return;
+        }
+        let block = self.steps.iter().sum::<f64>() / BLOCK_STEPS as f64;
+        let lufs = loudness(block);
+        if lufs <= ABSOLUTE_GATE_LUFS {
+            return;
+        }
+        let bin = (((lufs - ABSOLUTE_GATE_LUFS) / BIN_LU) as usize).min(BINS - 1);
+        self.counts[bin] += 1;
+        self.energy[bin] += block;
+    }
+
+    fn integrated(&self) -> Option<f64> {
+        let ungated = self.mean_power(0)?;
+        let threshold = loudness(ungated) - RELATIVE_GATE_LU;
+        let first = ((threshold - ABSOLUTE_GATE_LUFS) / BIN_LU).max(0.0) as usize;
+        self.mean_power(first).map(loudness)
+    }
+
+    fn mean_power(&self, first_bin: usize) -> Option<f64> {
+        let count: u64 = self.counts[first_bin..].iter().map(|&c| u64::from(c)).sum();
+        let energy: f64 = self.energy[first_bin..].iter().sum();
+        (count > 0).then(|| energy / count as f64)
+    }
+}
+
+fn loudness(power: f64) -> f64 {
+    -0.691 + 10.0 * power.log10()
+}
+
+/// The BS.1770 pre-filter, a high shelf modelling the head followed by a
+/// high pass, worked out for `rate` instead of the tabled 48 kHz.
+fn k_weighting(rate: f64) -> [Biquad; 2] {
+    let k = (PI * 1681.974450955533 / rate).tan();
+    let q = 0.7071752369554196;
+    let vh = 10f64.powf(3.999843853973347 / 20.0);
+    let vb = vh.powf(0.4996667741545416);
+    let a0 = 1.0 + k / q + k * k;
+    let shelf = Biquad::new(
+        [
+            (vh + vb * k / q + k * k) / a0,
+            2.0 * (k * k - vh) / a0,
+            (vh - vb * k / q + k * k) / a0,
+        ],
+        [2.0 * (k * k - 1.0) / a0, (1.0 - k / q + k * k) / a0],
+    );
+
+    let k = (PI * 38.13547087602444 / rate).tan();
+    let q = 0.5003270373238773;
+    let a0 = 1.0 + k / q + k * k;
+    let high_pass = Biquad::new(
+        [1.0, -2.0, 1.0],
+        [2.0 * (k * k - 1.0) / a0, (1.0 - k / q + k * k) / a0],
+    );
+    [shelf, high_pass]
+}
+
+/// Brings a track to the target loudness and keeps its true peaks under
+/// -1 dBTP, also when the volume is turned up past 1.0. Measures the track
+/// whether normalization is on or not.
+pub(crate) struct NormalizeSource<S> {
+    inner: S,
+    track: Arc<TrackLoudness>,
+    channels: usize,
+    /// Channel of the next sample.
+    channel: usize,
+    filters: Vec<[Biquad; 2]>,
+    step_power: f64,
+    step_frames: u64,
+    frames_per_step: u64,
+    gain: f32,
+    target_gain: f32,
+    gain_coeff: f32,
+    /// Last three gained samples per channel, for the peaks between samples.
+    history: Vec<[f32; 3]>,
+    delay: VecDeque<f32>,
+    delay_len: usize,
+    /// Smallest gain the peaks in the lookahead need.
+    held: f32,
+    hold: usize,
+    envelope: f32,
+    attack_coeff: f32,
+    release_coeff: f32,
+}
+
+impl<S: Source> NormalizeSource<S> {
+    fn new(inner: S, track: Arc<TrackLoudness>) -> Self {
+        let channels = usize::from(inner.channels()).max(1);
+        let rate = f64::from(inner.sample_rate()).max(1.0);
+        let target_gain = track
+            .control
+            .target()
+            .map_or(1.0, |target| track.gain_for(target));
+        let gain = match f32::from_bits(track.gain.load(Ordering::Relaxed)) {
+            g if g.is_nan() => target_gain,
+            g => g,
+        };
+        let lookahead_frames = (rate * LIMITER_LOOKAHEAD.as_secs_f64()).ceil().max(1.0);
+        let samples_per_sec = rate * channels as f64;
+        Self {
+            inner,
+            track,
+            channels,
+            channel: 0,
+            filters: vec![k_weighting(rate); channels],
+            step_power: 0.0,
+            step_frames: 0,
+            frames_per_step: (rate * STEP_MS as f64 / 1000.0) as u64,
+            gain,
+            target_gain,
+            gain_coeff: (1.0 - (-1.0 / (GAIN_TIME_CONSTANT_SECS * samples_per_sec)).exp())
+                as f32,
+            history: vec![[0.0; 3]; channels],
+            delay: VecDeque::with_capacity(lookahead_frames as usize * channels + 1),
+            delay_len: lookahead_frames as usize * channels,
+            held: 1.0,
+            hold: 0,
+            envelope: 1.0,
+            // most of the way down by the time a peak leaves the lookahead
+            attack_coeff: (1.0 - (-5.0 / (lookahead_frames * channels as f64)).exp()) as f32,
+            release_coeff: (1.0 - (-1.0 / (LIMITER_RELEASE_SECS * samples_per_sec)).exp())
+                as f32,
+        }
+    }
+
+    fn measure(&mut self, sample: f32) {
+        let [shelf, high_pass] = &mut self.filters[self.channel];
+        let weighted = high_pass.process(shelf.process(f64::from(sample)));
+        self.step_power += weighted * weighted;
+        if self.channel + 1 < self.channels {
+            return;
+        }
+        self.step_frames += 1;
+        if self.step_frames < self.frames_per_step {
+            return;
+        }
+        let power = self.step_power / self.step_frames as f64;
+        self.track.meter.lock().unwrap().push_step(power);
+        self.step_power = 0.0;
+        self.step_frames = 0;
+        self.target_gain = self
+            .track
+            .control
+            .target()
+            .map_or(1.0, |target| self.track.gain_for(target));
+        self.track.gain.store(self.gain.to_bits(), Ordering::Relaxed);
+    }
+
+    /// Highest level around the newest sample of this channel, with the
+    /// point halfway to the one before estimated from the samples around it.
+    fn true_peak(&mut self, sample: f32) -> f32 {
+        let [a, b, c] = self.history[self.channel];
+        self.history[self.channel] = [b, c, sample];
+        let between = (-a + 9.0 * b + 9.0 * c - sample) / 16.0;
+        sample.abs().max(between.abs())
+    }
+
+    /// Feeds one sample into the lookahead and returns the one leaving it.
+    fn limit(&mut self, sample: Option<f32>, ceiling: f32) -> Option<f32> {
+        if let Some(sample) = sample {
+            let peak = self.true_peak(sample);
+            let needed = if peak > ceiling { ceiling / peak } else { 1.0 };
+            if needed <= self.held {
+                self.held = needed;
+                self.hold = self.delay_len;
+            } else if self.hold > 0 {
+                self.hold -= 1;
+            } else {
+                self.held = needed;
+            }
+            self.delay.push_back(sample);
+            if self.delay.len() <= self.delay_len {
+                return None;
+            }
+        }
+        let out = self.delay.pop_front()?;
+        let coeff = if self.held < self.envelope {
+            self.attack_coeff
+        } else {
+            self.release_coeff
+        };
+        self.envelope += (self.held - self.envelope) * coeff;
+        Some(soft_clip(out * self.envelope, ceiling))
+    }
+}
+
+/// Leaves anything under the knee alone and bends the rest smoothly into the ceiling.
+fn soft_clip(sample: f32, ceiling: f32) -> f32 {
+    let knee = ceiling * LIMITER_KNEE;
+    let level = sample.abs();
+    if level <= knee {
+        return sample;
+    }
+    let room = ceiling - knee;
+    sample.signum() * (knee + room * ((level - knee) / room).tanh())
+}
+
+impl<S: Source> Iterator for NormalizeSource<S> {
+    type Item = f32;
+
+    fn next(&mut self) -> Option<f32> {
+        let control = &self.track.control;
+        let volume = control.volume();
+        let ceiling = if control.target().is_some() || volume > 1.0 {
+            LIMITER_CEILING / volume.max(1.0)
+        } else {
+            f32::INFINITY
+        };
+        loop {
+            let Some(sample) = self.inner.next() else {
+                // let the lookahead play out
+                return self.limit(None, ceiling);
+            };
+            self.measure(sample);
+            self.gain += (self.target_gain - self.gain) * self.gain_coeff;
+            let out = self.limit(Some(sample * self.gain), ceiling);
+            self.channel = (self.channel + 1) % self.channels;
+            if out.is_some() {
+                return out;
+            }
+        }
+    }
+}
+
+impl<S: Source> Source for NormalizeSource<S> {
+    fn current_span_len(&self) -> Option<usize> {
+        // the lookahead shifts span boundaries, a segment keeps one format throughout
+        None
+    }
+
+    fn channels(&self) -> u16 {
+        self.inner.channels()
+    }
+
+    fn sample_rate(&self) -> u32 {
+        self.inner.sample_rate()
+    }
+
+    fn total_duration(&self) -> Option<Duration> {
+        self.inner.total_duration()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn measurement(measured_ms: u64) -> Measurement {
+        Measurement {
+            lufs: -14.0,
+            measured_ms,
+            used: 0,
+        }
+    }
+
+    #[test]
+    fn memory_keeps_the_longer_measurement() {
+        let memory = LoudnessMemory::new(HashMap::new());
+        assert!(memory.record("a", measurement(30_000)));
+        assert!(!memory.record("a", measurement(25_000)));
+        assert!(memory.record("a", measurement(40_000)));
+        assert_eq!(memory.get("a").map(|m| m.measured_ms), Some(40_000));
+    }
+
+    #[test]
+    fn memory_forgets_the_least_recently_played() {
+        let memory = LoudnessMemory::new(HashMap::new());
+        for i in 0..MAX_REMEMBERED {
+            memory.record(&i.to_string(), measurement(30_000));
+        }
+        memory.get("0");
+        memory.record("new", measurement(30_000));
+
+        let tracks = memory.tracks.lock().unwrap();
+        assert_eq!(tracks.len(), MAX_REMEMBERED);
+        assert!(tracks.contains_key("0"));
+        assert!(!tracks.contains_key("1"));
+        assert!(tracks.contains_key("new"));
+    }
+}
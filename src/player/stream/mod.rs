@@ -1,7 +1,18 @@
 mod hls;
 mod cache;
+mod bandwidth;
+mod biquad;
+mod clock;
+mod equalizer;
+mod loudness;
 mod sample;
+mod speed;
 mod downloader;
 mod engine;
 
+pub(crate) use clock::PlaybackClock;
+pub(crate) use equalizer::EqualizerControl;
+pub(crate) use hls::StreamVariant;
+pub(crate) use loudness::LoudnessControl;
+pub(crate) use speed::SpeedControl;
 pub(crate) use engine::{PlaybackEngine, open_output_stream};
@@ -3,16 +3,35 @@ use rodio::Source;
 use std::collections::VecDeque;
 use std::sync::{Arc, Mutex};
 
+use super::clock::PlaybackClock;
+
 pub(crate) const WAVE_BUFFER_CAP: usize = 8192;
 
+/// Copies samples into the visualizer buffer and counts them on the clock as
+/// the output pulls them.
 pub(crate) struct TapSource<S> {
     inner: S,
     buffer: Arc<Mutex<VecDeque<f32>>>,
+    clock: Arc<PlaybackClock>,
+    generation: u64,
+    part: u64,
 }
 
 impl<S> TapSource<S> {
-    pub(crate) fn new(inner: S, buffer: Arc<Mutex<VecDeque<f32>>>) -> Self {
-        Self { inner, buffer }
+    pub(crate) fn new(
+        inner: S,
+        buffer: Arc<Mutex<VecDeque<f32>>>,
+        clock: Arc<PlaybackClock>,
+        generation: u64,
+        part: u64,
+    ) -> Self {
+        Self {
+            inner,
+            buffer,
+            clock,
+            generation,
+            part,
+        }
     }
 }
 
@@ -25,6 +44,8 @@ where
 
     fn next(&mut self) -> Option<Self::Item> {
         let sample = self.inner.next()?;
+        let rate = u64::from(self.inner.sample_rate()) * u64::from(self.inner.channels());
+        self.clock.count(self.generation, self.part, rate);
         let sample_f32: f32 = sample.to_sample_();
         let mut buffer = self.buffer.lock().unwrap();
         if buffer.len() >= WAVE_BUFFER_CAP {
@@ -0,0 +1,444 @@
+use rodio::Source;
+use std::collections::VecDeque;
+use std::f32::consts::PI;
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::time::Duration;
+
+pub(crate) const MIN_SPEED: f32 = 0.5;
+pub(crate) const MAX_SPEED: f32 = 2.0;
+
+/// Output advances by half a grain per step, grains overlap by half.
+const GRAIN: Duration = Duration::from_millis(40);
+/// How far a grain may move from its nominal spot to line up with the last one.
+const SEARCH: Duration = Duration::from_millis(8);
+/// Only every so many frames are compared while lining grains up.
+const SEARCH_STRIDE: usize = 4;
+
+/// Playback rate the sinks and the time stretch read, shared with `Player`.
+pub(crate) struct SpeedControl {
+    /// As `f32` bits.
+    speed: AtomicU32,
+    preserve_pitch: AtomicBool,
+}
+
+impl SpeedControl {
+    pub(crate) fn new() -> Self {
+        Self {
+            speed: AtomicU32::new(1.0f32.to_bits()),
+            preserve_pitch: AtomicBool::new(false),
+        }
+    }
+
+    pub(crate) fn speed(&self) -> f32 {
+        f32::from_bits(self.speed.load(Ordering::Relaxed))
+    }
+
+    pub(crate) fn set_speed(&self, speed: f32) {
+        self.speed
+            .store(speed.clamp(MIN_SPEED, MAX_SPEED).to_bits(), Ordering::Relaxed);
+    }
+
+    /// Returns whether that changed anything.
+    pub(crate) fn set_preserve_pitch(&self, preserve_pitch: bool) -> bool {
+        self.preserve_pitch.swap(preserve_pitch, Ordering::Relaxed) != preserve_pitch
+    }
+
+    /// Speed for the sink, which resamples and so shifts the pitch with it.
+    /// The time stretch does the work instead while the pitch is kept.
+    pub(crate) fn sink_speed(&self) -> f32 {
+        if self.preserve_pitch.load(Ordering::Relaxed) {
+            1.0
+        } else {
+            self.speed()
+        }
+    }
+
+    fn stretch(&self) -> Option<f32> {
+        let speed = self.speed();
+        (self.preserve_pitch.load(Ordering::Relaxed) && speed != 1.0).then_some(speed)
+    }
+}
+
+/// The time stretch of one generation. A segment that runs out mid-grain
+/// leaves its grains here for the next one to carry on with, so the audio is
+/// stretched as one stream instead of each segment flushing its own end.
+pub(crate) struct StretchChain {
+    control: Arc<SpeedControl>,
+    carried: Mutex<Option<Carried>>,
+    /// Set once no segment will follow the ones queued, so the last plays out.
+    closed: AtomicBool,
+}
+
+impl StretchChain {
+    pub(crate) fn new(control: Arc<SpeedControl>) -> Arc<Self> {
+        Arc::new(Self {
+            control,
+            carried: Mutex::new(None),
+            closed: AtomicBool::new(false),
+        })
+    }
+
+    /// Puts the time stretch after `inner`, one segment of this generation.
+    pub(crate) fn wrap<S: Source>(self: &Arc<Self>, inner: S) -> TimeStretch<S> {
+        TimeStretch::new(inner, Arc::clone(self))
+    }
+
+    pub(crate) fn close(&self) {
+        self.closed.store(true, Ordering::Relaxed);
+    }
+}
+
+/// What a segment left unfinished, in the frames it counted.
+struct Carried {
+    rate: u32,
+    channels: usize,
+    input: VecDeque<f32>,
+    input_start: usize,
+    input_end: usize,
+    nominal: f64,
+    last_grain: Option<usize>,
+    tail: Vec<f32>,
+}
+
+/// Changes speed without changing pitch, by overlapping short grains of the
+/// audio that are taken further apart or closer together than they are laid
+/// down (WSOLA). Each grain is nudged to where it best continues the one
+/// before, which keeps the phase from smearing. Passes the audio through
+/// untouched whenever it isn't needed.
+pub(crate) struct TimeStretch<S> {
+    inner: S,
+    chain: Arc<StretchChain>,
+    rate: u32,
+    channels: usize,
+    /// Frames per half grain.
+    hop: usize,
+    search: usize,
+    /// Rising half of a Hann window, the falling half is its mirror.
+    window: Vec<f32>,
+    /// Interleaved input from frame `input_start` on.
+    input: VecDeque<f32>,
+    input_start: usize,
+    /// Frames taken from `inner` so far.
+    input_end: usize,
+    /// Where the next grain would be taken if it didn't need lining up.
+    nominal: f64,
+    /// Start of the last grain, `None` while passing through.
+    last_grain: Option<usize>,
+    /// Falling half of the last grain, waiting for the next to overlap it.
+    tail: Vec<f32>,
+    output: VecDeque<f32>,
+    /// Channel of the next sample while passing through.
+    channel: usize,
+    ended: bool,
+    /// Whether what the segment before left was taken over yet.
+    resumed: bool,
+    /// Set once what's left went to the next segment.
+    handed_over: bool,
+}
+
+impl<S: Source> TimeStretch<S> {
+    fn new(inner: S, chain: Arc<StretchChain>) -> Self {
+        let channels = usize::from(inner.channels()).max(1);
+        let sample_rate = inner.sample_rate();
+        let rate = sample_rate as f32;
+        let hop = ((rate * GRAIN.as_secs_f32() / 2.0) as usize).max(1);
+        let search = (rate * SEARCH.as_secs_f32()) as usize;
+        let window = (0..hop)
+            .map(|i| {
+                let t = (i as f32 + 0.5) / (2 * hop) as f32;
+                (PI * t).sin().powi(2)
+            })
+            .collect();
+        Self {
+            inner,
+            chain,
+            rate: sample_rate,
+            channels,
+            hop,
+            search,
+            window,
+            input: VecDeque::new(),
+            input_start: 0,
+            input_end: 0,
+            nominal: 0.0,
+            last_grain: None,
+            tail: Vec::new(),
+            output: VecDeque::new(),
+            channel: 0,
+            ended: false,
+            resumed: false,
+            handed_over: false,
+        }
+    }
+
+    /// Carries on from the segment before, unless the format changed with it.
+    fn resume(&mut self) {
+        self.resumed = true;
+        let carried = self.chain.carried.lock().unwrap().take();
+        let Some(carried) = carried.filter(|c| c.rate == self.rate && c.channels == self.channels)
+        else {
+            return;
+        };
+        self.input = carried.input;
+        self.input_start = carried.input_start;
+        self.input_end = carried.input_end;
+        self.nominal = carried.nominal;
+        self.last_grain = carried.last_grain;
+        self.tail = carried.tail;
+    }
+
+    /// Leaves what this segment couldn't finish for the next one, and ends it.
+    fn hand_over(&mut self) -> Option<f32> {
+        self.handed_over = true;
+        // a segment cut off mid-frame would put the next one out of step
+        self.input.truncate((self.input_end - self.input_start) * self.channels);
+        *self.chain.carried.lock().unwrap() = Some(Carried {
+            rate: self.rate,
+            channels: self.channels,
+            input: std::mem::take(&mut self.input),
+            input_start: self.input_start,
+            input_end: self.input_end,
+            nominal: self.nominal,
+            last_grain: self.last_grain.take(),
+            tail: std::mem::take(&mut self.tail),
+        });
+        None
+    }
+
+    /// Whether frames up to `end` are in, or no more will come after this segment.
+    fn buffer(&mut self, end: usize) -> bool {
+        self.fill_to(end);
+        self.input_end >= end || self.chain.closed.load(Ordering::Relaxed)
+    }
+
+    /// Reads from `inner` until frame `end` is buffered or it runs out.
+    fn fill_to(&mut self, end: usize) {
+        while !self.ended && self.input_end < end {
+            for _ in 0..self.channels {
+                match self.inner.next() {
+                    Some(sample) => self.input.push_back(sample),
+                    None => {
+                        self.ended = true;
+                        break;
+                    }
+                }
+            }
+            if !self.ended {
+                self.input_end += 1;
+            }
+        }
+    }
+
+    fn sample(&self, frame: usize, channel: usize) -> f32 {
+        frame
+            .checked_sub(self.input_start)
+            .and_then(|offset| self.input.get(offset * self.channels + channel))
+            .copied()
+            .unwrap_or(0.0)
+    }
+
+    /// Mono sum of a frame, for lining grains up.
+    fn mono(&self, frame: usize) -> f32 {
+        (0..self.channels).map(|c| self.sample(frame, c)).sum()
+    }
+
+    /// Start near `nominal` whose first half sounds most like what naturally
+    /// follows the last grain.
+    fn best_start(&self, last_grain: usize, nominal: usize) -> usize {
+        let natural: Vec<f32> = (0..self.hop)
+            .step_by(SEARCH_STRIDE)
+            .map(|i| self.mono(last_grain + self.hop + i))
+            .collect();
+        let first = nominal.saturating_sub(self.search).max(self.input_start);
+        let last = nominal + self.search;
+        let candidates: Vec<f32> = (first..last + self.hop).map(|f| self.mono(f)).collect();
+
+        let mut best = (nominal.max(first), f32::MIN);
+        for start in first..=last {
+            let offset = start - first;
+            let (mut dot, mut energy) = (0.0, 0.0);
+            for (j, target) in natural.iter().enumerate() {
+                let x = candidates[offset + j * SEARCH_STRIDE];
+                dot += x * target;
+                energy += x * x;
+            }
+            let score = dot / energy.sqrt().max(1e-6);
+            if score > best.1 {
+                best = (start, score);
+            }
+        }
+        best.0
+    }
+
+    /// Overlaps the rising half of the grain at `start` with the waiting tail.
+    /// Without a tail the audio is coming out of passthrough and plays as is.
+    fn overlap_rising_half(&mut self, start: usize) {
+        for i in 0..self.hop {
+            for c in 0..self.channels {
+                let x = self.sample(start + i, c);
+                let out = match self.tail.get(i * self.channels + c) {
+                    Some(tail) => tail + x * self.window[i],
+                    None => x,
+                };
+                self.output.push_back(out);
+            }
+        }
+    }
+
+    /// Lays down one more half grain at `speed`.
+    fn step(&mut self, speed: f32) {
+        let nominal = self.nominal.round() as usize;
+        self.fill_to(nominal + self.search + 2 * self.hop);
+        let start = match self.last_grain {
+            Some(last_grain) => self.best_start(last_grain, nominal),
+            None => nominal,
+        };
+        self.overlap_rising_half(start);
+        self.tail = (0..self.hop)
+            .flat_map(|i| (0..self.channels).map(move |c| (i, c)))
+            .map(|(i, c)| self.sample(start + self.hop + i, c) * self.window[self.hop - 1 - i])
+            .collect();
+        self.last_grain = Some(start);
+        self.nominal += f64::from(speed) * self.hop as f64;
+        self.drop_input_before(start.min(nominal.saturating_sub(self.search)));
+    }
+
+    /// Goes back to passing the audio through, picking up right where the
+    /// last grain's tail fades out.
+    fn finish(&mut self) {
+        let Some(last_grain) = self.last_grain.take() else {
+            return;
+        };
+        let resume = last_grain + self.hop;
+        self.fill_to(resume + self.hop);
+        self.overlap_rising_half(resume);
+        self.tail.clear();
+        self.drop_input_before(resume + self.hop);
+        self.output.extend(self.input.drain(..));
+        self.input_start = self.input_end;
+        self.nominal = self.input_end as f64;
+        self.channel = 0;
+    }
+
+    fn drop_input_before(&mut self, frame: usize) {
+        let frames = frame.saturating_sub(self.input_start);
+        let samples = (frames * self.channels).min(self.input.len());
+        self.input.drain(..samples);
+        self.input_start += samples / self.channels;
+    }
+}
+
+impl<S: Source> Iterator for TimeStretch<S> {
+    type Item = f32;
+
+    fn next(&mut self) -> Option<f32> {
+        if self.handed_over {
+            return None;
+        }
+        if !self.resumed {
+            self.resume();
+        }
+        loop {
+            if let Some(sample) = self.output.pop_front() {
+                return Some(sample);
+            }
+            // the last segment of all finishes its grains on what it has
+            let last = self.ended && self.chain.closed.load(Ordering::Relaxed);
+            let stretch = self.chain.control.stretch().filter(|_| !last);
+            match (stretch, self.last_grain) {
+                // stretching starts on a frame boundary
+                (Some(speed), _) if self.channel == 0 => {
+                    let needed = self.nominal.round() as usize + self.search + 2 * self.hop;
+                    if !self.buffer(needed) {
+                        return self.hand_over();
+                    }
+                    self.step(speed);
+                }
+                (_, Some(last_grain)) => {
+                    if !self.buffer(last_grain + 2 * self.hop) {
+                        return self.hand_over();
+                    }
+                    self.finish();
+                }
+                // left over from a segment that ended as stretching started
+                _ if !self.input.is_empty() => {
+                    self.output.extend(self.input.drain(..));
+                    self.input_start = self.input_end;
+                    self.nominal = self.input_end as f64;
+                }
+                _ => {
+                    let Some(sample) = self.inner.next() else {
+                        return self.hand_over();
+                    };
+                    self.channel += 1;
+                    if self.channel == self.channels {
+                        self.channel = 0;
+                        self.input_end += 1;
+                        self.input_start = self.input_end;
+                        self.nominal = self.input_end as f64;
+                    }
+                    return Some(sample);
+                }
+            }
+        }
+    }
+}
+
+impl<S: Source> Source for TimeStretch<S> {
+    fn current_span_len(&self) -> Option<usize> {
+        // buffering shifts span boundaries, a segment keeps one format throughout
+        None
+    }
+
+    fn channels(&self) -> u16 {
+        self.inner.channels()
+    }
+
+    fn sample_rate(&self) -> u32 {
+        self.inner.sample_rate()
+    }
+
+    fn total_duration(&self) -> Option<Duration> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rodio::buffer::SamplesBuffer;
+
+    const RATE: u32 = 8_000;
+
+    fn chain(speed: f32) -> Arc<StretchChain> {
+        let control = Arc::new(SpeedControl::new());
+        control.set_speed(speed);
+        control.set_preserve_pitch(true);
+        StretchChain::new(control)
+    }
+
+    fn segment(frames: usize) -> SamplesBuffer {
+        let samples: Vec<f32> = (0..frames).map(|i| (i as f32 * 0.05).sin()).collect();
+        SamplesBuffer::new(1, RATE, samples)
+    }
+
+    #[test]
+    fn passes_segments_through_at_normal_speed() {
+        let chain = chain(1.0);
+        assert_eq!(chain.wrap(segment(8_000)).count(), 8_000);
+        chain.close();
+        assert_eq!(chain.wrap(segment(8_000)).count(), 8_000);
+    }
+
+    #[test]
+    fn segments_hand_their_grains_on() {
+        let chain = chain(2.0);
+        let first = chain.wrap(segment(8_000)).count();
+        chain.close();
+        let second = chain.wrap(segment(8_000)).count();
+        // twice as fast, with no segment end let out unstretched
+        assert!(first <= 4_000);
+        assert!((first + second).abs_diff(8_000) <= 320);
+    }
+}
@@ -4,13 +4,20 @@ use rodio::Sink;
 use std::collections::VecDeque;
 use std::sync::{
     Arc, Mutex,
-    atomic::{AtomicBool, Ordering},
-    mpsc::Receiver,
+    atomic::{AtomicBool, AtomicU64, Ordering},
+    mpsc::{Receiver, RecvTimeoutError, Sender},
 };
-use std::time::{Duration, Instant};
+use std::time::Duration;
 
+use super::PlayerEvent;
 use super::commands::PlayerCommand;
-use super::stream::{PlaybackEngine, open_output_stream};
+use super::stream::{
+    EqualizerControl, LoudnessControl, PlaybackClock, PlaybackEngine, SpeedControl,
+    StreamVariant, open_output_stream,
+};
+
+/// How often the playing track is checked on while no commands come in.
+const WATCH_INTERVAL: Duration = Duration::from_millis(100);
 
 pub(crate) fn player_loop(
     rx: Receiver<PlayerCommand>,
@@ -18,26 +25,48 @@ pub(crate) fn player_loop(
     is_playing_flag: Arc<AtomicBool>,
     is_seeking_flag: Arc<AtomicBool>,
     sink_arc: Arc<Mutex<Option<Sink>>>,
-    elapsed_time: Arc<Mutex<Duration>>,
-    last_start: Arc<Mutex<Option<Instant>>>,
+    clock: Arc<PlaybackClock>,
+    crossfade_ms: Arc<AtomicU64>,
+    loudness: Arc<LoudnessControl>,
+    equalizer: Arc<EqualizerControl>,
+    speed: Arc<SpeedControl>,
+    events: Sender<PlayerEvent>,
     current_track: Arc<Mutex<Option<Track>>>,
+    stream_variant: Arc<Mutex<Option<StreamVariant>>>,
     wave_buffer: Arc<Mutex<VecDeque<f32>>>,
 ) {
     let stream = open_output_stream();
-    let mut engine = PlaybackEngine::new(Arc::clone(&stream)).unwrap();
-
-    for msg in rx {
+    let mut engine = PlaybackEngine::new(
+        Arc::clone(&stream),
+        Arc::clone(&clock),
+        crossfade_ms,
+        Arc::clone(&loudness),
+        equalizer,
+        Arc::clone(&speed),
+        events.clone(),
+    )
+    .unwrap();
+
+    loop {
+        engine.watch(&sink_arc, &is_playing_flag, &current_track);
+        let msg = match rx.recv_timeout(WATCH_INTERVAL) {
+            Ok(msg) => msg,
+            Err(RecvTimeoutError::Timeout) => continue,
+            Err(RecvTimeoutError::Disconnected) => break,
+        };
         match msg {
             PlayerCommand::Play(track) => {
+                if engine.claim_gapless(&track) {
+                    continue;
+                }
                 engine.play_from_position(
                     &track,
                     0,
                     &token,
                     &sink_arc,
                     &is_playing_flag,
-                    &elapsed_time,
-                    &last_start,
                     &current_track,
+                    &stream_variant,
                     &wave_buffer,
                 );
             }
@@ -49,9 +78,8 @@ pub(crate) fn player_loop(
                     &token,
                     &sink_arc,
                     &is_playing_flag,
-                    &elapsed_time,
-                    &last_start,
                     &current_track,
+                    &stream_variant,
                     &wave_buffer,
                 );
             }
@@ -60,16 +88,12 @@ pub(crate) fn player_loop(
                 let _ = engine.preload_next_track(&track, &token);
             }
 
+            PlayerCommand::ClearPreload => engine.clear_preload(),
+
             PlayerCommand::Pause => {
                 if let Some(ref s) = *sink_arc.lock().unwrap() {
                     s.pause();
                     is_playing_flag.store(false, Ordering::SeqCst);
-
-                    if let Some(start) = *last_start.lock().unwrap() {
-                        let mut elapsed = elapsed_time.lock().unwrap();
-                        *elapsed += start.elapsed();
-                    }
-                    *last_start.lock().unwrap() = None;
                 }
             }
 
@@ -77,7 +101,6 @@ pub(crate) fn player_loop(
                 if let Some(ref s) = *sink_arc.lock().unwrap() {
                     s.play();
                     is_playing_flag.store(true, Ordering::SeqCst);
-                    *last_start.lock().unwrap() = Some(Instant::now());
                 }
             }
 
@@ -85,6 +108,8 @@ pub(crate) fn player_loop(
                 if let Some(ref s) = *sink_arc.lock().unwrap() {
                     let new_volume = (s.volume() + 0.1).min(2.0);
                     s.set_volume(new_volume);
+                    loudness.set_volume(new_volume);
+                    let _ = events.send(PlayerEvent::VolumeChanged(new_volume));
                 }
             }
 
@@ -92,105 +117,68 @@ pub(crate) fn player_loop(
                 if let Some(ref s) = *sink_arc.lock().unwrap() {
                     let new_volume = (s.volume() - 0.1).max(0.0);
                     s.set_volume(new_volume);
+                    loudness.set_volume(new_volume);
+                    let _ = events.send(PlayerEvent::VolumeChanged(new_volume));
                 }
             }
 
-            PlayerCommand::NextSong => {}
+            PlayerCommand::SetSpeed => {
+                if let Some(ref s) = *sink_arc.lock().unwrap() {
+                    s.set_speed(speed.sink_speed());
+                }
+            }
 
-            PlayerCommand::PrevSong => {}
+            PlayerCommand::SetStreamQuality(quality) => {
+                if !engine.set_stream_quality(quality) || !is_playing_flag.load(Ordering::SeqCst) {
+                    continue;
+                }
+                let track = current_track.lock().unwrap().clone();
+                if let Some(track) = track {
+                    engine.play_from_position(
+                        &track,
+                        clock.position_ms(),
+                        &token,
+                        &sink_arc,
+                        &is_playing_flag,
+                        &current_track,
+                        &stream_variant,
+                        &wave_buffer,
+                    );
+                }
+            }
 
-            PlayerCommand::FastForward => {
+            PlayerCommand::Seek(target) => {
                 if is_seeking_flag.swap(true, Ordering::SeqCst) {
                     continue;
                 }
-                let current_track_guard = current_track.lock().unwrap();
-                if let Some(track) = current_track_guard.clone() {
-                    drop(current_track_guard);
-
-                    let elapsed = elapsed_time.lock().unwrap();
-                    let current_elapsed = if is_playing_flag.load(Ordering::SeqCst) {
-                        if let Some(start) = *last_start.lock().unwrap() {
-                            *elapsed + start.elapsed()
-                        } else {
-                            *elapsed
-                        }
-                    } else {
-                        *elapsed
-                    };
-
-                    let new_elapsed = current_elapsed + Duration::from_secs(10);
-                    let max_duration = Duration::from_millis(track.duration_ms);
+                let track = current_track.lock().unwrap().clone();
+                if let Some(track) = track {
+                    // the manifest knows the real length, the api's duration can be off
+                    let duration_ms = engine.duration_ms(&track).unwrap_or(track.duration_ms);
+                    let new_position_ms = target.resolve(clock.position_ms(), duration_ms);
 
-                    drop(elapsed);
-
-                    if new_elapsed >= max_duration {
+                    if new_position_ms >= duration_ms {
                         if let Some(ref s) = *sink_arc.lock().unwrap() {
                             s.stop();
                         }
                         is_playing_flag.store(false, Ordering::SeqCst);
-                        *elapsed_time.lock().unwrap() = max_duration;
-                        *last_start.lock().unwrap() = None;
+                        clock.hold(duration_ms);
+                        let _ = events.send(PlayerEvent::Ended);
                     } else {
-                        let new_position_ms = new_elapsed.as_millis() as u64;
                         engine.play_from_position(
                             &track,
                             new_position_ms,
                             &token,
                             &sink_arc,
                             &is_playing_flag,
-                            &elapsed_time,
-                            &last_start,
                             &current_track,
+                            &stream_variant,
                             &wave_buffer,
                         );
                     }
                 }
                 is_seeking_flag.store(false, Ordering::SeqCst);
             }
-
-            PlayerCommand::Rewind => {
-                if is_seeking_flag.swap(true, Ordering::SeqCst) {
-                    continue;
-                }
-                let current_track_guard = current_track.lock().unwrap();
-                if let Some(track) = current_track_guard.clone() {
-                    drop(current_track_guard);
-
-                    let elapsed = elapsed_time.lock().unwrap();
-                    let current_elapsed = if is_playing_flag.load(Ordering::SeqCst) {
-                        if let Some(start) = *last_start.lock().unwrap() {
-                            *elapsed + start.elapsed()
-                        } else {
-                            *elapsed
-                        }
-                    } else {
-                        *elapsed
-                    };
-
-                    let rewind_duration = Duration::from_secs(10);
-                    let new_elapsed = if current_elapsed > rewind_duration {
-                        current_elapsed - rewind_duration
-                    } else {
-                        Duration::ZERO
-                    };
-
-                    drop(elapsed);
-
-                    let new_position_ms = new_elapsed.as_millis() as u64;
-                    engine.play_from_position(
-                        &track,
-                        new_position_ms,
-                        &token,
-                        &sink_arc,
-                        &is_playing_flag,
-                        &elapsed_time,
-                        &last_start,
-                        &current_track,
-                        &wave_buffer,
-                    );
-                }
-                is_seeking_flag.store(false, Ordering::SeqCst);
-            }
         }
     }
 }
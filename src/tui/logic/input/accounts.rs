@@ -0,0 +1,58 @@
+use ratatui::crossterm::event::{KeyCode, KeyEvent};
+
+use super::InputOutcome;
+use crate::config::{active_profile, is_valid_profile_name};
+use crate::tui::logic::state::{AccountPicker, AppState, Prompt, PromptKind};
+
+pub(crate) fn open_account_picker(state: &mut AppState) {
+    state.account_picker = Some(AccountPicker::new(&active_profile()));
+}
+
+pub(crate) fn handle_account_picker_key(key: KeyEvent, state: &mut AppState) -> InputOutcome {
+    let Some(picker) = state.account_picker.as_mut() else {
+        return InputOutcome::Continue;
+    };
+    // the last row is "Add account…"
+    let row_count = picker.profiles.len() + 1;
+
+    match key.code {
+        KeyCode::Esc => state.account_picker = None,
+        _ if picker.switching.is_some() => {}
+        KeyCode::Up => picker.selected = picker.selected.saturating_sub(1),
+        KeyCode::Down if picker.selected + 1 < row_count => picker.selected += 1,
+        KeyCode::Enter => match picker.profiles.get(picker.selected).cloned() {
+            Some(profile) if profile == active_profile() => state.account_picker = None,
+            Some(profile) => request_account(state, profile),
+            None => {
+                state.account_picker = None;
+                state.prompt = Some(Prompt {
+                    title: "New account name".to_string(),
+                    input: String::new(),
+                    kind: PromptKind::NewAccount,
+                });
+            }
+        },
+        _ => {}
+    }
+    InputOutcome::Continue
+}
+
+/// Called with the name typed into the "New account" prompt.
+pub(crate) fn submit_new_account(state: &mut AppState, name: String) {
+    open_account_picker(state);
+    if !is_valid_profile_name(&name) {
+        if let Some(picker) = state.account_picker.as_mut() {
+            picker.error = Some("Use only letters, digits, '-' and '_'".to_string());
+        }
+        return;
+    }
+    request_account(state, name);
+}
+
+fn request_account(state: &mut AppState, profile: String) {
+    if let Some(picker) = state.account_picker.as_mut() {
+        picker.switching = Some(profile.clone());
+        picker.error = None;
+    }
+    state.account_request = Some(profile);
+}
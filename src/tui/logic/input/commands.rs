@@ -1,13 +1,25 @@
 use ratatui::crossterm::event::{KeyEvent, KeyModifiers};
 
+use crate::api::Album;
+
 use super::InputOutcome;
-use crate::tui::logic::state::{AppData, AppState, EngagementAction, FollowingTracksFocus, PlaybackSource};
+use crate::tui::logic::state::{
+    AppData, AppState, EngagementAction, FollowingTracksFocus, RadioRequest,
+};
 use crate::player::Player;
-use crate::tui::logic::utils::build_queue;
+use crate::tui::logic::utils::{active_tracks, build_queue};
 use crate::tui::logic::utils::build_search_matches;
 use crate::tui::logic::utils::{soundcloud_id_from_urn, soundcloud_playlist_id_from_tracks_uri};
 
+use super::accounts::open_account_picker;
+use super::comments::open_comment_prompt;
+use super::helpers::{selected_artist_urn, selected_track};
+use super::profile::open_profile_selected;
+use super::playlists::{open_playlist_menu, open_playlist_picker, remove_selected_playlist_track};
 use super::queue::{handle_add_to_queue, handle_add_next_to_queue};
+use super::seek::handle_seek_char;
+use super::settings::toggle_settings;
+use super::speed::handle_speed_char;
 
 pub(crate) fn handle_char(
     key: KeyEvent,
@@ -18,10 +30,14 @@ pub(crate) fn handle_char(
 ) -> InputOutcome {
     if key.modifiers.contains(KeyModifiers::SHIFT) {
         handle_shift_char(c, state, data, player)
-    } else if state.selected_tab == 0 {
-        handle_space(c, player)
     } else if state.selected_tab == 1 {
         handle_search_char(c, state)
+    } else if c.is_ascii_digit() || c == ':' {
+        handle_seek_char(c, state, player)
+    } else if matches!(c, '[' | ']' | '\\') {
+        handle_speed_char(c, player)
+    } else if state.selected_tab == 0 {
+        handle_space(c, player)
     } else {
         InputOutcome::Continue
     }
@@ -57,13 +73,7 @@ fn handle_shift_char(
         's' | 'S' => {
             state.shuffle_enabled = !state.shuffle_enabled;
             if let Some(current_idx) = state.current_playing_index {
-                let active_tracks = match state.playback_source {
-                    PlaybackSource::Likes => &data.likes,
-                    PlaybackSource::Playlist
-                    | PlaybackSource::Album
-                    | PlaybackSource::FollowingPublished
-                    | PlaybackSource::FollowingLikes => &data.playback_tracks,
-                };
+                let active_tracks = active_tracks(state, data);
                 state.auto_queue =
                     build_queue(current_idx, active_tracks, state.shuffle_enabled);
             }
@@ -80,6 +90,36 @@ fn handle_shift_char(
         'l' | 'L' => {
             enqueue_like_follow_selected(state, data);
         }
+        'c' | 'C' => {
+            state.comments_visible = !state.comments_visible;
+        }
+        'w' | 'W' => {
+            open_comment_prompt(state, player);
+        }
+        't' | 'T' => {
+            enqueue_repost_selected(state, data);
+        }
+        'o' | 'O' => {
+            state.autoplay_enabled = !state.autoplay_enabled;
+        }
+        'g' | 'G' => {
+            request_radio_selected(state, data);
+        }
+        'i' | 'I' => {
+            open_profile_selected(state, data);
+        }
+        'm' | 'M' => {
+            open_account_picker(state);
+        }
+        'p' | 'P' => {
+            open_playlist_picker(state, data);
+        }
+        'e' | 'E' => {
+            open_playlist_menu(state, data);
+        }
+        'x' | 'X' => {
+            remove_selected_playlist_track(state, data);
+        }
         'f' | 'F' => {
             if state.selected_tab == 0 {
                 state.search_popup_visible = true;
@@ -98,6 +138,7 @@ fn handle_shift_char(
         'h' | 'H' => {
             state.help_visible = !state.help_visible;
         }
+        'b' | 'B' => toggle_settings(state),
         'v' | 'V' => {
             state.visualizer_mode = !state.visualizer_mode;
         }
@@ -106,13 +147,7 @@ fn handle_shift_char(
             if state.queue_visible {
                 if let Some(current_idx) = state.current_playing_index {
                     if state.auto_queue.is_empty() {
-                        let active_tracks = match state.playback_source {
-                            PlaybackSource::Likes => &data.likes,
-                            PlaybackSource::Playlist
-                            | PlaybackSource::Album
-                            | PlaybackSource::FollowingPublished
-                            | PlaybackSource::FollowingLikes => &data.playback_tracks,
-                        };
+                        let active_tracks = active_tracks(state, data);
                         state.auto_queue = build_queue(
                             current_idx,
                             active_tracks,
@@ -156,6 +191,8 @@ fn handle_shift_char(
                 }
             }
         }
+        // some terminals report the shift it takes to type ':'
+        ':' if state.selected_tab != 1 => return handle_seek_char(c, state, player),
         _ => {}
     }
     InputOutcome::Continue
@@ -340,6 +377,147 @@ fn enqueue_like_follow_selected(state: &mut AppState, data: &mut AppData) {
             }
             _ => {}
         }
+    } else if state.selected_tab == 2 {
+        enqueue_like_feed_selected(state, data);
+    }
+}
+
+/// On the activity pane a playlist or album item is liked as a whole, otherwise the
+/// selected track is.
+fn enqueue_like_feed_selected(state: &mut AppState, data: &mut AppData) {
+    let playlist = data
+        .feed
+        .get(state.selected_row)
+        .and_then(|activity| activity.playlist())
+        .filter(|_| !state.info_pane_selected);
+
+    if let Some(playlist) = playlist {
+        let Some(playlist_id) = soundcloud_playlist_id_from_tracks_uri(&playlist.tracks_uri) else {
+            return;
+        };
+        if playlist.playlist_type.eq_ignore_ascii_case("album") {
+            if data.liked_album_uris.remove(&playlist.tracks_uri) {
+                state.engagement_queue.push_back(EngagementAction::UnlikeAlbum {
+                    tracks_uri: playlist.tracks_uri.clone(),
+                    playlist_id,
+                });
+            } else {
+                data.liked_album_uris.insert(playlist.tracks_uri.clone());
+                state.engagement_queue.push_back(EngagementAction::LikeAlbum {
+                    album: Album::from(playlist),
+                    playlist_id,
+                });
+            }
+        } else if data.liked_playlist_uris.remove(&playlist.tracks_uri) {
+            state.engagement_queue.push_back(EngagementAction::UnlikePlaylist {
+                tracks_uri: playlist.tracks_uri.clone(),
+                playlist_id,
+            });
+        } else {
+            data.liked_playlist_uris.insert(playlist.tracks_uri.clone());
+            let mut liked_playlist = playlist.clone();
+            liked_playlist.is_owned = false;
+            state.engagement_queue.push_back(EngagementAction::LikePlaylist {
+                playlist: liked_playlist,
+                playlist_id,
+            });
+        }
+        return;
+    }
+
+    let Some(track) = selected_track(state, data) else {
+        return;
+    };
+    let Some(track_id) = soundcloud_id_from_urn(&track.track_urn) else {
+        return;
+    };
+    if data.liked_track_urns.remove(&track.track_urn) {
+        state.engagement_queue.push_back(EngagementAction::UnlikeTrack {
+            track_urn: track.track_urn,
+            track_id,
+        });
+    } else {
+        data.liked_track_urns.insert(track.track_urn.clone());
+        state
+            .engagement_queue
+            .push_back(EngagementAction::LikeTrack { track, track_id });
+    }
+}
+
+/// Artist views seed the radio from the selected artist, everything else from the selected track.
+fn request_radio_selected(state: &mut AppState, data: &AppData) {
+    if let Some(user_urn) = selected_artist_urn(state, data) {
+        state.radio_request = Some(RadioRequest::Artist { user_urn });
+    } else if let Some(track) = selected_track(state, data) {
+        state.radio_request = Some(RadioRequest::Track(Box::new(track)));
+    }
+}
+
+/// Playlists and albums are reposted from their own tabs, everywhere else the
+/// selected track is.
+fn enqueue_repost_selected(state: &mut AppState, data: &mut AppData) {
+    let tracks_uri = if state.selected_tab == 0 {
+        match state.selected_subtab {
+            1 => data.playlists.get(state.selected_row).map(|p| p.tracks_uri.clone()),
+            2 => {
+                let search_active = state.search_popup_visible && !state.search_query.trim().is_empty();
+                let selected_idx = if search_active {
+                    state.search_matches.get(state.selected_row).copied()
+                } else {
+                    Some(state.selected_row)
+                };
+                selected_idx
+                    .and_then(|idx| data.albums.get(idx))
+                    .map(|a| a.tracks_uri.clone())
+            }
+            _ => None,
+        }
+    } else if state.selected_tab == 1 {
+        match state.selected_searchfilter {
+            1 => data.search_albums.get(state.selected_row).map(|a| a.tracks_uri.clone()),
+            2 => data.search_playlists.get(state.selected_row).map(|p| p.tracks_uri.clone()),
+            _ => None,
+        }
+    } else {
+        None
+    };
+
+    if let Some(tracks_uri) = tracks_uri {
+        let Some(playlist_id) = soundcloud_playlist_id_from_tracks_uri(&tracks_uri) else {
+            return;
+        };
+        if data.reposted_playlist_uris.remove(&tracks_uri) {
+            state.engagement_queue.push_back(EngagementAction::UnrepostPlaylist {
+                tracks_uri,
+                playlist_id,
+            });
+        } else {
+            data.reposted_playlist_uris.insert(tracks_uri.clone());
+            state.engagement_queue.push_back(EngagementAction::RepostPlaylist {
+                tracks_uri,
+                playlist_id,
+            });
+        }
+        return;
+    }
+
+    let Some(track) = selected_track(state, data) else {
+        return;
+    };
+    let Some(track_id) = soundcloud_id_from_urn(&track.track_urn) else {
+        return;
+    };
+    if data.reposted_track_urns.remove(&track.track_urn) {
+        state.engagement_queue.push_back(EngagementAction::UnrepostTrack {
+            track_urn: track.track_urn,
+            track_id,
+        });
+    } else {
+        data.reposted_track_urns.insert(track.track_urn.clone());
+        state.engagement_queue.push_back(EngagementAction::RepostTrack {
+            track_urn: track.track_urn,
+            track_id,
+        });
     }
 }
 
@@ -0,0 +1,44 @@
+use ratatui::crossterm::event::{KeyCode, KeyEvent};
+
+use super::InputOutcome;
+use crate::player::Player;
+use crate::tui::logic::state::{AppData, AppState, Prompt, PromptKind};
+use crate::tui::render::format_duration;
+
+/// Keys for the comments popup, anything it doesn't use falls through.
+pub(crate) fn handle_comments_key(
+    key: KeyEvent,
+    state: &mut AppState,
+    data: &AppData,
+    player: &Player,
+) -> Option<InputOutcome> {
+    match key.code {
+        KeyCode::Esc => state.comments_visible = false,
+        KeyCode::Up => state.comments_selected = state.comments_selected.saturating_sub(1),
+        KeyCode::Down => {
+            if state.comments_selected + 1 < data.comments.len() {
+                state.comments_selected += 1;
+            }
+        }
+        KeyCode::Char('c') => open_comment_prompt(state, player),
+        _ => return None,
+    }
+    Some(InputOutcome::Continue)
+}
+
+/// Starts a comment on the playing track, anchored to the current position.
+pub(crate) fn open_comment_prompt(state: &mut AppState, player: &Player) {
+    let track = player.current_track();
+    if track.track_urn.is_empty() {
+        return;
+    }
+    let timestamp_ms = player.elapsed();
+    state.prompt = Some(Prompt {
+        title: format!("Comment at {}", format_duration(timestamp_ms)),
+        input: String::new(),
+        kind: PromptKind::Comment {
+            track_urn: track.track_urn,
+            timestamp_ms,
+        },
+    });
+}
@@ -1,4 +1,5 @@
-use crate::tui::logic::state::{AppState, QueuedTrack};
+use crate::api::Track;
+use crate::tui::logic::state::{AppData, AppState, FollowingTracksFocus, QueuedTrack};
 
 pub(crate) fn insert_manual_queue(state: &mut AppState, queued: QueuedTrack) {
     let mut items: Vec<QueuedTrack> = state.manual_queue.drain(..).collect();
@@ -9,3 +10,82 @@ pub(crate) fn insert_manual_queue(state: &mut AppState, queued: QueuedTrack) {
     items.insert(insert_idx, queued);
     state.manual_queue = items.into_iter().collect();
 }
+
+/// The track under the cursor in whichever library/search view is focused.
+pub(crate) fn selected_track(state: &AppState, data: &AppData) -> Option<Track> {
+    let search_active = state.search_popup_visible && !state.search_query.trim().is_empty();
+    let filtered = |row: usize| {
+        if search_active {
+            state.search_matches.get(row).copied()
+        } else {
+            Some(row)
+        }
+    };
+
+    let track = if state.selected_tab == 0 {
+        match state.selected_subtab {
+            0 => filtered(state.selected_row).and_then(|idx| data.likes.get(idx)),
+            1 => filtered(state.selected_playlist_track_row)
+                .and_then(|idx| data.playlist_tracks.get(idx)),
+            2 => data.album_tracks.get(state.selected_album_track_row),
+            3 => {
+                if state.following_tracks_focus == FollowingTracksFocus::Likes {
+                    data.following_likes_tracks.get(state.selected_following_like_row)
+                } else {
+                    data.following_tracks.get(state.selected_following_track_row)
+                }
+            }
+            _ => None,
+        }
+    } else if state.selected_tab == 1 {
+        match state.selected_searchfilter {
+            0 => data.search_tracks.get(state.selected_row),
+            1 => data.search_album_tracks.get(state.search_selected_album_track_row),
+            2 => data
+                .search_playlist_tracks
+                .get(state.search_selected_playlist_track_row),
+            3 => {
+                if state.search_people_tracks_focus == FollowingTracksFocus::Likes {
+                    data.search_people_likes_tracks
+                        .get(state.search_selected_person_like_row)
+                } else {
+                    data.search_people_tracks.get(state.search_selected_person_track_row)
+                }
+            }
+            _ => None,
+        }
+    } else if state.selected_tab == 2 {
+        data.feed_tracks.get(state.selected_info_row)
+    } else {
+        None
+    };
+
+    track.cloned()
+}
+
+/// The artist under the cursor in the Following and People views.
+pub(crate) fn selected_artist_urn(state: &AppState, data: &AppData) -> Option<String> {
+    if state.selected_tab == 0 && state.selected_subtab == 3 {
+        let search_active = state.search_popup_visible && !state.search_query.trim().is_empty();
+        let selected_idx = if search_active {
+            state.search_matches.get(state.selected_row).copied()
+        } else {
+            Some(state.selected_row)
+        };
+        selected_idx
+            .and_then(|idx| data.following.get(idx))
+            .map(|a| a.urn.clone())
+    } else if state.selected_tab == 1 && state.selected_searchfilter == 3 {
+        data.search_people.get(state.selected_row).map(|a| a.urn.clone())
+    } else {
+        None
+    }
+}
+
+/// `tracks_uri` of the selected feed activity when it is a playlist or album.
+pub(crate) fn feed_playlist_uri(state: &AppState, data: &AppData) -> Option<String> {
+    data.feed
+        .get(state.selected_row)
+        .and_then(|activity| activity.playlist())
+        .map(|playlist| playlist.tracks_uri.clone())
+}
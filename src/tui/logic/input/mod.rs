@@ -1,5 +1,6 @@
 use ratatui::crossterm::event::{KeyCode, KeyEvent};
 
+use crate::auth::{SessionStatus, session_status};
 use crate::player::Player;
 
 use crate::tui::logic::state::{AppData, AppState};
@@ -12,6 +13,15 @@ mod movement;
 mod playback;
 mod queue;
 mod commands;
+mod comments;
+mod playlists;
+mod prompt;
+mod profile;
+mod accounts;
+mod session;
+mod seek;
+mod settings;
+mod speed;
 
 pub enum InputOutcome {
     Continue,
@@ -28,6 +38,43 @@ pub fn handle_key_event(
         return quit::handle_quit_confirm(key, state);
     }
 
+    let session = session_status();
+    if session != SessionStatus::Active {
+        return session::handle_session_key(key, &session, state);
+    }
+
+    if state.prompt.is_some() {
+        return prompt::handle_prompt_input(key, state, data, player);
+    }
+
+    if state.account_picker.is_some() {
+        return accounts::handle_account_picker_key(key, state);
+    }
+
+    if state.settings_menu.is_some() {
+        return settings::handle_settings_key(key, state);
+    }
+
+    if state.playlist_picker.is_some() {
+        return playlists::handle_playlist_picker_key(key, state, data);
+    }
+
+    if state.playlist_menu.is_some() {
+        return playlists::handle_playlist_menu_key(key, state, data);
+    }
+
+    if state.profile.is_some()
+        && let Some(outcome) = profile::handle_profile_key(key, state, data, player)
+    {
+        return outcome;
+    }
+
+    if state.comments_visible
+        && let Some(outcome) = comments::handle_comments_key(key, state, data, player)
+    {
+        return outcome;
+    }
+
     if state.search_popup_visible {
         if let Some(outcome) = search::handle_search_input(key, state, data) {
             return outcome;
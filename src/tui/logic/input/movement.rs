@@ -1,7 +1,8 @@
 use ratatui::crossterm::event::{KeyEvent, KeyModifiers};
 
 use super::InputOutcome;
-use crate::tui::logic::state::{AppData, AppState, info_table_rows_count, table_rows_count, FollowingTracksFocus};
+use super::playlists::move_selected_playlist_track;
+use crate::tui::logic::state::{AppData, AppState, table_rows_count, FollowingTracksFocus};
 
 pub(crate) fn handle_down_key(
     key: KeyEvent,
@@ -10,6 +11,8 @@ pub(crate) fn handle_down_key(
 ) -> InputOutcome {
     if state.selected_tab == 1 {
         handle_search_down(key, state, data);
+    } else if state.selected_tab == 2 {
+        handle_feed_down(key, state, data);
     } else if state.selected_tab == 0 && state.selected_subtab == 1 {
         handle_playlist_down(key, state, data);
     } else if state.selected_tab == 0 && state.selected_subtab == 2 {
@@ -31,6 +34,8 @@ pub(crate) fn handle_up_key(
 ) -> InputOutcome {
     if state.selected_tab == 1 {
         handle_search_up(key, state, data);
+    } else if state.selected_tab == 2 {
+        handle_feed_up(key, state, data);
     } else if state.selected_tab == 0 && state.selected_subtab == 1 {
         handle_playlist_up(key, state, data);
     } else if state.selected_tab == 0 && state.selected_subtab == 2 {
@@ -52,7 +57,9 @@ fn handle_playlist_down(key: KeyEvent, state: &mut AppState, data: &mut AppData)
     } else {
         data.playlist_tracks.len()
     };
-    if key.modifiers.contains(KeyModifiers::SHIFT) {
+    if key.modifiers.contains(KeyModifiers::CONTROL) {
+        move_selected_playlist_track(state, data, false);
+    } else if key.modifiers.contains(KeyModifiers::SHIFT) {
         if state.selected_row + 1 < data.playlists.len() {
             state.selected_row += 1;
             state.selected_playlist_row = state.selected_row;
@@ -111,12 +118,7 @@ fn handle_following_down(key: KeyEvent, state: &mut AppState, data: &mut AppData
 
 fn handle_alt_down(key: KeyEvent, state: &mut AppState, data: &mut AppData) {
     let max_rows = table_rows_count(state.selected_subtab, data);
-    let max_info_rows = info_table_rows_count();
-    if state.selected_tab == 2 && state.info_pane_selected {
-        if max_info_rows > 0 {
-            state.selected_info_row = (state.selected_info_row + 10).min(max_info_rows - 1);
-        }
-    } else if max_rows > 0 {
+    if max_rows > 0 {
         state.selected_row = (state.selected_row + 10).min(max_rows - 1);
         match state.selected_subtab {
             0 => data.likes_state.select(Some(state.selected_row)),
@@ -130,13 +132,7 @@ fn handle_alt_down(key: KeyEvent, state: &mut AppState, data: &mut AppData) {
 
 fn handle_normal_down(key: KeyEvent, state: &mut AppState, data: &mut AppData) {
     let max_rows = table_rows_count(state.selected_subtab, data);
-    let max_info_rows = info_table_rows_count();
-    if state.selected_tab == 2
-        && state.info_pane_selected
-        && state.selected_info_row + 1 < max_info_rows
-    {
-        state.selected_info_row += 1;
-    } else if state.selected_row + 1 < max_rows {
+    if state.selected_row + 1 < max_rows {
         state.selected_row += 1;
         if state.selected_subtab == 1 && state.selected_tab == 0 {
             state.selected_playlist_row = state.selected_row;
@@ -161,7 +157,9 @@ fn handle_playlist_up(key: KeyEvent, state: &mut AppState, data: &mut AppData) {
     } else {
         data.playlist_tracks.len()
     };
-    if key.modifiers.contains(KeyModifiers::SHIFT) {
+    if key.modifiers.contains(KeyModifiers::CONTROL) {
+        move_selected_playlist_track(state, data, true);
+    } else if key.modifiers.contains(KeyModifiers::SHIFT) {
         if state.selected_row > 0 {
             state.selected_row -= 1;
             state.selected_playlist_row = state.selected_row;
@@ -214,24 +212,18 @@ fn handle_following_up(key: KeyEvent, state: &mut AppState, data: &mut AppData)
 }
 
 fn handle_alt_up(key: KeyEvent, state: &mut AppState, data: &mut AppData) {
-    if state.selected_tab == 2 && state.info_pane_selected {
-        state.selected_info_row = state.selected_info_row.saturating_sub(10);
-    } else {
-        state.selected_row = state.selected_row.saturating_sub(10);
-        match state.selected_subtab {
-            0 => data.likes_state.select(Some(state.selected_row)),
-            1 => data.playlists_state.select(Some(state.selected_row)),
-            2 => data.albums_state.select(Some(state.selected_row)),
-            3 => data.following_state.select(Some(state.selected_row)),
-            _ => {}
-        }
+    state.selected_row = state.selected_row.saturating_sub(10);
+    match state.selected_subtab {
+        0 => data.likes_state.select(Some(state.selected_row)),
+        1 => data.playlists_state.select(Some(state.selected_row)),
+        2 => data.albums_state.select(Some(state.selected_row)),
+        3 => data.following_state.select(Some(state.selected_row)),
+        _ => {}
     }
 }
 
 fn handle_normal_up(key: KeyEvent, state: &mut AppState, data: &mut AppData) {
-    if state.selected_tab == 2 && state.info_pane_selected && state.selected_info_row > 0 {
-        state.selected_info_row -= 1;
-    } else if state.selected_row > 0 {
+    if state.selected_row > 0 {
         state.selected_row -= 1;
         if state.selected_subtab == 1 && state.selected_tab == 0 {
             state.selected_playlist_row = state.selected_row;
@@ -249,6 +241,32 @@ fn handle_normal_up(key: KeyEvent, state: &mut AppState, data: &mut AppData) {
     }
 }
 
+/// Up/Down move within the focused pane, Shift always moves the activity list.
+fn handle_feed_down(key: KeyEvent, state: &mut AppState, data: &mut AppData) {
+    let step = if key.modifiers.contains(KeyModifiers::ALT) { 10 } else { 1 };
+    if state.info_pane_selected && !key.modifiers.contains(KeyModifiers::SHIFT) {
+        if !data.feed_tracks.is_empty() {
+            state.selected_info_row =
+                (state.selected_info_row + step).min(data.feed_tracks.len() - 1);
+            data.feed_tracks_state.select(Some(state.selected_info_row));
+        }
+    } else if !data.feed.is_empty() {
+        state.selected_row = (state.selected_row + step).min(data.feed.len() - 1);
+        data.feed_state.select(Some(state.selected_row));
+    }
+}
+
+fn handle_feed_up(key: KeyEvent, state: &mut AppState, data: &mut AppData) {
+    let step = if key.modifiers.contains(KeyModifiers::ALT) { 10 } else { 1 };
+    if state.info_pane_selected && !key.modifiers.contains(KeyModifiers::SHIFT) {
+        state.selected_info_row = state.selected_info_row.saturating_sub(step);
+        data.feed_tracks_state.select(Some(state.selected_info_row));
+    } else {
+        state.selected_row = state.selected_row.saturating_sub(step);
+        data.feed_state.select(Some(state.selected_row));
+    }
+}
+
 fn handle_search_down(key: KeyEvent, state: &mut AppState, data: &mut AppData) {
     match state.selected_searchfilter {
         0 => {
@@ -1,10 +1,11 @@
 use ratatui::crossterm::event::{KeyEvent, KeyModifiers};
 
 use super::InputOutcome;
+use super::seek::seek_step;
 use crate::api::Track;
 use crate::player::Player;
-use crate::tui::logic::state::{AppData, AppState, FollowingTracksFocus, PlaybackSource};
-use crate::tui::logic::utils::{build_queue, build_search_matches};
+use crate::tui::logic::state::{AppData, AppState, FollowingTracksFocus};
+use crate::tui::logic::utils::{active_tracks, build_queue, build_search_matches};
 
 pub(crate) fn handle_tab_switch(state: &mut AppState) -> InputOutcome {
     state.selected_tab = (state.selected_tab + 1) % 3;
@@ -19,9 +20,8 @@ pub(crate) fn handle_right_key(
     player: &Player,
 ) -> InputOutcome {
     if key.modifiers.contains(KeyModifiers::ALT) {
-        if player.is_playing() || state.current_playing_index.is_some() {
-            player.fast_forward();
-        }
+        let coarse = key.modifiers.contains(KeyModifiers::SHIFT);
+        seek_step(state, player, true, coarse);
         return InputOutcome::Continue;
     }
 
@@ -99,9 +99,8 @@ pub(crate) fn handle_left_key(
     player: &Player,
 ) -> InputOutcome {
     if key.modifiers.contains(KeyModifiers::ALT) {
-        if player.is_playing() || state.current_playing_index.is_some() {
-            player.rewind();
-        }
+        let coarse = key.modifiers.contains(KeyModifiers::SHIFT);
+        seek_step(state, player, false, coarse);
         return InputOutcome::Continue;
     }
 
@@ -186,13 +185,7 @@ fn handle_next_track(
     player: &Player,
 ) -> InputOutcome {
     if let Some(current_idx) = state.current_playing_index {
-        let active_tracks = match state.playback_source {
-            PlaybackSource::Likes => &data.likes,
-            PlaybackSource::Playlist
-            | PlaybackSource::Album
-            | PlaybackSource::FollowingPublished
-            | PlaybackSource::FollowingLikes => &data.playback_tracks,
-        };
+        let active_tracks = active_tracks(state, data);
         if state.manual_queue.is_empty() && state.auto_queue.is_empty() {
             state.auto_queue =
                 build_queue(current_idx, active_tracks, state.shuffle_enabled);
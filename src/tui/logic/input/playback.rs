@@ -1,4 +1,5 @@
 use super::InputOutcome;
+use super::helpers::feed_playlist_uri;
 use crate::api::Track;
 use crate::tui::logic::state::{AppData, AppState, PlaybackSource, FollowingTracksFocus};
 use crate::player::Player;
@@ -19,6 +20,8 @@ pub(crate) fn handle_enter(
         handle_album_enter(state, data, player);
     } else if state.selected_tab == 0 && state.selected_subtab == 3 {
         handle_following_enter(state, data, player);
+    } else if state.selected_tab == 2 {
+        handle_feed_enter(state, data, player);
     }
     InputOutcome::Continue
 }
@@ -93,6 +96,36 @@ fn handle_search_playlist_enter(state: &mut AppState, data: &mut AppData, player
     state.auto_queue = build_queue(selected_idx, &data.playback_tracks, state.shuffle_enabled);
 }
 
+fn handle_feed_enter(state: &mut AppState, data: &mut AppData, player: &Player) {
+    let selected_idx = state.selected_info_row;
+    let track = match data.feed_tracks.get(selected_idx) {
+        Some(track) => track,
+        None => return,
+    };
+    if !track.is_playable() {
+        return;
+    }
+
+    if state.playback_source != PlaybackSource::Playlist {
+        state.playback_history.clear();
+        state.manual_queue.clear();
+    } else if let Some(queued) = queued_from_current(state, data)
+        && !(queued.source == PlaybackSource::Playlist && queued.index == selected_idx)
+    {
+        state.playback_history.push(queued);
+    }
+
+    player.play(track.clone());
+    state.playback_source = PlaybackSource::Playlist;
+    state.override_playing = None;
+    state.current_playing_index = Some(selected_idx);
+    data.playback_tracks = data.feed_tracks.clone();
+    data.playback_playlist_uri = feed_playlist_uri(state, data);
+    data.playback_album_uri = None;
+    data.playback_following_user_urn = None;
+    state.auto_queue = build_queue(selected_idx, &data.playback_tracks, state.shuffle_enabled);
+}
+
 fn handle_search_album_enter(state: &mut AppState, data: &mut AppData, player: &Player) {
     let selected_idx = state.search_selected_album_track_row;
     let track = match data.search_album_tracks.get(selected_idx) {
@@ -0,0 +1,307 @@
+use ratatui::crossterm::event::{KeyCode, KeyEvent};
+
+use super::InputOutcome;
+use super::helpers::selected_track;
+use crate::api::{Playlist, Track};
+use crate::tui::logic::state::{
+    AppData, AppState, PlaylistAction, PlaylistMenu, PlaylistMenuItem, PlaylistPicker, Prompt,
+    PromptKind,
+};
+use crate::tui::logic::utils::soundcloud_playlist_id_from_tracks_uri;
+
+pub(crate) fn open_playlist_picker(state: &mut AppState, data: &AppData) {
+    if let Some(track) = selected_track(state, data) {
+        state.playlist_picker = Some(PlaylistPicker { track, selected: 0 });
+    }
+}
+
+pub(crate) fn open_playlist_menu(state: &mut AppState, data: &AppData) {
+    if state.selected_tab != 0 || state.selected_subtab != 1 {
+        return;
+    }
+    let playlist = data
+        .playlists
+        .get(state.selected_row)
+        .cloned()
+        .unwrap_or_default();
+    state.playlist_menu = Some(PlaylistMenu {
+        playlist,
+        selected: 0,
+        confirm_delete: false,
+    });
+}
+
+pub(crate) fn handle_playlist_picker_key(
+    key: KeyEvent,
+    state: &mut AppState,
+    data: &mut AppData,
+) -> InputOutcome {
+    let Some(picker) = state.playlist_picker.as_mut() else {
+        return InputOutcome::Continue;
+    };
+    let row_count = data.owned_playlists().len() + 1;
+
+    match key.code {
+        KeyCode::Esc => state.playlist_picker = None,
+        KeyCode::Up => picker.selected = picker.selected.saturating_sub(1),
+        KeyCode::Down if picker.selected + 1 < row_count => picker.selected += 1,
+        KeyCode::Enter => {
+            let picker = state.playlist_picker.take().unwrap();
+            if picker.selected == 0 {
+                state.prompt = Some(Prompt {
+                    title: "New playlist".to_string(),
+                    input: String::new(),
+                    kind: PromptKind::NewPlaylist {
+                        track: Some(picker.track),
+                    },
+                });
+            } else if let Some(playlist) = data.owned_playlists().get(picker.selected - 1) {
+                let tracks_uri = playlist.tracks_uri.clone();
+                add_track(state, data, &tracks_uri, picker.track);
+            }
+        }
+        _ => {}
+    }
+    InputOutcome::Continue
+}
+
+pub(crate) fn handle_playlist_menu_key(
+    key: KeyEvent,
+    state: &mut AppState,
+    data: &mut AppData,
+) -> InputOutcome {
+    let Some(menu) = state.playlist_menu.as_mut() else {
+        return InputOutcome::Continue;
+    };
+    let items = menu.items();
+
+    match key.code {
+        KeyCode::Esc => state.playlist_menu = None,
+        KeyCode::Up => {
+            menu.selected = menu.selected.saturating_sub(1);
+            menu.confirm_delete = false;
+        }
+        KeyCode::Down => {
+            if menu.selected + 1 < items.len() {
+                menu.selected += 1;
+            }
+            menu.confirm_delete = false;
+        }
+        KeyCode::Enter => match items.get(menu.selected) {
+            Some(PlaylistMenuItem::Rename) => {
+                let playlist = menu.playlist.clone();
+                state.playlist_menu = None;
+                state.prompt = Some(Prompt {
+                    title: "Rename playlist".to_string(),
+                    input: playlist.title.clone(),
+                    kind: PromptKind::RenamePlaylist { playlist },
+                });
+            }
+            Some(PlaylistMenuItem::TogglePrivacy) => {
+                let playlist = menu.playlist.clone();
+                state.playlist_menu = None;
+                toggle_privacy(state, data, &playlist);
+            }
+            Some(PlaylistMenuItem::Delete) => {
+                if menu.confirm_delete {
+                    let playlist = menu.playlist.clone();
+                    state.playlist_menu = None;
+                    delete_playlist(state, data, &playlist);
+                } else {
+                    menu.confirm_delete = true;
+                }
+            }
+            Some(PlaylistMenuItem::New) => {
+                state.playlist_menu = None;
+                state.prompt = Some(Prompt {
+                    title: "New playlist".to_string(),
+                    input: String::new(),
+                    kind: PromptKind::NewPlaylist { track: None },
+                });
+            }
+            None => {}
+        },
+        _ => {}
+    }
+    InputOutcome::Continue
+}
+
+pub(crate) fn submit_new_playlist(state: &mut AppState, title: String, track: Option<Track>) {
+    state
+        .playlist_queue
+        .push_back(PlaylistAction::Create { title, track });
+}
+
+pub(crate) fn submit_rename(
+    state: &mut AppState,
+    data: &mut AppData,
+    playlist: &Playlist,
+    title: String,
+) {
+    let Some(playlist_id) = soundcloud_playlist_id_from_tracks_uri(&playlist.tracks_uri) else {
+        return;
+    };
+    let Some(p) = owned_playlist_mut(data, &playlist.tracks_uri) else {
+        return;
+    };
+    let previous_title = std::mem::replace(&mut p.title, title.clone());
+    state.playlist_queue.push_back(PlaylistAction::Rename {
+        tracks_uri: playlist.tracks_uri.clone(),
+        playlist_id,
+        title,
+        previous_title,
+    });
+}
+
+/// Shift+X on the Playlists tab: drops the selected track from an owned playlist.
+pub(crate) fn remove_selected_playlist_track(state: &mut AppState, data: &mut AppData) {
+    let Some((tracks_uri, playlist_id)) = editable_playlist(state, data) else {
+        return;
+    };
+    let filter_active = state.search_popup_visible && !state.search_query.trim().is_empty();
+    if filter_active {
+        return;
+    }
+    let idx = state.selected_playlist_track_row;
+    if idx >= data.playlist_tracks.len() {
+        return;
+    }
+
+    let removed = data.playlist_tracks.remove(idx);
+    if let Some(p) = owned_playlist_mut(data, &tracks_uri) {
+        p.track_count = p.track_count.saturating_sub(1);
+        p.duration_ms = p.duration_ms.saturating_sub(removed.duration_ms);
+    }
+    if state.selected_playlist_track_row >= data.playlist_tracks.len() {
+        state.selected_playlist_track_row = data.playlist_tracks.len().saturating_sub(1);
+    }
+    data.playlist_tracks_state
+        .select(Some(state.selected_playlist_track_row));
+
+    state.playlist_queue.push_back(PlaylistAction::RemoveTrack {
+        tracks_uri,
+        playlist_id,
+        track: removed,
+    });
+}
+
+/// Ctrl+Up/Down on the Playlists tab: moves the selected track within an owned playlist.
+pub(crate) fn move_selected_playlist_track(state: &mut AppState, data: &mut AppData, up: bool) {
+    let Some((tracks_uri, playlist_id)) = editable_playlist(state, data) else {
+        return;
+    };
+    let filter_active = state.search_popup_visible && !state.search_query.trim().is_empty();
+    if filter_active {
+        return;
+    }
+    let idx = state.selected_playlist_track_row;
+    let target = if up {
+        match idx.checked_sub(1) {
+            Some(t) => t,
+            None => return,
+        }
+    } else {
+        idx + 1
+    };
+    if target >= data.playlist_tracks.len() {
+        return;
+    }
+
+    data.playlist_tracks.swap(idx, target);
+    state.selected_playlist_track_row = target;
+    data.playlist_tracks_state.select(Some(target));
+
+    state.playlist_queue.push_back(PlaylistAction::MoveTrack {
+        tracks_uri,
+        playlist_id,
+        track_urn: data.playlist_tracks[target].track_urn.clone(),
+        up,
+    });
+}
+
+fn add_track(state: &mut AppState, data: &mut AppData, tracks_uri: &str, track: Track) {
+    let Some(playlist_id) = soundcloud_playlist_id_from_tracks_uri(tracks_uri) else {
+        return;
+    };
+    let tracks_loaded = data.playlist_tracks_uri.as_deref() == Some(tracks_uri);
+    if tracks_loaded {
+        if data
+            .playlist_tracks
+            .iter()
+            .any(|t| t.track_urn == track.track_urn)
+        {
+            return;
+        }
+        data.playlist_tracks.push(track.clone());
+    }
+    if let Some(p) = owned_playlist_mut(data, tracks_uri) {
+        p.track_count += 1;
+        p.duration_ms += track.duration_ms;
+    }
+    state.playlist_queue.push_back(PlaylistAction::AddTrack {
+        tracks_uri: tracks_uri.to_string(),
+        playlist_id,
+        track,
+    });
+}
+
+fn toggle_privacy(state: &mut AppState, data: &mut AppData, playlist: &Playlist) {
+    let Some(playlist_id) = soundcloud_playlist_id_from_tracks_uri(&playlist.tracks_uri) else {
+        return;
+    };
+    let sharing = if playlist.sharing == "private" {
+        "public"
+    } else {
+        "private"
+    };
+    let Some(p) = owned_playlist_mut(data, &playlist.tracks_uri) else {
+        return;
+    };
+    let previous_sharing = std::mem::replace(&mut p.sharing, sharing.to_string());
+    state.playlist_queue.push_back(PlaylistAction::SetSharing {
+        tracks_uri: playlist.tracks_uri.clone(),
+        playlist_id,
+        sharing: sharing.to_string(),
+        previous_sharing,
+    });
+}
+
+fn delete_playlist(state: &mut AppState, data: &mut AppData, playlist: &Playlist) {
+    let Some(playlist_id) = soundcloud_playlist_id_from_tracks_uri(&playlist.tracks_uri) else {
+        return;
+    };
+    data.playlists
+        .retain(|p| !(p.is_owned && p.tracks_uri == playlist.tracks_uri));
+    if data.playlist_tracks_uri.as_deref() == Some(playlist.tracks_uri.as_str()) {
+        data.playlist_tracks.clear();
+        data.playlist_tracks_uri = None;
+    }
+    if state.selected_row >= data.playlists.len() {
+        state.selected_row = data.playlists.len().saturating_sub(1);
+    }
+    data.playlists_state.select(Some(state.selected_row));
+
+    state.playlist_queue.push_back(PlaylistAction::Delete {
+        playlist_id,
+        playlist: Box::new(playlist.clone()),
+    });
+}
+
+/// The owned playlist currently open on the Playlists tab, once its tracks are loaded.
+fn editable_playlist(state: &AppState, data: &AppData) -> Option<(String, u64)> {
+    if state.selected_tab != 0 || state.selected_subtab != 1 {
+        return None;
+    }
+    let playlist = data.playlists.get(state.selected_row)?;
+    if !playlist.is_owned || data.playlist_tracks_uri.as_deref() != Some(&playlist.tracks_uri) {
+        return None;
+    }
+    let playlist_id = soundcloud_playlist_id_from_tracks_uri(&playlist.tracks_uri)?;
+    Some((playlist.tracks_uri.clone(), playlist_id))
+}
+
+fn owned_playlist_mut<'a>(data: &'a mut AppData, tracks_uri: &str) -> Option<&'a mut Playlist> {
+    data.playlists
+        .iter_mut()
+        .find(|p| p.is_owned && p.tracks_uri == tracks_uri)
+}
@@ -0,0 +1,122 @@
+use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+use super::InputOutcome;
+use super::helpers::{insert_manual_queue, selected_artist_urn, selected_track};
+use crate::player::Player;
+use crate::tui::logic::state::{
+    AppData, AppState, EngagementAction, PROFILE_TABS, PlaybackSource, ProfileView, QueuedTrack,
+};
+use crate::tui::logic::utils::{play_track_list, soundcloud_id_from_urn};
+
+/// Keys for the profile overlay, anything it doesn't use falls through.
+pub(crate) fn handle_profile_key(
+    key: KeyEvent,
+    state: &mut AppState,
+    data: &mut AppData,
+    player: &Player,
+) -> Option<InputOutcome> {
+    let shift = key.modifiers.contains(KeyModifiers::SHIFT);
+    let view = state.profile.as_mut()?;
+    let rows = data
+        .profile
+        .as_ref()
+        .map_or(0, |profile| profile.rows_count(view.selected_tab));
+
+    match key.code {
+        KeyCode::Esc => state.profile = None,
+        KeyCode::Char('i' | 'I') if shift => state.profile = None,
+        KeyCode::Left if key.modifiers.is_empty() => {
+            view.selected_tab = (view.selected_tab + PROFILE_TABS.len() - 1) % PROFILE_TABS.len();
+            view.selected_row = 0;
+        }
+        KeyCode::Right if key.modifiers.is_empty() => {
+            view.selected_tab = (view.selected_tab + 1) % PROFILE_TABS.len();
+            view.selected_row = 0;
+        }
+        KeyCode::Up => view.selected_row = view.selected_row.saturating_sub(1),
+        KeyCode::Down => {
+            if view.selected_row + 1 < rows {
+                view.selected_row += 1;
+            }
+        }
+        KeyCode::Enter => play_profile_selected(state, data, player),
+        KeyCode::Char('a' | 'A') if shift => queue_profile_repost(state, data),
+        KeyCode::Char('l' | 'L') if shift => toggle_follow_profile(state, data),
+        _ => return None,
+    }
+    Some(InputOutcome::Continue)
+}
+
+/// Opens the profile of the selected artist, or of the selected track's uploader.
+pub(crate) fn open_profile_selected(state: &mut AppState, data: &AppData) {
+    let artist_urn = selected_artist_urn(state, data)
+        .or_else(|| selected_track(state, data).map(|t| t.user.urn));
+
+    if let Some(user_urn) = artist_urn.filter(|urn| !urn.is_empty()) {
+        state.profile = Some(ProfileView {
+            user_urn,
+            selected_tab: 0,
+            selected_row: 0,
+            error: None,
+        });
+    }
+}
+
+fn play_profile_selected(state: &mut AppState, data: &mut AppData, player: &Player) {
+    let (Some(view), Some(profile)) = (state.profile.as_ref(), data.profile.as_ref()) else {
+        return;
+    };
+    let row = view.selected_row;
+    match view.selected_tab {
+        0 => state.profile_play = profile.playlists.get(row).map(|p| p.tracks_uri.clone()),
+        1 => state.profile_play = profile.albums.get(row).map(|a| a.tracks_uri.clone()),
+        2 => {
+            let tracks = profile.reposts.clone();
+            play_track_list(tracks, row, PlaybackSource::Playlist, None, state, data, player);
+        }
+        _ => {}
+    }
+}
+
+fn queue_profile_repost(state: &mut AppState, data: &AppData) {
+    let (Some(view), Some(profile)) = (state.profile.as_ref(), data.profile.as_ref()) else {
+        return;
+    };
+    if view.selected_tab != 2 {
+        return;
+    }
+    let index = view.selected_row;
+    if let Some(track) = profile.reposts.get(index).filter(|t| t.is_playable()) {
+        let queued = QueuedTrack {
+            source: PlaybackSource::Playlist,
+            index,
+            track: track.clone(),
+            tracks_snapshot: Some(profile.reposts.clone()),
+            playlist_uri: None,
+            album_uri: None,
+            following_user_urn: None,
+            user_added: true,
+        };
+        insert_manual_queue(state, queued);
+    }
+}
+
+fn toggle_follow_profile(state: &mut AppState, data: &mut AppData) {
+    let Some(artist) = data.profile.as_ref().map(|p| p.artist.clone()) else {
+        return;
+    };
+    let Some(user_id) = soundcloud_id_from_urn(&artist.urn) else {
+        return;
+    };
+    if data.followed_user_urns.remove(&artist.urn) {
+        state.engagement_queue.push_back(EngagementAction::UnfollowUser {
+            urn: artist.urn,
+            user_id,
+        });
+    } else {
+        data.followed_user_urns.insert(artist.urn.clone());
+        state
+            .engagement_queue
+            .push_back(EngagementAction::FollowUser { artist, user_id });
+    }
+}
@@ -0,0 +1,52 @@
+use ratatui::crossterm::event::{KeyCode, KeyEvent};
+
+use super::InputOutcome;
+use super::accounts::submit_new_account;
+use super::playlists::{submit_new_playlist, submit_rename};
+use super::seek::submit_seek;
+use crate::player::Player;
+use crate::tui::logic::state::{AppData, AppState, PendingComment, PromptKind};
+
+pub(crate) fn handle_prompt_input(
+    key: KeyEvent,
+    state: &mut AppState,
+    data: &mut AppData,
+    player: &Player,
+) -> InputOutcome {
+    let Some(prompt) = state.prompt.as_mut() else {
+        return InputOutcome::Continue;
+    };
+
+    match key.code {
+        KeyCode::Esc => state.prompt = None,
+        KeyCode::Backspace => {
+            prompt.input.pop();
+        }
+        KeyCode::Char(c) => prompt.input.push(c),
+        KeyCode::Enter => {
+            let prompt = state.prompt.take().unwrap();
+            let input = prompt.input.trim().to_string();
+            if input.is_empty() {
+                return InputOutcome::Continue;
+            }
+            match prompt.kind {
+                PromptKind::NewPlaylist { track } => submit_new_playlist(state, input, track),
+                PromptKind::RenamePlaylist { playlist } => {
+                    submit_rename(state, data, &playlist, input)
+                }
+                PromptKind::Comment {
+                    track_urn,
+                    timestamp_ms,
+                } => state.comment_queue.push_back(PendingComment {
+                    track_urn,
+                    body: input,
+                    timestamp_ms,
+                }),
+                PromptKind::NewAccount => submit_new_account(state, input),
+                PromptKind::Seek => submit_seek(state, player, prompt.title, input),
+            }
+        }
+        _ => {}
+    }
+    InputOutcome::Continue
+}
@@ -1,4 +1,4 @@
-use super::helpers::insert_manual_queue;
+use super::helpers::{feed_playlist_uri, insert_manual_queue};
 use crate::api::Track;
 use crate::tui::logic::state::{AppData, AppState, PlaybackSource, QueuedTrack, FollowingTracksFocus};
 
@@ -22,6 +22,10 @@ pub(crate) fn handle_add_to_queue(
             3 => add_search_people_to_queue(state, data),
             _ => {}
         }
+    } else if state.selected_tab == 2
+        && let Some(queued) = get_feed_queued(state, data)
+    {
+        insert_manual_queue(state, queued);
     }
 }
 
@@ -46,6 +50,8 @@ pub(crate) fn handle_add_next_to_queue(
             3 => queued = get_search_people_queued(state, data),
             _ => {}
         }
+    } else if state.selected_tab == 2 {
+        queued = get_feed_queued(state, data);
     }
 
     if let Some(queued) = queued {
@@ -180,6 +186,24 @@ fn get_search_tracks_queued(state: &mut AppState, data: &mut AppData) -> Option<
     })
 }
 
+fn get_feed_queued(state: &mut AppState, data: &mut AppData) -> Option<QueuedTrack> {
+    let idx = state.selected_info_row;
+    let track = data.feed_tracks.get(idx)?;
+    if !track.is_playable() {
+        return None;
+    }
+    Some(QueuedTrack {
+        source: PlaybackSource::Playlist,
+        index: idx,
+        track: track.clone(),
+        tracks_snapshot: Some(data.feed_tracks.clone()),
+        playlist_uri: feed_playlist_uri(state, data),
+        album_uri: None,
+        following_user_urn: None,
+        user_added: true,
+    })
+}
+
 fn get_search_playlist_queued(state: &mut AppState, data: &mut AppData) -> Option<QueuedTrack> {
     let idx = state.search_selected_playlist_track_row;
     let track = data.search_playlist_tracks.get(idx)?;
@@ -0,0 +1,168 @@
+use super::InputOutcome;
+use crate::player::{Player, SeekTarget};
+use crate::tui::logic::state::{AppState, Prompt, PromptKind};
+use crate::tui::render::format_duration;
+
+fn can_seek(state: &AppState, player: &Player) -> bool {
+    player.is_playing() || state.current_playing_index.is_some()
+}
+
+/// Alt + Left/Right, with Shift for the coarse step.
+pub(crate) fn seek_step(state: &AppState, player: &Player, forward: bool, coarse: bool) {
+    if !can_seek(state, player) {
+        return;
+    }
+    let step = if coarse {
+        state.seek_steps.coarse
+    } else {
+        state.seek_steps.fine
+    };
+    let ms = step.as_millis() as i64;
+    player.seek(SeekTarget::By(if forward { ms } else { -ms }));
+}
+
+/// `0`-`9` jump to that tenth of the track, `:` asks for a position.
+pub(crate) fn handle_seek_char(c: char, state: &mut AppState, player: &Player) -> InputOutcome {
+    if !can_seek(state, player) {
+        return InputOutcome::Continue;
+    }
+    if let Some(digit) = c.to_digit(10) {
+        player.seek(SeekTarget::Percent(digit as u8 * 10));
+    } else if c == ':' {
+        let track = player.current_track();
+        state.prompt = Some(Prompt {
+            title: format!(
+                "Seek to (now {} of {})",
+                format_duration(player.elapsed()),
+                format_duration(track.duration_ms)
+            ),
+            input: String::new(),
+            kind: PromptKind::Seek,
+        });
+    }
+    InputOutcome::Continue
+}
+
+/// Called with what was typed into the seek prompt, keeps it open if it doesn't parse.
+pub(crate) fn submit_seek(state: &mut AppState, player: &Player, title: String, input: String) {
+    match parse_seek(&input) {
+        Some(target) => player.seek(target),
+        None => {
+            state.prompt = Some(Prompt {
+                title,
+                input,
+                kind: PromptKind::Seek,
+            })
+        }
+    }
+}
+
+/// `1:23:45`, `23:45` or `45` from the start, `+30`/`-1:00` from the current
+/// position, or `50%`. A leading `seek` is allowed, as in `:seek 1:23:45`.
+fn parse_seek(input: &str) -> Option<SeekTarget> {
+    let input = input.trim();
+    let input = input.strip_prefix("seek").unwrap_or(input).trim();
+
+    if let Some(percent) = input.strip_suffix('%') {
+        let percent: u8 = percent.trim().parse().ok()?;
+        return (percent <= 100).then_some(SeekTarget::Percent(percent));
+    }
+    if let Some(rest) = input.strip_prefix('+') {
+        return Some(SeekTarget::By(parse_timestamp(rest)? as i64));
+    }
+    if let Some(rest) = input.strip_prefix('-') {
+        return Some(SeekTarget::By(-(parse_timestamp(rest)? as i64)));
+    }
+    parse_timestamp(input).map(SeekTarget::To)
+}
+
+/// `h:mm:ss`, `m:ss` or plain seconds, in ms.
+fn parse_timestamp(input: &str) -> Option<u64> {
+    let parts: Vec<&str> = input.trim().split(':').collect();
+    if parts.len() > 3 {
+        return None;
+    }
+    let mut secs: u64 = 0;
+    for (i, part) in parts.iter().enumerate() {
+        let value: u64 = part.parse().ok()?;
+        // only the leading field may go past 59
+        if i > 0 && value >= 60 {
+            return None;
+        }
+        secs = secs.checked_mul(60)?.checked_add(value)?;
+    }
+    secs.checked_mul(1000)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn timestamps() {
+        assert_eq!(parse_timestamp("45"), Some(45_000));
+        assert_eq!(parse_timestamp("0"), Some(0));
+        assert_eq!(parse_timestamp("23:45"), Some((23 * 60 + 45) * 1000));
+        assert_eq!(parse_timestamp("1:23:45"), Some((3600 + 23 * 60 + 45) * 1000));
+        assert_eq!(parse_timestamp(" 2:05 "), Some(125_000));
+        // the leading field isn't limited
+        assert_eq!(parse_timestamp("90"), Some(90_000));
+        assert_eq!(parse_timestamp("75:00"), Some(75 * 60 * 1000));
+    }
+
+    #[test]
+    fn invalid_timestamps() {
+        assert_eq!(parse_timestamp(""), None);
+        assert_eq!(parse_timestamp("abc"), None);
+        assert_eq!(parse_timestamp("1:"), None);
+        assert_eq!(parse_timestamp(":30"), None);
+        assert_eq!(parse_timestamp("1:2:3:4"), None);
+        assert_eq!(parse_timestamp("1.5"), None);
+        assert_eq!(parse_timestamp("-5"), None);
+        assert_eq!(parse_timestamp("99999999999999999999"), None);
+    }
+
+    #[test]
+    fn fields_after_the_first_stop_at_59() {
+        assert_eq!(parse_timestamp("1:59"), Some(119_000));
+        assert_eq!(parse_timestamp("1:60"), None);
+        assert_eq!(parse_timestamp("1:59:59"), Some((3600 + 59 * 60 + 59) * 1000));
+        assert_eq!(parse_timestamp("1:60:00"), None);
+        assert_eq!(parse_timestamp("1:00:60"), None);
+    }
+
+    #[test]
+    fn absolute_seeks() {
+        assert_eq!(parse_seek("1:30"), Some(SeekTarget::To(90_000)));
+        assert_eq!(parse_seek("seek 1:23:45"), Some(SeekTarget::To(5_025_000)));
+        assert_eq!(parse_seek("  seek   10 "), Some(SeekTarget::To(10_000)));
+    }
+
+    #[test]
+    fn relative_seeks() {
+        assert_eq!(parse_seek("+30"), Some(SeekTarget::By(30_000)));
+        assert_eq!(parse_seek("-1:00"), Some(SeekTarget::By(-60_000)));
+        assert_eq!(parse_seek("seek +1:00:00"), Some(SeekTarget::By(3_600_000)));
+        assert_eq!(parse_seek("+"), None);
+        assert_eq!(parse_seek("+-5"), None);
+        assert_eq!(parse_seek("-0:60"), None);
+    }
+
+    #[test]
+    fn percent_seeks() {
+        assert_eq!(parse_seek("50%"), Some(SeekTarget::Percent(50)));
+        assert_eq!(parse_seek("0%"), Some(SeekTarget::Percent(0)));
+        assert_eq!(parse_seek("100 %"), Some(SeekTarget::Percent(100)));
+        assert_eq!(parse_seek("101%"), None);
+        assert_eq!(parse_seek("-5%"), None);
+        assert_eq!(parse_seek("%"), None);
+        assert_eq!(parse_seek("1:00%"), None);
+    }
+
+    #[test]
+    fn nothing_to_seek_to() {
+        assert_eq!(parse_seek(""), None);
+        assert_eq!(parse_seek("seek"), None);
+        assert_eq!(parse_seek("later"), None);
+    }
+}
@@ -0,0 +1,29 @@
+use ratatui::crossterm::event::{KeyCode, KeyEvent};
+
+use super::InputOutcome;
+use crate::auth::SessionStatus;
+use crate::tui::logic::state::AppState;
+
+/// Only logging in again or quitting work while the session is expired.
+pub(crate) fn handle_session_key(
+    key: KeyEvent,
+    session: &SessionStatus,
+    state: &mut AppState,
+) -> InputOutcome {
+    match key.code {
+        KeyCode::Enter
+            if matches!(
+                session,
+                SessionStatus::Expired | SessionStatus::LoginFailed(_)
+            ) =>
+        {
+            state.login_requested = true;
+        }
+        KeyCode::Esc => {
+            state.quit_confirm_visible = true;
+            state.quit_confirm_selected = 1;
+        }
+        _ => {}
+    }
+    InputOutcome::Continue
+}
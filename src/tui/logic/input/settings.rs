@@ -0,0 +1,108 @@
+use ratatui::crossterm::event::{KeyCode, KeyEvent};
+
+use super::InputOutcome;
+use crate::config::{
+    EQ_BAND_COUNT, EQ_PRESETS, MAX_CROSSFADE_SECS, MAX_EQ_DB, MAX_TARGET_LUFS, MIN_TARGET_LUFS,
+    STREAM_QUALITIES, Settings, eq_preset_index,
+};
+use crate::tui::logic::state::{AppState, SETTINGS_ITEMS, SettingsItem, SettingsMenu};
+
+pub(crate) fn toggle_settings(state: &mut AppState) {
+    state.settings_menu = match state.settings_menu {
+        Some(_) => None,
+        None => Some(SettingsMenu {
+            selected: 0,
+            equalizer: None,
+        }),
+    };
+}
+
+pub(crate) fn handle_settings_key(key: KeyEvent, state: &mut AppState) -> InputOutcome {
+    let Some(menu) = state.settings_menu.as_mut() else {
+        return InputOutcome::Continue;
+    };
+    if let Some(row) = menu.equalizer.as_mut() {
+        match key.code {
+            KeyCode::Esc | KeyCode::Enter => menu.equalizer = None,
+            KeyCode::Up => *row = row.saturating_sub(1),
+            KeyCode::Down if *row < EQ_BAND_COUNT => *row += 1,
+            KeyCode::Left => change_equalizer(state, -1),
+            KeyCode::Right => change_equalizer(state, 1),
+            _ => {}
+        }
+        return InputOutcome::Continue;
+    }
+
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('b') | KeyCode::Char('B') => state.settings_menu = None,
+        KeyCode::Up => menu.selected = menu.selected.saturating_sub(1),
+        KeyCode::Down if menu.selected + 1 < SETTINGS_ITEMS.len() => menu.selected += 1,
+        KeyCode::Enter if SETTINGS_ITEMS[menu.selected] == SettingsItem::Equalizer => {
+            menu.equalizer = Some(0)
+        }
+        KeyCode::Left => change_setting(state, -1),
+        KeyCode::Right => change_setting(state, 1),
+        _ => {}
+    }
+    InputOutcome::Continue
+}
+
+/// Steps the selected setting by `step` and saves right away.
+fn change_setting(state: &mut AppState, step: i8) {
+    let Some(menu) = state.settings_menu.as_ref() else {
+        return;
+    };
+    let settings = &mut state.settings;
+    match SETTINGS_ITEMS[menu.selected] {
+        SettingsItem::Crossfade => {
+            settings.crossfade_secs = settings
+                .crossfade_secs
+                .saturating_add_signed(step)
+                .min(MAX_CROSSFADE_SECS);
+        }
+        SettingsItem::Normalize => settings.normalize = !settings.normalize,
+        SettingsItem::TargetLoudness => {
+            settings.target_lufs = settings
+                .target_lufs
+                .saturating_add(step)
+                .clamp(MIN_TARGET_LUFS, MAX_TARGET_LUFS);
+        }
+        SettingsItem::Equalizer => cycle_preset(settings, step),
+        SettingsItem::PreservePitch => settings.preserve_pitch = !settings.preserve_pitch,
+        SettingsItem::StreamQuality => {
+            let current = settings.stream_quality();
+            let i = STREAM_QUALITIES.iter().position(|&q| q == current).unwrap_or(0);
+            let next = (i as isize + step as isize).rem_euclid(STREAM_QUALITIES.len() as isize);
+            settings.stream_quality = Some(STREAM_QUALITIES[next as usize]);
+        }
+    }
+    let _ = settings.save();
+}
+
+/// Steps the preset or the selected band of the equalizer page.
+fn change_equalizer(state: &mut AppState, step: i8) {
+    let Some(row) = state.settings_menu.as_ref().and_then(|menu| menu.equalizer) else {
+        return;
+    };
+    let settings = &mut state.settings;
+    match row {
+        0 => cycle_preset(settings, step),
+        band => {
+            let gain = &mut settings.eq_gains_db[band - 1];
+            *gain = gain.saturating_add(step).clamp(-MAX_EQ_DB, MAX_EQ_DB);
+        }
+    }
+    let _ = settings.save();
+}
+
+/// A custom curve steps to the first or last preset.
+fn cycle_preset(settings: &mut Settings, step: i8) {
+    let len = EQ_PRESETS.len();
+    let next = match (eq_preset_index(&settings.eq_gains_db), step > 0) {
+        (Some(i), true) => (i + 1) % len,
+        (Some(i), false) => (i + len - 1) % len,
+        (None, true) => 0,
+        (None, false) => len - 1,
+    };
+    settings.eq_gains_db = EQ_PRESETS[next].gains_db;
+}
@@ -0,0 +1,16 @@
+use super::InputOutcome;
+use crate::player::Player;
+
+const SPEED_STEP: f32 = 0.1;
+
+/// `[` and `]` slow down and speed up, `\` goes back to normal speed.
+pub(crate) fn handle_speed_char(c: char, player: &Player) -> InputOutcome {
+    let speed = match c {
+        '[' => player.speed() - SPEED_STEP,
+        ']' => player.speed() + SPEED_STEP,
+        _ => 1.0,
+    };
+    // keep repeated steps from drifting off the tenths
+    player.set_speed((speed * 10.0).round() / 10.0);
+    InputOutcome::Continue
+}
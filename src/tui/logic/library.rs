@@ -0,0 +1,48 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+
+use crate::api::{API, Album, Artist, Playlist, Track};
+use crate::config::{library_cache_path, write_private};
+
+/// First page of each library view, as loaded at startup and kept on disk so
+/// the next launch has something to show before the network answers.
+#[derive(Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Library {
+    pub likes: Vec<Track>,
+    pub playlists: Vec<Playlist>,
+    pub albums: Vec<Album>,
+    pub following: Vec<Artist>,
+}
+
+impl Library {
+    /// Loads every view from its first page, all at once.
+    pub async fn fetch(api: &API) -> anyhow::Result<Self> {
+        api.rewind();
+        let (likes, playlists, albums, following) = futures::try_join!(
+            api.get_liked_tracks(),
+            api.get_playlists(),
+            api.get_albums(),
+            api.get_following(),
+        )?;
+        Ok(Self {
+            likes,
+            playlists,
+            albums,
+            following,
+        })
+    }
+
+    /// The snapshot saved for `profile`, if there is a readable one.
+    pub fn load_cached(profile: &str) -> Option<Self> {
+        let data = fs::read_to_string(library_cache_path(profile)?).ok()?;
+        serde_json::from_str(&data).ok()
+    }
+
+    pub fn save(&self, profile: &str) -> anyhow::Result<()> {
+        let Some(path) = library_cache_path(profile) else {
+            return Ok(());
+        };
+        write_private(&path, &serde_json::to_string(self)?)
+    }
+}
@@ -1,23 +1,36 @@
 mod filtering;
 mod input;
 mod animation;
+pub(crate) mod library;
 pub(crate) mod state;
 mod utils;
 
 use crate::api::{
-    API, fetch_album_tracks, fetch_following_liked_tracks, fetch_following_tracks,
-    fetch_playlist_tracks, fetch_search_albums, fetch_search_people, fetch_search_playlists,
-    fetch_search_tracks, follow_user, like_playlist, like_track, unfollow_user, unlike_playlist,
-    unlike_track,
+    API, ApiError, TrackEdit, create_playlist, delete_playlist, edit_playlist_tracks,
+    fetch_album_tracks,
+    fetch_following_liked_tracks, fetch_following_tracks, fetch_playlist_tracks,
+    fetch_search_albums, fetch_search_people, fetch_search_playlists, fetch_search_tracks,
+    Activity, ActivityOrigin, Comment, Track, fetch_artist_radio, fetch_my_reposts, fetch_related_tracks,
+    fetch_track_comments, fetch_user, fetch_user_playlists, fetch_user_reposts, follow_user,
+    like_playlist, like_track, repost_playlist, repost_track,
+    unfollow_user, unlike_playlist, unlike_track, unrepost_playlist,
+    unrepost_track, post_comment, update_playlist,
 };
-use crate::player::Player;
+use crate::auth::{
+    Token, authenticate, mark_logged_in, mark_logging_in, mark_login_failed, read_token,
+    refresh_token, retry_after_login, session_status,
+};
+use crate::config::{ConfigFile, Settings, active_profile, set_active_profile};
+use crate::player::{Player, PlayerEvent};
 use ratatui::{
     DefaultTerminal,
     crossterm::event::{self, Event},
 };
 
+use std::collections::HashMap;
 use std::result::Result::Ok;
 use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
 
@@ -33,18 +46,33 @@ use super::render::render;
 use self::filtering::{build_filtered_views, clamp_selection, is_filter_active};
 use self::input::{handle_key_event, InputOutcome};
 use self::animation::{SinSignal, on_tick};
-use self::state::{AppData, AppState, EngagementAction, EngagementDone, FollowingTracksFocus, PlaybackSource};
-use self::utils::{build_queue, play_queued_track, queued_from_current};
+use self::library::Library;
+use self::state::{
+    AccountPicker, AppData, AppState, EngagementAction, EngagementDone, FollowingTracksFocus, PlaybackSource,
+    PlaylistAction, PlaylistDone, RadioRequest, SeekSteps, UserProfile,
+};
+use self::utils::{
+    advance_after_track_end, dedupe_radio_tracks, due_comment, play_track_list, preload_next_track,
+    start_radio, sync_preload,
+};
 
 const TAB_TITLES: [&str; 3] = ["Library", "Search", "Feed"];
 const SUBTAB_TITLES: [&str; 4] = ["Likes", "Playlists", "Albums", "Following"];
 const SEARCHFILTERS: [&str; 4] = ["Tracks", "Albums", "Playlists", "People"];
+const RECENTLY_PLAYED_LIMIT: usize = 100;
+/// Fetch the next feed page once the selection is this close to the end.
+const FEED_PREFETCH_ROWS: usize = 10;
+/// How long to wait before loading the library again after it failed.
+const LIBRARY_RETRY: Duration = Duration::from_secs(15);
+/// How long a failed background load stays in the tab bar.
+const API_ERROR_NOTICE: Duration = Duration::from_secs(8);
 
 enum AppEvent {
     Redraw(Result<ResizeResponse, Errors>),
+    AvatarRedraw(Result<ResizeResponse, Errors>),
 }
 
-pub fn run(api: &mut Arc<Mutex<API>>, player: Player) -> anyhow::Result<()> {
+pub fn run(api: API, player: Player) -> anyhow::Result<()> {
     color_eyre::install().map_err(|e| anyhow::anyhow!(e))?;
     let terminal = ratatui::init();
     let result = start(terminal, api, player);
@@ -52,38 +80,267 @@ pub fn run(api: &mut Arc<Mutex<API>>, player: Player) -> anyhow::Result<()> {
     result
 }
 
-fn spawn_fetch<T, F>(api: Arc<Mutex<API>>, tx: std::sync::mpsc::Sender<T>, fetch_fn: F)
-where
+/// Loads the next page of one view on the runtime, unless `task` is still on
+/// the previous one. A page rejected for its token waits for the user to log in
+/// again and is retried, the cursor only moves on success so it picks up the
+/// same page. Other failures go to `tx_errors`.
+fn spawn_page<T, F, Fut>(
+    task: &mut Option<tokio::task::JoinHandle<()>>,
+    async_rt: &tokio::runtime::Runtime,
+    api: &API,
+    tx: Sender<T>,
+    tx_errors: Sender<ApiError>,
+    load: F,
+) where
     T: Send + 'static,
-    F: FnOnce(&mut API) -> anyhow::Result<T> + Send + 'static,
+    F: Fn(API) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = anyhow::Result<T>> + Send,
 {
-    std::thread::spawn(move || {
-        let result = {
-            let mut api_guard = api.lock().unwrap();
-            fetch_fn(&mut api_guard)
-        };
+    if task.as_ref().is_some_and(|t| !t.is_finished()) {
+        return;
+    }
+    let api = api.clone();
+    *task = Some(async_rt.spawn(async move {
+        match retry_after_login(|| load(api.clone())).await {
+            Ok(page) => {
+                let _ = tx.send(page);
+            }
+            Err(e) => {
+                if let Some(error) = ApiError::of(&e) {
+                    let _ = tx_errors.send(error.clone());
+                }
+            }
+        }
+    }));
+}
+
+/// Loads the library's first pages on the runtime to replace the cached
+/// snapshot, and saves them as the next launch's snapshot.
+fn spawn_library_load(
+    state: &mut AppState,
+    async_rt: &tokio::runtime::Runtime,
+    api: &API,
+    tx_library: &Sender<anyhow::Result<Library>>,
+) {
+    let api = api.clone();
+    let tx = tx_library.clone();
+    let profile = active_profile();
+    state.library_task = Some(async_rt.spawn(async move {
+        let result = retry_after_login(|| {
+            let api = api.clone();
+            async move { Library::fetch(&api).await }
+        })
+        .await;
+        if let Ok(library) = &result {
+            let _ = library.save(&profile);
+        }
+        let _ = tx.send(result);
+    }));
+}
 
-        if let Ok(item) = result {
-            let _ = tx.send(item);
+/// Everything the library load leaves for later: the next playlists page and what
+/// the account has reposted.
+fn spawn_account_fetches(
+    state: &mut AppState,
+    api: &API,
+    tx_playlists: &Sender<Vec<crate::api::Playlist>>,
+    tx_reposts: &Sender<(Vec<String>, Vec<String>)>,
+    tx_api_errors: &Sender<ApiError>,
+    async_rt: &tokio::runtime::Runtime,
+) {
+    spawn_page(
+        &mut state.playlists_task,
+        async_rt,
+        api,
+        tx_playlists.clone(),
+        tx_api_errors.clone(),
+        |api| async move { api.get_playlists().await },
+    );
+
+    let token = api.token_clone();
+    let tx_reposts = tx_reposts.clone();
+    async_rt.spawn(async move {
+        if let Ok(reposts) = retry_after_login(|| fetch_my_reposts(Arc::clone(&token))).await {
+            let _ = tx_reposts.send(reposts);
         }
     });
 }
 
+struct LoadedAccount {
+    token: Token,
+    api: API,
+    data: AppData,
+}
+
+/// Logs into `profile` and loads its library on a client of its own, so a
+/// failed login leaves the running account untouched. Blocks on the browser
+/// login when the profile has no usable token.
+fn load_account(profile: &str, rt: &tokio::runtime::Handle) -> anyhow::Result<LoadedAccount> {
+    let token = match read_token(profile) {
+        Some(token) if !token.is_expired() => token,
+        Some(token) => refresh_token(&token).or_else(|_| authenticate(profile))?,
+        None => authenticate(profile)?,
+    };
+    let api = API::init(Arc::new(Mutex::new(token.clone())));
+    let library = rt.block_on(Library::fetch(&api))?;
+    let _ = library.save(profile);
+    let data = AppData::new(library, 0);
+    Ok(LoadedAccount { token, api, data })
+}
+
+/// Sends one playlist edit, `None` when there's nothing for the UI to catch up with.
+async fn run_playlist_action(
+    token: Arc<Mutex<Token>>,
+    action: PlaylistAction,
+) -> Option<PlaylistDone> {
+    let result = match &action {
+        PlaylistAction::Create { title, track } => {
+            let track_urns: Vec<String> = track.iter().map(|t| t.track_urn.clone()).collect();
+            return retry_after_login(|| {
+                create_playlist(
+                    Arc::clone(&token),
+                    title.clone(),
+                    "public".to_string(),
+                    track_urns.clone(),
+                )
+            })
+            .await
+            .ok()
+            .map(|playlist| PlaylistDone::Created(Box::new(playlist)));
+        }
+        PlaylistAction::Rename { playlist_id, title, .. } => {
+            retry_after_login(|| {
+                update_playlist(Arc::clone(&token), *playlist_id, Some(title.clone()), None)
+            })
+            .await
+        }
+        PlaylistAction::SetSharing { playlist_id, sharing, .. } => {
+            retry_after_login(|| {
+                let sharing = Some(sharing.clone());
+                update_playlist(Arc::clone(&token), *playlist_id, None, sharing)
+            })
+            .await
+        }
+        PlaylistAction::Delete { playlist_id, .. } => {
+            retry_after_login(|| delete_playlist(Arc::clone(&token), *playlist_id)).await
+        }
+        PlaylistAction::AddTrack {
+            tracks_uri,
+            playlist_id,
+            track,
+        } => {
+            let edit = TrackEdit::Add(track.track_urn.clone());
+            edit_tracks(&token, tracks_uri, *playlist_id, edit).await
+        }
+        PlaylistAction::RemoveTrack {
+            tracks_uri,
+            playlist_id,
+            track,
+        } => {
+            let edit = TrackEdit::Remove(track.track_urn.clone());
+            edit_tracks(&token, tracks_uri, *playlist_id, edit).await
+        }
+        PlaylistAction::MoveTrack {
+            tracks_uri,
+            playlist_id,
+            track_urn,
+            up,
+        } => {
+            let edit = TrackEdit::Move {
+                track_urn: track_urn.clone(),
+                up: *up,
+            };
+            edit_tracks(&token, tracks_uri, *playlist_id, edit).await
+        }
+    };
+    result.err().map(|_| PlaylistDone::Failed(Box::new(action)))
+}
+
+async fn edit_tracks(
+    token: &Arc<Mutex<Token>>,
+    tracks_uri: &str,
+    playlist_id: u64,
+    edit: TrackEdit,
+) -> anyhow::Result<()> {
+    retry_after_login(|| {
+        edit_playlist_tracks(Arc::clone(token), tracks_uri.to_string(), playlist_id, edit.clone())
+    })
+    .await
+}
+
+/// Puts back what a failed playlist edit changed, unless a later edit changed it again,
+/// and reloads the playlist's tracks.
+fn undo_playlist_action(action: PlaylistAction, data: &mut AppData) {
+    if let Some(tracks_uri) = action.tracks_uri()
+        && data.playlist_tracks_uri.as_deref() == Some(tracks_uri)
+    {
+        data.playlist_tracks_uri = None;
+    }
+    let tracks_uri = action.tracks_uri().unwrap_or_default().to_string();
+    let playlist = data
+        .playlists
+        .iter_mut()
+        .find(|p| p.is_owned && p.tracks_uri == tracks_uri);
+
+    match (action, playlist) {
+        (
+            PlaylistAction::Rename {
+                title,
+                previous_title,
+                ..
+            },
+            Some(playlist),
+        ) if playlist.title == title => playlist.title = previous_title,
+        (
+            PlaylistAction::SetSharing {
+                sharing,
+                previous_sharing,
+                ..
+            },
+            Some(playlist),
+        ) if playlist.sharing == sharing => playlist.sharing = previous_sharing,
+        (PlaylistAction::Delete { playlist, .. }, None) => {
+            let at = data
+                .playlists
+                .iter()
+                .position(|p| p.created_at < playlist.created_at)
+                .unwrap_or(data.playlists.len());
+            data.playlists.insert(at, *playlist);
+        }
+        (PlaylistAction::AddTrack { track, .. }, Some(playlist)) => {
+            playlist.track_count = playlist.track_count.saturating_sub(1);
+            playlist.duration_ms = playlist.duration_ms.saturating_sub(track.duration_ms);
+        }
+        (PlaylistAction::RemoveTrack { track, .. }, Some(playlist)) => {
+            playlist.track_count += 1;
+            playlist.duration_ms += track.duration_ms;
+        }
+        _ => {}
+    }
+}
+
 fn start(
     mut terminal: DefaultTerminal,
-    api: &mut Arc<Mutex<API>>,
+    mut api: API,
     player: Player,
 ) -> anyhow::Result<()> {
     let mut state = AppState::new();
+    // a config file that does not parse keeps the defaults
+    let config = ConfigFile::load().ok();
+    state.seek_steps = config.as_ref().map(SeekSteps::from_config).unwrap_or_default();
+    state.settings = Settings::load();
+    state.settings.default_stream_quality = config
+        .and_then(|config| config.stream_quality)
+        .unwrap_or_default();
+    let async_rt = tokio::runtime::Runtime::new().unwrap();
 
-    let mut api_guard = api.lock().unwrap();
-    let mut data = AppData::new(&mut api_guard, state.selected_row)?;
-    drop(api_guard);
+    // the last run's library, shown until this run's has loaded
+    let cached = Library::load_cached(&active_profile()).unwrap_or_default();
+    let mut data = AppData::new(cached, state.selected_row);
 
     let mut signal = SinSignal::new(0.1, 2.0, 10.0);
     let mut data_points = signal.by_ref().take(200).collect::<Vec<(f64, f64)>>();
     let mut window = [0.0, 20.0];
-    let async_rt = tokio::runtime::Runtime::new().unwrap();
 
     let (tx_likes, rx_likes): (Sender<Vec<crate::api::Track>>, Receiver<Vec<crate::api::Track>>) =
         mpsc::channel();
@@ -148,12 +405,30 @@ fn start(
         Receiver<(u64, Vec<crate::api::Track>)>,
     ) = mpsc::channel();
 
-    let (tx_engagement, rx_engagement): (Sender<EngagementDone>, Receiver<EngagementDone>) =
-        mpsc::channel();
-
-    spawn_fetch(Arc::clone(api), tx_playlists.clone(), |api| {
-        api.get_playlists()
-    });
+    // bumped on an account switch, work the old account started carries the
+    // generation before and neither runs on nor lands in the new account's data
+    let account_generation = Arc::new(AtomicU64::new(0));
+    let (tx_engagement, rx_engagement) =
+        mpsc::channel::<(u64, u64, Option<EngagementDone>)>();
+    let (tx_playlist_done, rx_playlist_done) = mpsc::channel::<(u64, PlaylistDone)>();
+    let mut playlist_tasks: HashMap<String, tokio::task::JoinHandle<()>> = HashMap::new();
+    let mut playlist_creates: Vec<tokio::task::JoinHandle<()>> = Vec::new();
+    let (tx_reposts, rx_reposts) = mpsc::channel::<(Vec<String>, Vec<String>)>();
+    let (tx_comments, rx_comments) = mpsc::channel::<(u64, Vec<Comment>)>();
+    let (tx_comment_posted, rx_comment_posted) = mpsc::channel::<(u64, String, Comment)>();
+    let (tx_radio, rx_radio) = mpsc::channel::<(u64, RadioRequest, Vec<Track>)>();
+    let (tx_feed, rx_feed) = mpsc::channel::<Vec<Activity>>();
+    let (tx_api_errors, rx_api_errors) = mpsc::channel::<ApiError>();
+    let (tx_library, rx_library) = mpsc::channel::<anyhow::Result<Library>>();
+    let (tx_profile, rx_profile) =
+        mpsc::channel::<(u64, Result<(UserProfile, Option<Vec<u8>>), String>)>();
+    let (tx_profile_tracks, rx_profile_tracks) =
+        mpsc::channel::<(PlaybackSource, String, Vec<Track>)>();
+    let (tx_feed_tracks, rx_feed_tracks) = mpsc::channel::<(u64, Vec<Track>)>();
+
+    let (tx_account, rx_account) = mpsc::channel::<(String, anyhow::Result<LoadedAccount>)>();
+
+    spawn_library_load(&mut state, &async_rt, &api, &tx_library);
 
     let mut picker = Picker::from_query_stdio()?;
 
@@ -171,15 +446,152 @@ fn start(
         });
     }
 
+    // The profile avatar gets its own worker, resize responses are matched to a
+    // protocol by an id that every `ThreadProtocol` starts counting from zero.
+    let (tx_avatar_worker, rx_avatar_worker) = mpsc::channel::<ResizeRequest>();
+    {
+        let tx_main_render = tx_main.clone();
+        std::thread::spawn(move || loop {
+            if let Ok(request) = rx_avatar_worker.recv() {
+                tx_main_render
+                    .send(AppEvent::AvatarRedraw(request.resize_encode()))
+                    .unwrap();
+            }
+        });
+    }
+
     let mut cover_art_async = ThreadProtocol::new(tx_worker.clone(), None);
     let mut last_artwork_url: Option<String> = None;
     let mut last_artwork_image: Option<DynamicImage> = None;
+    let mut profile_avatar = ThreadProtocol::new(tx_avatar_worker.clone(), None);
+    let mut last_avatar_image: Option<DynamicImage> = None;
 
     let wave_buffer = player.wave_buffer();
     let tick_rate = Duration::from_millis(200);
     let mut last_tick = Instant::now();
 
     loop {
+        while let Ok((profile, result)) = rx_account.try_recv() {
+            match result {
+                Ok(account) => {
+                    set_active_profile(&profile);
+                    account_generation.fetch_add(1, Ordering::SeqCst);
+                    for (_, task) in playlist_tasks.drain() {
+                        task.abort();
+                    }
+                    for task in playlist_creates.drain(..) {
+                        task.abort();
+                    }
+                    while rx_playlist_done.try_recv().is_ok() {}
+                    while rx_engagement.try_recv().is_ok() {}
+                    while rx_comment_posted.try_recv().is_ok() {}
+                    // the player and refresh thread hold the shared token, swap its contents
+                    let token = api.token_clone();
+                    *token.lock().unwrap() = account.token;
+                    api = account.api;
+                    api.set_token(token);
+
+                    player.pause();
+                    state.abort_tasks();
+                    let mut new_state = AppState::new();
+                    new_state.shuffle_enabled = state.shuffle_enabled;
+                    new_state.repeat_enabled = state.repeat_enabled;
+                    new_state.autoplay_enabled = state.autoplay_enabled;
+                    new_state.visualizer_view = state.visualizer_view;
+                    new_state.seek_steps = state.seek_steps;
+                    new_state.settings = state.settings.clone();
+                    state = new_state;
+                    state.library_fresh = true;
+                    data = account.data;
+
+                    // pages of the old account still in flight
+                    while rx_likes.try_recv().is_ok() {}
+                    while rx_playlists.try_recv().is_ok() {}
+                    while rx_albums.try_recv().is_ok() {}
+                    while rx_following.try_recv().is_ok() {}
+                    while rx_feed.try_recv().is_ok() {}
+                    while rx_reposts.try_recv().is_ok() {}
+                    while rx_library.try_recv().is_ok() {}
+                    spawn_account_fetches(
+                        &mut state,
+                        &api,
+                        &tx_playlists,
+                        &tx_reposts,
+                        &tx_api_errors,
+                        &async_rt,
+                    );
+                }
+                Err(e) => {
+                    let picker = state
+                        .account_picker
+                        .get_or_insert_with(|| AccountPicker::new(&active_profile()));
+                    picker.switching = None;
+                    picker.error = Some(format!("Could not switch to {}: {}", profile, e));
+                }
+            }
+        }
+
+        while let Ok(result) = rx_library.try_recv() {
+            match result {
+                Ok(library) => {
+                    data.replace_library(library);
+                    // toggles still in flight aren't in the fetched library yet
+                    for action in state.engagement_in_flight.values() {
+                        data.apply_engagement(action);
+                    }
+                    state.library_fresh = true;
+                    state.offline = false;
+                    spawn_account_fetches(
+                        &mut state,
+                        &api,
+                        &tx_playlists,
+                        &tx_reposts,
+                        &tx_api_errors,
+                        &async_rt,
+                    );
+                }
+                Err(e) => {
+                    state.offline = ApiError::is_offline(&e);
+                    if let Some(error) = ApiError::of(&e)
+                        && !state.offline
+                    {
+                        state.api_error = Some((error.clone(), Instant::now()));
+                    }
+                    state.library_retry_at = Some(Instant::now() + LIBRARY_RETRY);
+                }
+            }
+        }
+        if state
+            .library_retry_at
+            .is_some_and(|at| at <= Instant::now())
+        {
+            state.library_retry_at = None;
+            spawn_library_load(&mut state, &async_rt, &api, &tx_library);
+        }
+
+        if std::mem::take(&mut state.login_requested) {
+            mark_logging_in();
+            // only the token's contents change, the client keeps its page cursors
+            let token = api.token_clone();
+            let profile = token.lock().unwrap().profile.clone();
+            std::thread::spawn(move || match authenticate(&profile) {
+                Ok(new_token) => {
+                    *token.lock().unwrap() = new_token;
+                    mark_logged_in();
+                }
+                Err(e) => mark_login_failed(e.to_string()),
+            });
+        }
+
+        if let Some(profile) = state.account_request.take() {
+            let tx = tx_account.clone();
+            let rt = async_rt.handle().clone();
+            std::thread::spawn(move || {
+                let result = load_account(&profile, &rt);
+                let _ = tx.send((profile, result));
+            });
+        }
+
         data.apply_updates(
             &rx_likes,
             &rx_playlists,
@@ -195,6 +607,26 @@ fn start(
             state.following_likes_request_id,
         );
 
+        while let Ok(activities) = rx_feed.try_recv() {
+            data.feed.extend(activities);
+        }
+        while let Ok(error) = rx_api_errors.try_recv() {
+            state.api_error = Some((error, Instant::now()));
+        }
+        if state
+            .api_error
+            .as_ref()
+            .is_some_and(|(_, at)| at.elapsed() >= API_ERROR_NOTICE)
+        {
+            state.api_error = None;
+        }
+        while let Ok((request_id, tracks)) = rx_feed_tracks.try_recv() {
+            if request_id == state.feed_tracks_request_id {
+                data.feed_tracks = tracks;
+                data.feed_tracks_state.select(Some(0));
+            }
+        }
+
         while let Ok((request_id, tracks)) = rx_search_tracks.try_recv() {
             if request_id == state.search_results_request_id {
                 data.search_tracks = tracks;
@@ -245,7 +677,118 @@ fn start(
             }
         }
 
-        while let Ok(done) = rx_engagement.try_recv() {
+        while let Ok((request_id, comments)) = rx_comments.try_recv() {
+            if request_id == state.comments_request_id {
+                data.comments = comments;
+                state.comments_selected = 0;
+            }
+        }
+        while let Ok((generation, track_urn, comment)) = rx_comment_posted.try_recv() {
+            if generation == account_generation.load(Ordering::SeqCst)
+                && data.comments_track_urn.as_deref() == Some(track_urn.as_str()) {
+                let idx = data
+                    .comments
+                    .partition_point(|c| c.timestamp_ms <= comment.timestamp_ms);
+                data.comments.insert(idx, comment);
+            }
+        }
+
+        while let Ok((request_id, request, tracks)) = rx_radio.try_recv() {
+            if request_id != state.radio_request_id {
+                continue;
+            }
+            match request {
+                RadioRequest::Autoplay { .. } => {
+                    data.radio_tracks = dedupe_radio_tracks(tracks, &state, &data);
+                }
+                RadioRequest::Track(seed) => {
+                    state.recently_played.push_back(seed.track_urn.clone());
+                    let mut radio = vec![*seed];
+                    radio.extend(dedupe_radio_tracks(tracks, &state, &data));
+                    start_radio(radio, &mut state, &mut data, &player);
+                }
+                RadioRequest::Artist { .. } => {
+                    let radio = dedupe_radio_tracks(tracks, &state, &data);
+                    start_radio(radio, &mut state, &mut data, &player);
+                }
+            }
+        }
+
+        if let Some(request) = state.radio_request.take() {
+            if let Some(handle) = state.radio_task.take() {
+                handle.abort();
+            }
+            state.radio_request_id = state.radio_request_id.wrapping_add(1);
+            let request_id = state.radio_request_id;
+            let token = api.token_clone();
+            let tx = tx_radio.clone();
+            state.radio_task = Some(async_rt.spawn(async move {
+                let result = match &request {
+                    RadioRequest::Autoplay { seed_urn } => {
+                        retry_after_login(|| {
+                            fetch_related_tracks(Arc::clone(&token), seed_urn.clone())
+                        })
+                        .await
+                    }
+                    RadioRequest::Track(seed) => {
+                        retry_after_login(|| {
+                            fetch_related_tracks(Arc::clone(&token), seed.track_urn.clone())
+                        })
+                        .await
+                    }
+                    RadioRequest::Artist { user_urn } => {
+                        retry_after_login(|| {
+                            fetch_artist_radio(Arc::clone(&token), user_urn.clone())
+                        })
+                        .await
+                    }
+                };
+                if let Ok(tracks) = result {
+                    let _ = tx.send((request_id, request, tracks));
+                }
+            }));
+        }
+
+        while let Some(pending) = state.comment_queue.pop_front() {
+            let token = api.token_clone();
+            let tx = tx_comment_posted.clone();
+            let accounts = Arc::clone(&account_generation);
+            let generation = accounts.load(Ordering::SeqCst);
+            async_rt.spawn(async move {
+                if accounts.load(Ordering::SeqCst) != generation {
+                    return;
+                }
+                let posted = retry_after_login(|| {
+                    post_comment(
+                        Arc::clone(&token),
+                        pending.track_urn.clone(),
+                        pending.body.clone(),
+                        pending.timestamp_ms,
+                    )
+                })
+                .await;
+                if let Ok(comment) = posted {
+                    let _ = tx.send((generation, pending.track_urn, comment));
+                }
+            });
+        }
+
+        while let Ok((track_urns, playlist_uris)) = rx_reposts.try_recv() {
+            data.reposted_track_urns.extend(track_urns);
+            data.reposted_playlist_uris.extend(playlist_uris);
+            for action in state.engagement_in_flight.values() {
+                data.apply_engagement(action);
+            }
+        }
+
+        while let Ok((generation, request_id, done)) = rx_engagement.try_recv() {
+            if generation != account_generation.load(Ordering::SeqCst) {
+                continue;
+            }
+            state.engagement_in_flight.remove(&request_id);
+            let Some(done) = done else {
+                continue;
+            };
             match done {
                 EngagementDone::LikedTrack(track) => {
                     if !data.liked_track_urns.contains(&track.track_urn) {
@@ -347,55 +890,159 @@ fn start(
                         }
                     }
                 }
+                EngagementDone::RepostedTrack { track_urn } => {
+                    if data.reposted_track_urns.contains(&track_urn) {
+                        data.adjust_track_reposts(&track_urn, true);
+                    }
+                }
+                EngagementDone::UnrepostedTrack { track_urn } => {
+                    if !data.reposted_track_urns.contains(&track_urn) {
+                        data.adjust_track_reposts(&track_urn, false);
+                    }
+                }
+                EngagementDone::RepostedPlaylist { tracks_uri } => {
+                    if data.reposted_playlist_uris.contains(&tracks_uri) {
+                        data.adjust_playlist_reposts(&tracks_uri, true);
+                    }
+                }
+                EngagementDone::UnrepostedPlaylist { tracks_uri } => {
+                    if !data.reposted_playlist_uris.contains(&tracks_uri) {
+                        data.adjust_playlist_reposts(&tracks_uri, false);
+                    }
+                }
             }
         }
 
         while let Some(action) = state.engagement_queue.pop_front() {
-            let token = {
-                let api_guard = api.lock().unwrap();
-                api_guard.token_clone()
-            };
+            state.engagement_request_id = state.engagement_request_id.wrapping_add(1);
+            let request_id = state.engagement_request_id;
+            state.engagement_in_flight.insert(request_id, action.clone());
+            let token = api.token_clone();
             let tx = tx_engagement.clone();
+            let accounts = Arc::clone(&account_generation);
+            let generation = accounts.load(Ordering::SeqCst);
             async_rt.spawn(async move {
+                if accounts.load(Ordering::SeqCst) != generation {
+                    return;
+                }
                 let result: anyhow::Result<EngagementDone> = match action {
-                    EngagementAction::LikeTrack { track, track_id } => like_track(token, track_id)
-                        .await
-                        .map(|_| EngagementDone::LikedTrack(track)),
+                    EngagementAction::LikeTrack { track, track_id } => {
+                        retry_after_login(|| like_track(Arc::clone(&token), track_id))
+                            .await
+                            .map(|_| EngagementDone::LikedTrack(track))
+                    }
                     EngagementAction::UnlikeTrack { track_urn, track_id } => {
-                        unlike_track(token, track_id)
+                        retry_after_login(|| unlike_track(Arc::clone(&token), track_id))
                             .await
                             .map(|_| EngagementDone::UnlikedTrack { track_urn })
                     }
                     EngagementAction::LikePlaylist {
                         playlist,
                         playlist_id,
-                    } => like_playlist(token, playlist_id)
-                        .await
-                        .map(|_| EngagementDone::LikedPlaylist(playlist)),
+                    } => {
+                        retry_after_login(|| like_playlist(Arc::clone(&token), playlist_id))
+                            .await
+                            .map(|_| EngagementDone::LikedPlaylist(playlist))
+                    }
                     EngagementAction::UnlikePlaylist { tracks_uri, playlist_id } => {
-                        unlike_playlist(token, playlist_id)
+                        retry_after_login(|| unlike_playlist(Arc::clone(&token), playlist_id))
                             .await
                             .map(|_| EngagementDone::UnlikedPlaylist { tracks_uri })
                     }
-                    EngagementAction::LikeAlbum { album, playlist_id } => like_playlist(token, playlist_id)
-                        .await
-                        .map(|_| EngagementDone::LikedAlbum(album)),
+                    EngagementAction::LikeAlbum { album, playlist_id } => {
+                        retry_after_login(|| like_playlist(Arc::clone(&token), playlist_id))
+                            .await
+                            .map(|_| EngagementDone::LikedAlbum(album))
+                    }
                     EngagementAction::UnlikeAlbum { tracks_uri, playlist_id } => {
-                        unlike_playlist(token, playlist_id)
+                        retry_after_login(|| unlike_playlist(Arc::clone(&token), playlist_id))
                             .await
                             .map(|_| EngagementDone::UnlikedAlbum { tracks_uri })
                     }
-                    EngagementAction::FollowUser { artist, user_id } => follow_user(token, user_id)
-                        .await
-                        .map(|_| EngagementDone::FollowedUser(artist)),
-                    EngagementAction::UnfollowUser { urn, user_id } => unfollow_user(token, user_id)
-                        .await
-                        .map(|_| EngagementDone::UnfollowedUser { urn }),
+                    EngagementAction::FollowUser { artist, user_id } => {
+                        retry_after_login(|| follow_user(Arc::clone(&token), user_id))
+                            .await
+                            .map(|_| EngagementDone::FollowedUser(artist))
+                    }
+                    EngagementAction::UnfollowUser { urn, user_id } => {
+                        retry_after_login(|| unfollow_user(Arc::clone(&token), user_id))
+                            .await
+                            .map(|_| EngagementDone::UnfollowedUser { urn })
+                    }
+                    EngagementAction::RepostTrack { track_urn, track_id } => {
+                        retry_after_login(|| repost_track(Arc::clone(&token), track_id))
+                            .await
+                            .map(|_| EngagementDone::RepostedTrack { track_urn })
+                    }
+                    EngagementAction::UnrepostTrack { track_urn, track_id } => {
+                        retry_after_login(|| unrepost_track(Arc::clone(&token), track_id))
+                            .await
+                            .map(|_| EngagementDone::UnrepostedTrack { track_urn })
+                    }
+                    EngagementAction::RepostPlaylist { tracks_uri, playlist_id } => {
+                        retry_after_login(|| repost_playlist(Arc::clone(&token), playlist_id))
+                            .await
+                            .map(|_| EngagementDone::RepostedPlaylist { tracks_uri })
+                    }
+                    EngagementAction::UnrepostPlaylist { tracks_uri, playlist_id } => {
+                        retry_after_login(|| unrepost_playlist(Arc::clone(&token), playlist_id))
+                            .await
+                            .map(|_| EngagementDone::UnrepostedPlaylist { tracks_uri })
+                    }
                 };
-                if let Ok(done) = result {
-                    let _ = tx.send(done);
+                let _ = tx.send((generation, request_id, result.ok()));
+            });
+        }
+
+        while let Ok((generation, done)) = rx_playlist_done.try_recv() {
+            if generation != account_generation.load(Ordering::SeqCst) {
+                continue;
+            }
+            match done {
+                PlaylistDone::Created(playlist) => {
+                    let exists = data
+                        .playlists
+                        .iter()
+                        .any(|p| p.tracks_uri == playlist.tracks_uri && p.is_owned);
+                    if !exists {
+                        data.playlists.insert(0, *playlist);
+                        if state.selected_tab == 0 && state.selected_subtab == 1 {
+                            state.selected_row = 0;
+                            data.playlists_state.select(Some(0));
+                        }
+                    }
+                }
+                PlaylistDone::Failed(action) => undo_playlist_action(*action, &mut data),
+            }
+        }
+
+        // one playlist's edits run in order, each PUT builds on the one before it
+        playlist_tasks.retain(|_, task| !task.is_finished());
+        playlist_creates.retain(|task| !task.is_finished());
+        while let Some(action) = state.playlist_queue.pop_front() {
+            let token = api.token_clone();
+            let tx = tx_playlist_done.clone();
+            let accounts = Arc::clone(&account_generation);
+            let generation = accounts.load(Ordering::SeqCst);
+            let key = action.tracks_uri().map(str::to_string);
+            let previous = key.as_ref().and_then(|key| playlist_tasks.remove(key));
+            let task = async_rt.spawn(async move {
+                if let Some(previous) = previous {
+                    let _ = previous.await;
+                }
+                if accounts.load(Ordering::SeqCst) != generation {
+                    return;
+                }
+                if let Some(done) = run_playlist_action(token, action).await {
+                    let _ = tx.send((generation, done));
                 }
             });
+            match key {
+                Some(key) => {
+                    playlist_tasks.insert(key, task);
+                }
+                None => playlist_creates.push(task),
+            }
         }
 
         while let Ok(app_ev) = rx_main.try_recv() {
@@ -403,6 +1050,9 @@ fn start(
                 AppEvent::Redraw(completed) => {
                     let _ = cover_art_async.update_resized_protocol(completed?);
                 }
+                AppEvent::AvatarRedraw(completed) => {
+                    let _ = profile_avatar.update_resized_protocol(completed?);
+                }
             }
         }
 
@@ -427,6 +1077,132 @@ fn start(
             }
         }
 
+        let profile_urn = state
+            .profile
+            .as_ref()
+            .filter(|view| view.error.is_none())
+            .map(|view| view.user_urn.clone());
+        if let Some(user_urn) = profile_urn
+            && data.profile_user_urn.as_deref() != Some(user_urn.as_str())
+        {
+            if let Some(handle) = state.profile_task.take() {
+                handle.abort();
+            }
+            state.profile_request_id = state.profile_request_id.wrapping_add(1);
+            let request_id = state.profile_request_id;
+            let token = api.token_clone();
+            data.profile = None;
+            data.profile_user_urn = Some(user_urn.clone());
+            profile_avatar.empty_protocol();
+            last_avatar_image = None;
+            let tx = tx_profile.clone();
+            state.profile_task = Some(async_rt.spawn(async move {
+                let (artist, playlists, reposts) = futures::join!(
+                    retry_after_login(|| fetch_user(Arc::clone(&token), user_urn.clone())),
+                    retry_after_login(|| {
+                        fetch_user_playlists(Arc::clone(&token), user_urn.clone())
+                    }),
+                    retry_after_login(|| fetch_user_reposts(Arc::clone(&token), user_urn.clone())),
+                );
+                let artist = match artist {
+                    Ok(artist) => artist,
+                    Err(e) => {
+                        let _ = tx.send((request_id, Err(format!("{:#}", e))));
+                        return;
+                    }
+                };
+                let avatar = match reqwest::get(artist.avatar_url.as_str()).await {
+                    Ok(resp) => resp.bytes().await.ok().map(|b| b.to_vec()),
+                    Err(_) => None,
+                };
+                let (playlists, albums) = playlists.unwrap_or_default();
+                let profile = UserProfile {
+                    artist,
+                    playlists,
+                    albums,
+                    reposts: reposts.unwrap_or_default(),
+                };
+                let _ = tx.send((request_id, Ok((profile, avatar))));
+            }));
+        }
+
+        while let Ok((request_id, result)) = rx_profile.try_recv() {
+            if request_id != state.profile_request_id {
+                continue;
+            }
+            let (profile, avatar) = match result {
+                Ok(loaded) => loaded,
+                Err(error) => {
+                    // forget the attempt so opening the profile again retries it
+                    data.profile_user_urn = None;
+                    if let Some(view) = state.profile.as_mut() {
+                        view.error = Some(error);
+                    }
+                    continue;
+                }
+            };
+            if let Some(dyn_img) = avatar.and_then(|bytes| image::load_from_memory(&bytes).ok()) {
+                let resize_proto = picker.new_resize_protocol(dyn_img.clone());
+                profile_avatar = ThreadProtocol::new(tx_avatar_worker.clone(), Some(resize_proto));
+                last_avatar_image = Some(dyn_img);
+            }
+            data.profile = Some(profile);
+        }
+
+        if let Some(tracks_uri) = state.profile_play.take() {
+            let source = match &data.profile {
+                Some(profile) if profile.albums.iter().any(|a| a.tracks_uri == tracks_uri) => {
+                    PlaybackSource::Album
+                }
+                _ => PlaybackSource::Playlist,
+            };
+            let token = api.token_clone();
+            let tx = tx_profile_tracks.clone();
+            async_rt.spawn(async move {
+                let result = retry_after_login(|| {
+                    fetch_playlist_tracks(Arc::clone(&token), tracks_uri.clone())
+                })
+                .await;
+                if let Ok(tracks) = result {
+                    let _ = tx.send((source, tracks_uri, tracks));
+                }
+            });
+        }
+
+        while let Ok((source, tracks_uri, tracks)) = rx_profile_tracks.try_recv() {
+            let start = tracks.iter().position(|t| t.is_playable()).unwrap_or(0);
+            play_track_list(tracks, start, source, Some(tracks_uri), &mut state, &mut data, &player);
+        }
+
+        let playing_urn = player.current_track().track_urn;
+        if !playing_urn.is_empty() && state.recently_played.back() != Some(&playing_urn) {
+            state.recently_played.push_back(playing_urn.clone());
+            if state.recently_played.len() > RECENTLY_PLAYED_LIMIT {
+                state.recently_played.pop_front();
+            }
+        }
+        if !playing_urn.is_empty() && data.comments_track_urn.as_deref() != Some(&playing_urn) {
+            if let Some(handle) = state.comments_task.take() {
+                handle.abort();
+            }
+            state.comments_request_id = state.comments_request_id.wrapping_add(1);
+            let request_id = state.comments_request_id;
+            let token = api.token_clone();
+            data.comments.clear();
+            data.comments_track_urn = Some(playing_urn.clone());
+            state.comments_selected = 0;
+            let tx = tx_comments.clone();
+            state.comments_task = Some(async_rt.spawn(async move {
+                let result = retry_after_login(|| {
+                    fetch_track_comments(Arc::clone(&token), playing_urn.clone())
+                })
+                .await;
+                if let Ok(comments) = result {
+                    let _ = tx.send((request_id, comments));
+                }
+            }));
+        }
+
         let filter_active = is_filter_active(&state);
         let filtered = build_filtered_views(&state, &data);
         let likes_len = if filter_active && state.selected_subtab == 0 {
@@ -491,17 +1267,18 @@ fn start(
                     state.playlist_tracks_request_id =
                         state.playlist_tracks_request_id.wrapping_add(1);
                     let request_id = state.playlist_tracks_request_id;
-                    let token = {
-                        let api_guard = api.lock().unwrap();
-                        api_guard.token_clone()
-                    };
+                    let token = api.token_clone();
                     data.playlist_tracks_uri = Some(tracks_uri.clone());
                     data.playlist_tracks.clear();
                     data.playlist_tracks_state.select(Some(0));
                     state.selected_playlist_track_row = 0;
                     let tx = tx_playlist_tracks.clone();
                     state.playlist_tracks_task = Some(async_rt.spawn(async move {
-                        if let Ok(tracks) = fetch_playlist_tracks(token, tracks_uri).await {
+                        let result = retry_after_login(|| {
+                            fetch_playlist_tracks(Arc::clone(&token), tracks_uri.clone())
+                        })
+                        .await;
+                        if let Ok(tracks) = result {
                             let _ = tx.send((request_id, tracks));
                         }
                     }));
@@ -544,17 +1321,18 @@ fn start(
                     state.album_tracks_request_id =
                         state.album_tracks_request_id.wrapping_add(1);
                     let request_id = state.album_tracks_request_id;
-                    let token = {
-                        let api_guard = api.lock().unwrap();
-                        api_guard.token_clone()
-                    };
+                    let token = api.token_clone();
                     data.album_tracks_uri = Some(tracks_uri.clone());
                     data.album_tracks.clear();
                     data.album_tracks_state.select(Some(0));
                     state.selected_album_track_row = 0;
                     let tx = tx_album_tracks.clone();
                     state.album_tracks_task = Some(async_rt.spawn(async move {
-                        if let Ok(tracks) = fetch_album_tracks(token, tracks_uri).await {
+                        let result = retry_after_login(|| {
+                            fetch_album_tracks(Arc::clone(&token), tracks_uri.clone())
+                        })
+                        .await;
+                        if let Ok(tracks) = result {
                             let _ = tx.send((request_id, tracks));
                         }
                     }));
@@ -593,10 +1371,7 @@ fn start(
                     state.following_tracks_request_id =
                         state.following_tracks_request_id.wrapping_add(1);
                     let request_id = state.following_tracks_request_id;
-                    let token = {
-                        let api_guard = api.lock().unwrap();
-                        api_guard.token_clone()
-                    };
+                    let token = api.token_clone();
                     data.following_tracks_user_urn = Some(user_urn.clone());
                     data.following_tracks.clear();
                     data.following_tracks_state.select(Some(0));
@@ -604,7 +1379,11 @@ fn start(
                     state.following_tracks_focus = FollowingTracksFocus::Published;
                     let tx = tx_following_tracks.clone();
                     state.following_tracks_task = Some(async_rt.spawn(async move {
-                        if let Ok(tracks) = fetch_following_tracks(token, user_urn_for_tracks).await {
+                        let result = retry_after_login(|| {
+                            fetch_following_tracks(Arc::clone(&token), user_urn_for_tracks.clone())
+                        })
+                        .await;
+                        if let Ok(tracks) = result {
                             let _ = tx.send((request_id, tracks));
                         }
                     }));
@@ -622,17 +1401,18 @@ fn start(
                     state.following_likes_request_id =
                         state.following_likes_request_id.wrapping_add(1);
                     let request_id = state.following_likes_request_id;
-                    let token = {
-                        let api_guard = api.lock().unwrap();
-                        api_guard.token_clone()
-                    };
+                    let token = api.token_clone();
                     data.following_likes_user_urn = Some(user_urn.clone());
                     data.following_likes_tracks.clear();
                     data.following_likes_state.select(Some(0));
                     state.selected_following_like_row = 0;
                     let tx = tx_following_likes.clone();
                     state.following_likes_task = Some(async_rt.spawn(async move {
-                        if let Ok(tracks) = fetch_following_liked_tracks(token, user_urn_for_likes).await {
+                        let result = retry_after_login(|| {
+                            fetch_following_liked_tracks(Arc::clone(&token), user_urn_for_likes.clone())
+                        })
+                        .await;
+                        if let Ok(tracks) = result {
                             let _ = tx.send((request_id, tracks));
                         }
                     }));
@@ -693,10 +1473,7 @@ fn start(
                 state.search_people_likes_request_id.wrapping_add(1);
 
             let request_id = state.search_results_request_id;
-            let token = {
-                let api_guard = api.lock().unwrap();
-                api_guard.token_clone()
-            };
+            let token = api.token_clone();
             let query = state.query.clone();
             let filter = state.selected_searchfilter;
 
@@ -742,22 +1519,38 @@ fn start(
                 state.search_results_task = Some(async_rt.spawn(async move {
                     match filter {
                         0 => {
-                            if let Ok(tracks) = fetch_search_tracks(token, query).await {
+                            let result = retry_after_login(|| {
+                                fetch_search_tracks(Arc::clone(&token), query.clone())
+                            })
+                            .await;
+                            if let Ok(tracks) = result {
                                 let _ = tx_tracks.send((request_id, tracks));
                             }
                         }
                         1 => {
-                            if let Ok(albums) = fetch_search_albums(token, query).await {
+                            let result = retry_after_login(|| {
+                                fetch_search_albums(Arc::clone(&token), query.clone())
+                            })
+                            .await;
+                            if let Ok(albums) = result {
                                 let _ = tx_albums.send((request_id, albums));
                             }
                         }
                         2 => {
-                            if let Ok(playlists) = fetch_search_playlists(token, query).await {
+                            let result = retry_after_login(|| {
+                                fetch_search_playlists(Arc::clone(&token), query.clone())
+                            })
+                            .await;
+                            if let Ok(playlists) = result {
                                 let _ = tx_playlists.send((request_id, playlists));
                             }
                         }
                         3 => {
-                            if let Ok(people) = fetch_search_people(token, query).await {
+                            let result = retry_after_login(|| {
+                                fetch_search_people(Arc::clone(&token), query.clone())
+                            })
+                            .await;
+                            if let Ok(people) = result {
                                 let _ = tx_people.send((request_id, people));
                             }
                         }
@@ -814,17 +1607,18 @@ fn start(
                     state.search_playlist_tracks_request_id =
                         state.search_playlist_tracks_request_id.wrapping_add(1);
                     let request_id = state.search_playlist_tracks_request_id;
-                    let token = {
-                        let api_guard = api.lock().unwrap();
-                        api_guard.token_clone()
-                    };
+                    let token = api.token_clone();
                     data.search_playlist_tracks_uri = Some(tracks_uri.clone());
                     data.search_playlist_tracks.clear();
                     data.search_playlist_tracks_state.select(Some(0));
                     state.search_selected_playlist_track_row = 0;
                     let tx = tx_search_playlist_tracks.clone();
                     state.search_playlist_tracks_task = Some(async_rt.spawn(async move {
-                        if let Ok(tracks) = fetch_playlist_tracks(token, tracks_uri).await {
+                        let result = retry_after_login(|| {
+                            fetch_playlist_tracks(Arc::clone(&token), tracks_uri.clone())
+                        })
+                        .await;
+                        if let Ok(tracks) = result {
                             let _ = tx.send((request_id, tracks));
                         }
                     }));
@@ -860,17 +1654,18 @@ fn start(
                     state.search_album_tracks_request_id =
                         state.search_album_tracks_request_id.wrapping_add(1);
                     let request_id = state.search_album_tracks_request_id;
-                    let token = {
-                        let api_guard = api.lock().unwrap();
-                        api_guard.token_clone()
-                    };
+                    let token = api.token_clone();
                     data.search_album_tracks_uri = Some(tracks_uri.clone());
                     data.search_album_tracks.clear();
                     data.search_album_tracks_state.select(Some(0));
                     state.search_selected_album_track_row = 0;
                     let tx = tx_search_album_tracks.clone();
                     state.search_album_tracks_task = Some(async_rt.spawn(async move {
-                        if let Ok(tracks) = fetch_album_tracks(token, tracks_uri).await {
+                        let result = retry_after_login(|| {
+                            fetch_album_tracks(Arc::clone(&token), tracks_uri.clone())
+                        })
+                        .await;
+                        if let Ok(tracks) = result {
                             let _ = tx.send((request_id, tracks));
                         }
                     }));
@@ -909,10 +1704,7 @@ fn start(
                     state.search_people_tracks_request_id =
                         state.search_people_tracks_request_id.wrapping_add(1);
                     let request_id = state.search_people_tracks_request_id;
-                    let token = {
-                        let api_guard = api.lock().unwrap();
-                        api_guard.token_clone()
-                    };
+                    let token = api.token_clone();
                     data.search_people_tracks_user_urn = Some(user_urn.clone());
                     data.search_people_tracks.clear();
                     data.search_people_tracks_state.select(Some(0));
@@ -920,7 +1712,11 @@ fn start(
                     state.search_people_tracks_focus = FollowingTracksFocus::Published;
                     let tx = tx_search_people_tracks.clone();
                     state.search_people_tracks_task = Some(async_rt.spawn(async move {
-                        if let Ok(tracks) = fetch_following_tracks(token, user_urn_for_tracks).await {
+                        let result = retry_after_login(|| {
+                            fetch_following_tracks(Arc::clone(&token), user_urn_for_tracks.clone())
+                        })
+                        .await;
+                        if let Ok(tracks) = result {
                             let _ = tx.send((request_id, tracks));
                         }
                     }));
@@ -938,17 +1734,18 @@ fn start(
                     state.search_people_likes_request_id =
                         state.search_people_likes_request_id.wrapping_add(1);
                     let request_id = state.search_people_likes_request_id;
-                    let token = {
-                        let api_guard = api.lock().unwrap();
-                        api_guard.token_clone()
-                    };
+                    let token = api.token_clone();
                     data.search_people_likes_user_urn = Some(user_urn.clone());
                     data.search_people_likes_tracks.clear();
                     data.search_people_likes_state.select(Some(0));
                     state.search_selected_person_like_row = 0;
                     let tx = tx_search_people_likes.clone();
                     state.search_people_likes_task = Some(async_rt.spawn(async move {
-                        if let Ok(tracks) = fetch_following_liked_tracks(token, user_urn_for_likes).await {
+                        let result = retry_after_login(|| {
+                            fetch_following_liked_tracks(Arc::clone(&token), user_urn_for_likes.clone())
+                        })
+                        .await;
+                        if let Ok(tracks) = result {
                             let _ = tx.send((request_id, tracks));
                         }
                     }));
@@ -981,12 +1778,62 @@ fn start(
             }
         }
 
+        if state.selected_tab == 2 {
+            if !data.feed.is_empty() && state.selected_row >= data.feed.len() {
+                state.selected_row = data.feed.len() - 1;
+            }
+            data.feed_state.select(Some(state.selected_row));
+
+            if let Some(activity) = data.feed.get(state.selected_row) {
+                let key = match &activity.origin {
+                    ActivityOrigin::Track(track) | ActivityOrigin::TrackRepost(track) => {
+                        track.track_urn.clone()
+                    }
+                    ActivityOrigin::Playlist(playlist)
+                    | ActivityOrigin::PlaylistRepost(playlist) => playlist.tracks_uri.clone(),
+                };
+                if data.feed_tracks_key.as_deref() != Some(key.as_str()) {
+                    if let Some(handle) = state.feed_tracks_task.take() {
+                        handle.abort();
+                    }
+                    state.feed_tracks_request_id = state.feed_tracks_request_id.wrapping_add(1);
+                    state.selected_info_row = 0;
+                    data.feed_tracks_state.select(Some(0));
+                    data.feed_tracks_key = Some(key.clone());
+                    if let Some(track) = activity.track() {
+                        data.feed_tracks = vec![track.clone()];
+                    } else {
+                        data.feed_tracks.clear();
+                        let request_id = state.feed_tracks_request_id;
+                        let token = api.token_clone();
+                        let tx = tx_feed_tracks.clone();
+                        state.feed_tracks_task = Some(async_rt.spawn(async move {
+                            let result = retry_after_login(|| {
+                                fetch_playlist_tracks(Arc::clone(&token), key.clone())
+                            })
+                            .await;
+                            if let Ok(tracks) = result {
+                                let _ = tx.send((request_id, tracks));
+                            }
+                        }));
+                    }
+                }
+            }
+
+            if !data.feed_tracks.is_empty() && state.selected_info_row >= data.feed_tracks.len() {
+                state.selected_info_row = data.feed_tracks.len() - 1;
+                data.feed_tracks_state.select(Some(state.selected_info_row));
+            }
+        }
+
+        // spelled out, the draw below borrows the rest of `data` mutably
         let queue_tracks = match state.playback_source {
             PlaybackSource::Likes => &data.likes,
             PlaybackSource::Playlist
             | PlaybackSource::Album
             | PlaybackSource::FollowingPublished
-            | PlaybackSource::FollowingLikes => &data.playback_tracks,
+            | PlaybackSource::FollowingLikes
+            | PlaybackSource::Radio => &data.playback_tracks,
         };
         let previous_playing_track = state
             .playback_history
@@ -1001,6 +1848,10 @@ fn start(
                     .current_playing_index
                     .and_then(|idx| queue_tracks.get(idx).cloned())
             });
+        let current_comment = due_comment(&data.comments, state.progress);
+        let account = active_profile();
+        let session = session_status();
+        let library = state.library_status();
         terminal.draw(|frame| {
             render(
                 frame,
@@ -1011,6 +1862,8 @@ fn start(
                 &data.liked_album_uris,
                 &data.liked_playlist_uris,
                 &data.followed_user_urns,
+                &data.reposted_track_urns,
+                &data.reposted_playlist_uris,
                 playlists_ref,
                 &mut data.playlists_state,
                 playlist_tracks_ref,
@@ -1059,24 +1912,53 @@ fn start(
                 state.search_selected_person_track_row,
                 state.search_selected_person_like_row,
                 state.search_people_tracks_focus == FollowingTracksFocus::Likes,
+                &data.feed,
+                &mut data.feed_state,
+                &data.feed_tracks,
+                &mut data.feed_tracks_state,
                 state.info_pane_selected,
                 state.selected_info_row,
                 &mut data_points,
                 &mut window,
                 &mut state.progress,
+                state.buffering && player.is_playing(),
+                player.is_reconnecting(),
+                state.player_error.as_ref().map(|(reason, _)| reason.as_str()),
+                player.speed(),
+                player.stream_label(),
                 player.current_track(),
                 &mut cover_art_async,
-                player.get_volume(),
+                state.volume,
                 state.shuffle_enabled,
                 state.repeat_enabled,
+                state.autoplay_enabled,
+                &data.comments,
+                current_comment,
+                state.comments_visible,
+                state.comments_selected,
                 state.queue_visible,
                 &state.manual_queue,
                 &state.auto_queue,
                 current_playing_track.clone(),
                 previous_playing_track.clone(),
+                state.playback_source == PlaybackSource::Radio,
+                &data.radio_tracks,
                 state.help_visible,
                 state.quit_confirm_visible,
                 state.quit_confirm_selected,
+                state.playlist_picker.as_ref(),
+                state.playlist_menu.as_ref(),
+                state.prompt.as_ref(),
+                state.profile.as_ref(),
+                data.profile.as_ref(),
+                &mut profile_avatar,
+                state.account_picker.as_ref(),
+                state.settings_menu.as_ref(),
+                &state.settings,
+                &account,
+                &session,
+                state.api_error.as_ref().map(|(error, _)| error),
+                library,
                 state.search_popup_visible,
                 &state.search_query,
                 state.search_matches.len(),
@@ -1104,13 +1986,53 @@ fn start(
                     } else {
                         cover_art_async.empty_protocol();
                     }
+                    if let Some(image) = last_avatar_image.as_ref() {
+                        let resize_proto = picker.new_resize_protocol(image.clone());
+                        profile_avatar =
+                            ThreadProtocol::new(tx_avatar_worker.clone(), Some(resize_proto));
+                    }
                 }
                 _ => {}
             }
         }
 
+        while let Some(event) = player.next_event() {
+            match event {
+                PlayerEvent::Started => {
+                    state.buffering = false;
+                    state.preloaded = None;
+                }
+                // the audio already moved on, catch the queue up with it
+                PlayerEvent::TrackAdvanced => {
+                    state.buffering = false;
+                    advance_after_track_end(&mut state, &mut data, &player)
+                }
+                PlayerEvent::Buffering => state.buffering = true,
+                PlayerEvent::Resumed => state.buffering = false,
+                PlayerEvent::NearEnd => preload_next_track(&mut state, &data, &player),
+                PlayerEvent::Ended => advance_after_track_end(&mut state, &mut data, &player),
+                PlayerEvent::Error { reason } => {
+                    state.player_error = Some((reason, Instant::now()))
+                }
+                PlayerEvent::VolumeChanged(volume) => state.volume = volume,
+            }
+        }
+        sync_preload(&mut state, &data, &player);
+        if state
+            .player_error
+            .as_ref()
+            .is_some_and(|(_, at)| at.elapsed() >= API_ERROR_NOTICE)
+        {
+            state.player_error = None;
+        }
+
         if last_tick.elapsed() >= tick_rate {
             state.progress = player.elapsed();
+            player.set_crossfade(state.crossfade(player.current_track().duration_ms));
+            player.set_normalization(state.settings.normalization());
+            player.set_equalizer(state.settings.equalizer());
+            player.set_preserve_pitch(state.settings.preserve_pitch);
+            player.set_stream_quality(state.settings.stream_quality());
 
             let is_playing = player.is_playing();
             if is_playing {
@@ -1119,110 +2041,19 @@ fn start(
 
             let current_track = player.current_track();
             if is_playing && !current_track.track_urn.is_empty() {
-                let preload_threshold = (current_track.duration_ms as f64 * 0.8) as u64;
-                let should_preload = state.progress >= preload_threshold 
-                    && state.progress < current_track.duration_ms.saturating_sub(100)
-                    && state.preload_triggered_for_track_urn.as_deref() != Some(current_track.track_urn.as_str());
-                
-                if should_preload {
-                    if let Some(current_idx) = state.current_playing_index {
-                        let active_tracks = match state.playback_source {
-                            PlaybackSource::Likes => &data.likes,
-                            PlaybackSource::Playlist
-                            | PlaybackSource::Album
-                            | PlaybackSource::FollowingPublished
-                            | PlaybackSource::FollowingLikes => &data.playback_tracks,
-                        };
-
-                        let next_track = if state.repeat_enabled {
-                            active_tracks.get(current_idx).cloned()
-                        } else if let Some(queued) = state.manual_queue.front() {
-                            Some(queued.track.clone())
-                        } else if let Some(&next_idx) = state.auto_queue.front() {
-                            active_tracks.get(next_idx).cloned()
-                        } else {
-                            if state.auto_queue.is_empty() {
-                                state.auto_queue = build_queue(current_idx, active_tracks, state.shuffle_enabled);
-                            }
-                            state.auto_queue.front().and_then(|&idx| active_tracks.get(idx).cloned())
-                        };
-
-                        if let Some(track) = next_track {
-                            if track.track_urn != current_track.track_urn && track.is_playable() {
-                                player.preload_next(track);
-                                state.preload_triggered_for_track_urn = Some(current_track.track_urn.clone());
-                            }
-                        }
-                    }
-                }
-
-                if state.preload_triggered_for_track_urn.as_deref() != Some(current_track.track_urn.as_str()) {
-                    state.preload_triggered_for_track_urn = None;
+                let queue_exhausted = state.manual_queue.is_empty()
+                    && state.auto_queue.is_empty()
+                    && !state.repeat_enabled;
+                if state.autoplay_enabled
+                    && queue_exhausted
+                    && data.radio_tracks.is_empty()
+                    && state.radio_seed_urn.as_deref() != Some(current_track.track_urn.as_str())
+                {
+                    state.radio_seed_urn = Some(current_track.track_urn.clone());
+                    state.radio_request = Some(RadioRequest::Autoplay {
+                        seed_urn: current_track.track_urn.clone(),
+                    });
                 }
-
-                let at_end = state.progress >= current_track.duration_ms.saturating_sub(50)
-                    && current_track.duration_ms > 0;
-
-                if !at_end {
-                    state.end_handled_track_urn = None;
-                } else if state.end_handled_track_urn.as_deref() != Some(current_track.track_urn.as_str()) {
-                    state.end_handled_track_urn = Some(current_track.track_urn.clone());
-
-                    if let Some(current_idx) = state.current_playing_index {
-                        if state.repeat_enabled {
-                        let active_tracks = match state.playback_source {
-                            PlaybackSource::Likes => &data.likes,
-                            PlaybackSource::Playlist
-                            | PlaybackSource::Album
-                            | PlaybackSource::FollowingPublished
-                            | PlaybackSource::FollowingLikes => &data.playback_tracks,
-                        };
-                        if let Some(track) = active_tracks.get(current_idx) {
-                            player.play(track.clone());
-                            state.override_playing = None;
-                        }
-                        } else {
-                            if state.manual_queue.is_empty() && state.auto_queue.is_empty() {
-                            let active_tracks = match state.playback_source {
-                                PlaybackSource::Likes => &data.likes,
-                                PlaybackSource::Playlist
-                                | PlaybackSource::Album
-                                | PlaybackSource::FollowingPublished
-                                | PlaybackSource::FollowingLikes => &data.playback_tracks,
-                            };
-                                state.auto_queue =
-                                    build_queue(current_idx, active_tracks, state.shuffle_enabled);
-                            }
-                            if let Some(queued) = state.manual_queue.pop_front() {
-                            if let Some(current) = queued_from_current(&state, &data) {
-                                state.playback_history.push(current);
-                            }
-                                play_queued_track(queued, &mut state, &mut data, &player, true);
-                            } else if let Some(next_idx) = state.auto_queue.pop_front() {
-                                let active_tracks = match state.playback_source {
-                                PlaybackSource::Likes => &data.likes,
-                                PlaybackSource::Playlist
-                                | PlaybackSource::Album
-                                | PlaybackSource::FollowingPublished
-                                | PlaybackSource::FollowingLikes => &data.playback_tracks,
-                                };
-                                if let Some(track) = active_tracks.get(next_idx) {
-                                    if let Some(current) = queued_from_current(&state, &data) {
-                                        state.playback_history.push(current);
-                                    }
-                                    player.play(track.clone());
-                                    state.override_playing = None;
-                                    state.current_playing_index = Some(next_idx);
-                                }
-                            } else {
-                                player.pause();
-                                state.current_playing_index = None;
-                            }
-                        }
-                    }
-                }
-            } else {
-                state.preload_triggered_for_track_urn = None;
             }
 
             let filter_active = is_filter_active(&state);
@@ -1281,12 +2112,14 @@ fn start(
                 &data.playlist_tracks
             };
 
+            // spelled out, the draw below borrows the rest of `data` mutably
             let queue_tracks = match state.playback_source {
                 PlaybackSource::Likes => &data.likes,
                 PlaybackSource::Playlist
                 | PlaybackSource::Album
                 | PlaybackSource::FollowingPublished
-                | PlaybackSource::FollowingLikes => &data.playback_tracks,
+                | PlaybackSource::FollowingLikes
+                | PlaybackSource::Radio => &data.playback_tracks,
             };
             let previous_playing_track = state
                 .playback_history
@@ -1301,6 +2134,10 @@ fn start(
                         .current_playing_index
                         .and_then(|idx| queue_tracks.get(idx).cloned())
                 });
+            let current_comment = due_comment(&data.comments, state.progress);
+            let account = active_profile();
+            let session = session_status();
+            let library = state.library_status();
             terminal.draw(|frame| {
                 render(
                     frame,
@@ -1311,6 +2148,8 @@ fn start(
                     &data.liked_album_uris,
                     &data.liked_playlist_uris,
                     &data.followed_user_urns,
+                    &data.reposted_track_urns,
+                    &data.reposted_playlist_uris,
                     playlists_ref,
                     &mut data.playlists_state,
                     playlist_tracks_ref,
@@ -1359,24 +2198,53 @@ fn start(
                     state.search_selected_person_track_row,
                     state.search_selected_person_like_row,
                     state.search_people_tracks_focus == FollowingTracksFocus::Likes,
+                    &data.feed,
+                    &mut data.feed_state,
+                    &data.feed_tracks,
+                    &mut data.feed_tracks_state,
                     state.info_pane_selected,
                     state.selected_info_row,
                     &mut data_points,
                     &mut window,
                     &mut state.progress,
+                    state.buffering && player.is_playing(),
+                    player.is_reconnecting(),
+                    state.player_error.as_ref().map(|(reason, _)| reason.as_str()),
+                    player.speed(),
+                    player.stream_label(),
                     player.current_track(),
                     &mut cover_art_async,
-                    player.get_volume(),
+                    state.volume,
                     state.shuffle_enabled,
                     state.repeat_enabled,
+                    state.autoplay_enabled,
+                    &data.comments,
+                    current_comment,
+                    state.comments_visible,
+                    state.comments_selected,
                     state.queue_visible,
                     &state.manual_queue,
                     &state.auto_queue,
                     current_playing_track,
                     previous_playing_track,
+                    state.playback_source == PlaybackSource::Radio,
+                    &data.radio_tracks,
                     state.help_visible,
                     state.quit_confirm_visible,
                     state.quit_confirm_selected,
+                    state.playlist_picker.as_ref(),
+                    state.playlist_menu.as_ref(),
+                    state.prompt.as_ref(),
+                    state.profile.as_ref(),
+                    data.profile.as_ref(),
+                    &mut profile_avatar,
+                    state.account_picker.as_ref(),
+                    state.settings_menu.as_ref(),
+                    &state.settings,
+                    &account,
+                    &session,
+                    state.api_error.as_ref().map(|(error, _)| error),
+                    library,
                     state.search_popup_visible,
                     &state.search_query,
                     state.search_matches.len(),
@@ -1388,18 +2256,60 @@ fn start(
 
             last_tick = Instant::now();
 
-            match state.selected_subtab {
-                0 => spawn_fetch(Arc::clone(api), tx_likes.clone(), |api| {
-                    api.get_liked_tracks()
-                }),
-                1 => spawn_fetch(Arc::clone(api), tx_playlists.clone(), |api| {
-                    api.get_playlists()
-                }),
-                2 => spawn_fetch(Arc::clone(api), tx_albums.clone(), |api| api.get_albums()),
-                3 => spawn_fetch(Arc::clone(api), tx_following.clone(), |api| {
-                    api.get_following()
-                }),
-                _ => {}
+            // paging on from the snapshot would duplicate what the library load brings,
+            // and each view has at most one page in flight, none once it's exhausted
+            if state.library_fresh {
+                let tx_errors = tx_api_errors.clone();
+                if state.selected_tab == 2 {
+                    if state.selected_row + FEED_PREFETCH_ROWS >= data.feed.len()
+                        && !api.feed.is_exhausted()
+                    {
+                        spawn_page(
+                            &mut state.feed_task,
+                            &async_rt,
+                            &api,
+                            tx_feed.clone(),
+                            tx_errors,
+                            |api| async move { api.get_feed().await },
+                        );
+                    }
+                } else {
+                    match state.selected_subtab {
+                        0 if !api.likes.is_exhausted() => spawn_page(
+                            &mut state.likes_task,
+                            &async_rt,
+                            &api,
+                            tx_likes.clone(),
+                            tx_errors,
+                            |api| async move { api.get_liked_tracks().await },
+                        ),
+                        1 if !api.playlists_exhausted() => spawn_page(
+                            &mut state.playlists_task,
+                            &async_rt,
+                            &api,
+                            tx_playlists.clone(),
+                            tx_errors,
+                            |api| async move { api.get_playlists().await },
+                        ),
+                        2 if !api.albums.is_exhausted() => spawn_page(
+                            &mut state.albums_task,
+                            &async_rt,
+                            &api,
+                            tx_albums.clone(),
+                            tx_errors,
+                            |api| async move { api.get_albums().await },
+                        ),
+                        3 if !api.following.is_exhausted() => spawn_page(
+                            &mut state.following_task,
+                            &async_rt,
+                            &api,
+                            tx_following.clone(),
+                            tx_errors,
+                            |api| async move { api.get_following().await },
+                        ),
+                        _ => {}
+                    }
+                }
             }
         }
     }
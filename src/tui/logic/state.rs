@@ -1,7 +1,11 @@
-use crate::api::{API, Album, Artist, Playlist, Track};
+use crate::api::{Activity, ApiError, Album, Artist, Comment, Playlist, Track};
+use crate::config::{ConfigFile, Settings, profile_names};
 use ratatui::widgets::TableState;
-use std::collections::{HashSet, VecDeque};
+use std::collections::{BTreeMap, HashSet, VecDeque};
 use std::sync::mpsc::Receiver;
+use std::time::{Duration, Instant};
+
+use super::library::Library;
 
 #[derive(Clone, Copy, PartialEq, Eq)]
 pub enum PlaybackSource {
@@ -10,6 +14,8 @@ pub enum PlaybackSource {
     Album,
     FollowingPublished,
     FollowingLikes,
+    /// Related tracks, either from autoplay or an explicit "start radio".
+    Radio,
 }
 
 #[derive(Clone, Copy, PartialEq, Eq)]
@@ -62,6 +68,10 @@ pub enum EngagementAction {
     UnlikeAlbum { tracks_uri: String, playlist_id: u64 },
     FollowUser { artist: Artist, user_id: u64 },
     UnfollowUser { urn: String, user_id: u64 },
+    RepostTrack { track_urn: String, track_id: u64 },
+    UnrepostTrack { track_urn: String, track_id: u64 },
+    RepostPlaylist { tracks_uri: String, playlist_id: u64 },
+    UnrepostPlaylist { tracks_uri: String, playlist_id: u64 },
 }
 
 #[derive(Clone)]
@@ -74,6 +84,235 @@ pub enum EngagementDone {
     UnlikedAlbum { tracks_uri: String },
     FollowedUser(Artist),
     UnfollowedUser { urn: String },
+    RepostedTrack { track_urn: String },
+    UnrepostedTrack { track_urn: String },
+    RepostedPlaylist { tracks_uri: String },
+    UnrepostedPlaylist { tracks_uri: String },
+}
+
+/// Edits are shown straight away, each carries what it replaced so it can be
+/// undone if the request fails.
+#[derive(Clone)]
+pub enum PlaylistAction {
+    Create { title: String, track: Option<Track> },
+    Rename {
+        tracks_uri: String,
+        playlist_id: u64,
+        title: String,
+        previous_title: String,
+    },
+    SetSharing {
+        tracks_uri: String,
+        playlist_id: u64,
+        sharing: String,
+        previous_sharing: String,
+    },
+    Delete { playlist_id: u64, playlist: Box<Playlist> },
+    AddTrack { tracks_uri: String, playlist_id: u64, track: Track },
+    RemoveTrack { tracks_uri: String, playlist_id: u64, track: Track },
+    MoveTrack { tracks_uri: String, playlist_id: u64, track_urn: String, up: bool },
+}
+
+impl PlaylistAction {
+    /// The playlist an edit applies to, `None` when creating one.
+    pub fn tracks_uri(&self) -> Option<&str> {
+        match self {
+            PlaylistAction::Create { .. } => None,
+            PlaylistAction::Delete { playlist, .. } => Some(&playlist.tracks_uri),
+            PlaylistAction::Rename { tracks_uri, .. }
+            | PlaylistAction::SetSharing { tracks_uri, .. }
+            | PlaylistAction::AddTrack { tracks_uri, .. }
+            | PlaylistAction::RemoveTrack { tracks_uri, .. }
+            | PlaylistAction::MoveTrack { tracks_uri, .. } => Some(tracks_uri),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub enum PlaylistDone {
+    Created(Box<Playlist>),
+    /// The optimistic edit didn't stick, undo it and refetch the playlist's tracks.
+    Failed(Box<PlaylistAction>),
+}
+
+/// "Add to playlist…" popup, row 0 is "New playlist…" followed by owned playlists.
+pub struct PlaylistPicker {
+    pub track: Track,
+    pub selected: usize,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum PlaylistMenuItem {
+    Rename,
+    TogglePrivacy,
+    Delete,
+    New,
+}
+
+pub struct PlaylistMenu {
+    pub playlist: Playlist,
+    pub selected: usize,
+    pub confirm_delete: bool,
+}
+
+impl PlaylistMenu {
+    pub fn items(&self) -> Vec<PlaylistMenuItem> {
+        if self.playlist.is_owned {
+            vec![
+                PlaylistMenuItem::Rename,
+                PlaylistMenuItem::TogglePrivacy,
+                PlaylistMenuItem::Delete,
+                PlaylistMenuItem::New,
+            ]
+        } else {
+            vec![PlaylistMenuItem::New]
+        }
+    }
+}
+
+#[derive(Clone)]
+pub enum PromptKind {
+    NewPlaylist { track: Option<Track> },
+    RenamePlaylist { playlist: Playlist },
+    Comment { track_urn: String, timestamp_ms: u64 },
+    NewAccount,
+    Seek,
+}
+
+/// How far Alt + Left/Right and Alt + Shift + Left/Right jump.
+#[derive(Clone, Copy)]
+pub struct SeekSteps {
+    pub fine: Duration,
+    pub coarse: Duration,
+}
+
+impl Default for SeekSteps {
+    fn default() -> Self {
+        Self {
+            fine: Duration::from_secs(10),
+            coarse: Duration::from_secs(60),
+        }
+    }
+}
+
+impl SeekSteps {
+    pub fn from_config(config: &ConfigFile) -> Self {
+        let default = Self::default();
+        Self {
+            fine: config
+                .seek_step_secs
+                .map(Duration::from_secs)
+                .unwrap_or(default.fine),
+            coarse: config
+                .seek_coarse_step_secs
+                .map(Duration::from_secs)
+                .unwrap_or(default.coarse),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub enum RadioRequest {
+    /// The queue is about to run out, continue with tracks related to the seed.
+    Autoplay { seed_urn: String },
+    /// Play the track now, followed by its related tracks.
+    Track(Box<Track>),
+    /// Play the artist's top tracks followed by related tracks.
+    Artist { user_urn: String },
+}
+
+/// A comment waiting to be posted, anchored to where playback was when it was started.
+pub struct PendingComment {
+    pub track_urn: String,
+    pub body: String,
+    pub timestamp_ms: u64,
+}
+
+pub const PROFILE_TABS: [&str; 3] = ["Playlists", "Albums", "Reposts"];
+
+/// The open profile overlay, its content lives in `AppData::profile`.
+pub struct ProfileView {
+    pub user_urn: String,
+    /// Index into `PROFILE_TABS`.
+    pub selected_tab: usize,
+    pub selected_row: usize,
+    /// Why the profile couldn't be loaded, shown in place of it.
+    pub error: Option<String>,
+}
+
+pub struct UserProfile {
+    pub artist: Artist,
+    pub playlists: Vec<Playlist>,
+    pub albums: Vec<Album>,
+    pub reposts: Vec<Track>,
+}
+
+impl UserProfile {
+    pub fn rows_count(&self, tab: usize) -> usize {
+        match tab {
+            0 => self.playlists.len(),
+            1 => self.albums.len(),
+            2 => self.reposts.len(),
+            _ => 0,
+        }
+    }
+}
+
+/// Account switcher, one row per saved profile followed by "Add account…".
+pub struct AccountPicker {
+    pub profiles: Vec<String>,
+    pub selected: usize,
+    /// Profile being logged into, the switch happens once it has loaded.
+    pub switching: Option<String>,
+    pub error: Option<String>,
+}
+
+impl AccountPicker {
+    pub fn new(active: &str) -> Self {
+        let profiles = profile_names();
+        let selected = profiles.iter().position(|p| p == active).unwrap_or(0);
+        Self {
+            profiles,
+            selected,
+            switching: None,
+            error: None,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum SettingsItem {
+    Crossfade,
+    Normalize,
+    TargetLoudness,
+    Equalizer,
+    PreservePitch,
+    StreamQuality,
+}
+
+pub const SETTINGS_ITEMS: [SettingsItem; 6] = [
+    SettingsItem::Crossfade,
+    SettingsItem::Normalize,
+    SettingsItem::TargetLoudness,
+    SettingsItem::Equalizer,
+    SettingsItem::PreservePitch,
+    SettingsItem::StreamQuality,
+];
+
+/// The open settings overlay, the values live in `AppState::settings`.
+pub struct SettingsMenu {
+    /// Index into `SETTINGS_ITEMS`.
+    pub selected: usize,
+    /// Selected row while the equalizer bands are shown, the preset and then
+    /// one per band.
+    pub equalizer: Option<usize>,
+}
+
+/// Single line text input popup.
+pub struct Prompt {
+    pub title: String,
+    pub input: String,
+    pub kind: PromptKind,
 }
 
 pub struct AppState {
@@ -105,6 +344,7 @@ pub struct AppState {
     pub search_album_tracks_request_id: u64,
     pub search_people_tracks_request_id: u64,
     pub search_people_likes_request_id: u64,
+    pub feed_tracks_request_id: u64,
     pub playlist_tracks_task: Option<tokio::task::JoinHandle<()>>,
     pub album_tracks_task: Option<tokio::task::JoinHandle<()>>,
     pub following_tracks_task: Option<tokio::task::JoinHandle<()>>,
@@ -114,6 +354,7 @@ pub struct AppState {
     pub search_album_tracks_task: Option<tokio::task::JoinHandle<()>>,
     pub search_people_tracks_task: Option<tokio::task::JoinHandle<()>>,
     pub search_people_likes_task: Option<tokio::task::JoinHandle<()>>,
+    pub feed_tracks_task: Option<tokio::task::JoinHandle<()>>,
     pub progress: u64,
     pub current_playing_index: Option<usize>,
     pub playback_source: PlaybackSource,
@@ -123,7 +364,60 @@ pub struct AppState {
     pub manual_queue: VecDeque<QueuedTrack>,
     pub auto_queue: VecDeque<usize>,
     pub override_playing: Option<QueuedTrack>,
+    /// Set once the player asked for the next track: the one it was given to
+    /// preload, if any, kept in step with the queue until the track ends.
+    pub preloaded: Option<Option<String>>,
     pub engagement_queue: VecDeque<EngagementAction>,
+    /// Sent engagement actions still waiting for an answer, in the order they were
+    /// sent, reapplied over library data that was fetched before they went through.
+    pub engagement_in_flight: BTreeMap<u64, EngagementAction>,
+    pub engagement_request_id: u64,
+    pub playlist_queue: VecDeque<PlaylistAction>,
+    pub playlist_picker: Option<PlaylistPicker>,
+    pub playlist_menu: Option<PlaylistMenu>,
+    pub prompt: Option<Prompt>,
+    pub seek_steps: SeekSteps,
+    pub settings: Settings,
+    pub settings_menu: Option<SettingsMenu>,
+    pub autoplay_enabled: bool,
+    pub radio_request: Option<RadioRequest>,
+    pub radio_request_id: u64,
+    pub radio_task: Option<tokio::task::JoinHandle<()>>,
+    /// Track the last autoplay fetch was seeded from, so it only runs once per track.
+    pub radio_seed_urn: Option<String>,
+    /// Urns of the most recently played tracks, newest last.
+    pub recently_played: VecDeque<String>,
+    pub comment_queue: VecDeque<PendingComment>,
+    pub comments_visible: bool,
+    pub comments_selected: usize,
+    pub comments_request_id: u64,
+    pub comments_task: Option<tokio::task::JoinHandle<()>>,
+    pub profile: Option<ProfileView>,
+    pub profile_request_id: u64,
+    pub profile_task: Option<tokio::task::JoinHandle<()>>,
+    /// `tracks_uri` of a profile playlist or album to fetch and play.
+    pub profile_play: Option<String>,
+    pub account_picker: Option<AccountPicker>,
+    /// Profile to switch to, picked up by the main loop.
+    pub account_request: Option<String>,
+    /// Log in again after the session expired, picked up by the main loop.
+    pub login_requested: bool,
+    /// Next page loads of the paginated views.
+    pub likes_task: Option<tokio::task::JoinHandle<()>>,
+    pub playlists_task: Option<tokio::task::JoinHandle<()>>,
+    pub albums_task: Option<tokio::task::JoinHandle<()>>,
+    pub following_task: Option<tokio::task::JoinHandle<()>>,
+    pub feed_task: Option<tokio::task::JoinHandle<()>>,
+    /// Last failed background load and when it happened.
+    pub api_error: Option<(ApiError, Instant)>,
+    /// Load of the library's first pages, replacing the cached snapshot.
+    pub library_task: Option<tokio::task::JoinHandle<()>>,
+    /// Set once this session's library has loaded, paging waits for it.
+    pub library_fresh: bool,
+    /// When to try loading the library again after it failed.
+    pub library_retry_at: Option<Instant>,
+    /// The last library load couldn't reach SoundCloud.
+    pub offline: bool,
     pub following_tracks_focus: FollowingTracksFocus,
     pub queue_visible: bool,
     pub help_visible: bool,
@@ -134,8 +428,11 @@ pub struct AppState {
     pub search_matches: Vec<usize>,
     pub visualizer_mode: bool,
     pub visualizer_view: VisualizerMode,
-    pub end_handled_track_urn: Option<String>,
-    pub preload_triggered_for_track_urn: Option<String>,
+    /// The player ran out of audio and is waiting for more.
+    pub buffering: bool,
+    /// Why the last track or seek failed to play, and when.
+    pub player_error: Option<(String, Instant)>,
+    pub volume: f32,
 }
 
 impl AppState {
@@ -178,6 +475,8 @@ impl AppState {
             search_album_tracks_task: None,
             search_people_tracks_task: None,
             search_people_likes_task: None,
+            feed_tracks_request_id: 0,
+            feed_tracks_task: None,
             progress: 0,
             current_playing_index: None,
             playback_source: PlaybackSource::Likes,
@@ -187,7 +486,45 @@ impl AppState {
             manual_queue: VecDeque::new(),
             auto_queue: VecDeque::new(),
             override_playing: None,
+            preloaded: None,
             engagement_queue: VecDeque::new(),
+            engagement_in_flight: BTreeMap::new(),
+            engagement_request_id: 0,
+            playlist_queue: VecDeque::new(),
+            playlist_picker: None,
+            playlist_menu: None,
+            prompt: None,
+            seek_steps: SeekSteps::default(),
+            settings: Settings::default(),
+            settings_menu: None,
+            autoplay_enabled: false,
+            radio_request: None,
+            radio_request_id: 0,
+            radio_task: None,
+            radio_seed_urn: None,
+            recently_played: VecDeque::new(),
+            comment_queue: VecDeque::new(),
+            comments_visible: false,
+            comments_selected: 0,
+            comments_request_id: 0,
+            comments_task: None,
+            profile: None,
+            profile_request_id: 0,
+            profile_task: None,
+            profile_play: None,
+            account_picker: None,
+            account_request: None,
+            login_requested: false,
+            likes_task: None,
+            playlists_task: None,
+            albums_task: None,
+            following_task: None,
+            feed_task: None,
+            api_error: None,
+            library_task: None,
+            library_fresh: false,
+            library_retry_at: None,
+            offline: false,
             following_tracks_focus: FollowingTracksFocus::Published,
             queue_visible: false,
             help_visible: false,
@@ -198,10 +535,77 @@ impl AppState {
             search_matches: Vec::new(),
             visualizer_mode: false,
             visualizer_view: VisualizerMode::Oscilloscope,
-            end_handled_track_urn: None,
-            preload_triggered_for_track_urn: None,
+            buffering: false,
+            player_error: None,
+            volume: 1.0,
         }
     }
+
+    /// Crossfade for what is playing. Albums are left to run into their next
+    /// track, and a repeated track can't overlap itself. A track shorter than
+    /// twice the crossfade only fades over its second half.
+    pub fn crossfade(&self, duration_ms: u64) -> Duration {
+        if self.playback_source == PlaybackSource::Album || self.repeat_enabled {
+            Duration::ZERO
+        } else if duration_ms > 0 {
+            self.settings.crossfade().min(Duration::from_millis(duration_ms / 2))
+        } else {
+            self.settings.crossfade()
+        }
+    }
+
+    pub fn library_status(&self) -> LibraryStatus {
+        if self.library_fresh {
+            LibraryStatus::Fresh
+        } else if self.offline {
+            LibraryStatus::Offline
+        } else {
+            LibraryStatus::Refreshing
+        }
+    }
+
+    /// Stops every in-flight fetch, their results belong to the old account.
+    pub fn abort_tasks(&mut self) {
+        let tasks = [
+            &mut self.playlist_tracks_task,
+            &mut self.album_tracks_task,
+            &mut self.following_tracks_task,
+            &mut self.following_likes_task,
+            &mut self.search_results_task,
+            &mut self.search_playlist_tracks_task,
+            &mut self.search_album_tracks_task,
+            &mut self.search_people_tracks_task,
+            &mut self.search_people_likes_task,
+            &mut self.feed_tracks_task,
+            &mut self.radio_task,
+            &mut self.comments_task,
+            &mut self.profile_task,
+            &mut self.likes_task,
+            &mut self.playlists_task,
+            &mut self.albums_task,
+            &mut self.following_task,
+            &mut self.feed_task,
+            &mut self.library_task,
+        ];
+        for handle in tasks.into_iter().filter_map(Option::take) {
+            handle.abort();
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum LibraryStatus {
+    Fresh,
+    /// Showing the cached snapshot while the library loads.
+    Refreshing,
+    /// Showing the cached snapshot until SoundCloud can be reached again.
+    Offline,
+}
+
+fn clamp_row(state: &mut TableState, len: usize) {
+    if let Some(row) = state.selected() {
+        state.select(Some(row.min(len.saturating_sub(1))));
+    }
 }
 
 pub struct AppData {
@@ -233,6 +637,17 @@ pub struct AppData {
     pub following: Vec<Artist>,
     pub following_state: TableState,
     pub followed_user_urns: HashSet<String>,
+    pub reposted_track_urns: HashSet<String>,
+    /// `tracks_uri`s of reposted playlists and albums.
+    pub reposted_playlist_uris: HashSet<String>,
+    /// Related tracks fetched by autoplay, played once the queue runs out.
+    pub radio_tracks: Vec<Track>,
+    /// Comments of the playing track, sorted by timestamp.
+    pub comments: Vec<Comment>,
+    pub comments_track_urn: Option<String>,
+    /// Profile shown in the overlay, `None` while it loads.
+    pub profile: Option<UserProfile>,
+    pub profile_user_urn: Option<String>,
     pub search_tracks: Vec<Track>,
     pub search_tracks_state: TableState,
     pub search_playlists: Vec<Playlist>,
@@ -253,17 +668,29 @@ pub struct AppData {
     pub search_people_likes_tracks: Vec<Track>,
     pub search_people_likes_state: TableState,
     pub search_people_likes_user_urn: Option<String>,
+    pub feed: Vec<Activity>,
+    pub feed_state: TableState,
+    /// Tracks of the selected activity, a single track or the playlist's tracks.
+    pub feed_tracks: Vec<Track>,
+    pub feed_tracks_state: TableState,
+    /// Track urn or playlist `tracks_uri` that `feed_tracks` belong to.
+    pub feed_tracks_key: Option<String>,
 }
 
 impl AppData {
-    pub fn new(api: &mut API, selected_row: usize) -> anyhow::Result<Self> {
-        let likes: Vec<Track> = api.get_liked_tracks()?.into_iter().collect();
+    pub fn new(library: Library, selected_row: usize) -> Self {
+        let Library {
+            likes,
+            playlists,
+            albums,
+            following,
+        } = library;
+
         let mut likes_state = TableState::default();
         likes_state.select(Some(selected_row));
         let liked_track_urns: HashSet<String> =
             likes.iter().map(|t| t.track_urn.clone()).collect();
 
-        let playlists: Vec<Playlist> = api.get_playlists()?.into_iter().collect();
         let mut playlists_state = TableState::default();
         playlists_state.select(Some(selected_row));
         let liked_playlist_uris: HashSet<String> = playlists
@@ -280,7 +707,6 @@ impl AppData {
         let mut album_tracks_state = TableState::default();
         album_tracks_state.select(Some(0));
 
-        let albums: Vec<Album> = api.get_albums()?.into_iter().collect();
         let mut albums_state = TableState::default();
         albums_state.select(Some(selected_row));
         let liked_album_uris: HashSet<String> =
@@ -294,7 +720,6 @@ impl AppData {
         let mut following_likes_state = TableState::default();
         following_likes_state.select(Some(0));
 
-        let following: Vec<Artist> = api.get_following()?.into_iter().collect();
         let mut following_state = TableState::default();
         following_state.select(Some(selected_row));
         let followed_user_urns: HashSet<String> =
@@ -332,7 +757,13 @@ impl AppData {
         let mut search_people_likes_state = TableState::default();
         search_people_likes_state.select(Some(0));
 
-        Ok(Self {
+        let mut feed_state = TableState::default();
+        feed_state.select(Some(0));
+
+        let mut feed_tracks_state = TableState::default();
+        feed_tracks_state.select(Some(0));
+
+        Self {
             likes,
             likes_state,
             liked_track_urns,
@@ -361,6 +792,13 @@ impl AppData {
             following,
             following_state,
             followed_user_urns,
+            reposted_track_urns: HashSet::new(),
+            reposted_playlist_uris: HashSet::new(),
+            radio_tracks: Vec::new(),
+            comments: Vec::new(),
+            comments_track_urn: None,
+            profile: None,
+            profile_user_urn: None,
             search_tracks,
             search_tracks_state,
             search_playlists,
@@ -381,7 +819,36 @@ impl AppData {
             search_people_likes_tracks,
             search_people_likes_state,
             search_people_likes_user_urn: None,
-        })
+            feed: Vec::new(),
+            feed_state,
+            feed_tracks: Vec::new(),
+            feed_tracks_state,
+            feed_tracks_key: None,
+        }
+    }
+
+    /// Swaps in a freshly loaded library for the one shown so far, keeping
+    /// the selections in range.
+    pub fn replace_library(&mut self, library: Library) {
+        self.liked_track_urns = library.likes.iter().map(|t| t.track_urn.clone()).collect();
+        self.liked_playlist_uris = library
+            .playlists
+            .iter()
+            .filter(|p| !p.is_owned)
+            .map(|p| p.tracks_uri.clone())
+            .collect();
+        self.liked_album_uris = library.albums.iter().map(|a| a.tracks_uri.clone()).collect();
+        self.followed_user_urns = library.following.iter().map(|a| a.urn.clone()).collect();
+
+        self.likes = library.likes;
+        self.playlists = library.playlists;
+        self.albums = library.albums;
+        self.following = library.following;
+
+        clamp_row(&mut self.likes_state, self.likes.len());
+        clamp_row(&mut self.playlists_state, self.playlists.len());
+        clamp_row(&mut self.albums_state, self.albums.len());
+        clamp_row(&mut self.following_state, self.following.len());
     }
 
     pub fn apply_updates(
@@ -450,6 +917,103 @@ impl AppData {
             self.following.extend(new_following);
         }
     }
+
+    /// Sets the liked, followed or reposted flag the way `action` left it in the UI.
+    pub fn apply_engagement(&mut self, action: &EngagementAction) {
+        match action {
+            EngagementAction::LikeTrack { track, .. } => {
+                self.liked_track_urns.insert(track.track_urn.clone());
+            }
+            EngagementAction::UnlikeTrack { track_urn, .. } => {
+                self.liked_track_urns.remove(track_urn);
+            }
+            EngagementAction::LikePlaylist { playlist, .. } => {
+                self.liked_playlist_uris.insert(playlist.tracks_uri.clone());
+            }
+            EngagementAction::UnlikePlaylist { tracks_uri, .. } => {
+                self.liked_playlist_uris.remove(tracks_uri);
+            }
+            EngagementAction::LikeAlbum { album, .. } => {
+                self.liked_album_uris.insert(album.tracks_uri.clone());
+            }
+            EngagementAction::UnlikeAlbum { tracks_uri, .. } => {
+                self.liked_album_uris.remove(tracks_uri);
+            }
+            EngagementAction::FollowUser { artist, .. } => {
+                self.followed_user_urns.insert(artist.urn.clone());
+            }
+            EngagementAction::UnfollowUser { urn, .. } => {
+                self.followed_user_urns.remove(urn);
+            }
+            EngagementAction::RepostTrack { track_urn, .. } => {
+                self.reposted_track_urns.insert(track_urn.clone());
+            }
+            EngagementAction::UnrepostTrack { track_urn, .. } => {
+                self.reposted_track_urns.remove(track_urn);
+            }
+            EngagementAction::RepostPlaylist { tracks_uri, .. } => {
+                self.reposted_playlist_uris.insert(tracks_uri.clone());
+            }
+            EngagementAction::UnrepostPlaylist { tracks_uri, .. } => {
+                self.reposted_playlist_uris.remove(tracks_uri);
+            }
+        }
+    }
+
+    /// Keeps the shown repost counts in line once a repost went through.
+    pub fn adjust_track_reposts(&mut self, track_urn: &str, reposted: bool) {
+        let lists = [
+            &mut self.likes,
+            &mut self.playlist_tracks,
+            &mut self.album_tracks,
+            &mut self.following_tracks,
+            &mut self.following_likes_tracks,
+            &mut self.playback_tracks,
+            &mut self.search_tracks,
+            &mut self.search_playlist_tracks,
+            &mut self.search_album_tracks,
+            &mut self.search_people_tracks,
+            &mut self.search_people_likes_tracks,
+        ];
+        for track in lists
+            .into_iter()
+            .flat_map(|list| list.iter_mut())
+            .filter(|t| t.track_urn == track_urn)
+        {
+            track.reposts_count = adjust_count(track.reposts_count, reposted);
+        }
+    }
+
+    pub fn adjust_playlist_reposts(&mut self, tracks_uri: &str, reposted: bool) {
+        for playlist in self
+            .playlists
+            .iter_mut()
+            .chain(self.search_playlists.iter_mut())
+            .filter(|p| p.tracks_uri == tracks_uri)
+        {
+            playlist.reposts_count = adjust_count(playlist.reposts_count, reposted);
+        }
+        for album in self
+            .albums
+            .iter_mut()
+            .chain(self.search_albums.iter_mut())
+            .filter(|a| a.tracks_uri == tracks_uri)
+        {
+            album.reposts_count = adjust_count(album.reposts_count, reposted);
+        }
+    }
+
+    pub fn owned_playlists(&self) -> Vec<&Playlist> {
+        self.playlists.iter().filter(|p| p.is_owned).collect()
+    }
+}
+
+fn adjust_count(count: u64, increment: bool) -> u64 {
+    if increment {
+        count + 1
+    } else {
+        count.saturating_sub(1)
+    }
 }
 
 pub fn table_rows_count(selected_subtab: usize, data: &AppData) -> usize {
@@ -461,7 +1025,3 @@ pub fn table_rows_count(selected_subtab: usize, data: &AppData) -> usize {
         _ => 0,
     }
 }
-
-pub fn info_table_rows_count() -> usize {
-    2
-}
@@ -1,12 +1,16 @@
-use std::collections::VecDeque;
+use std::collections::{HashSet, VecDeque};
 
 use rand::seq::SliceRandom;
 
-use crate::api::{Album, Artist, Playlist, Track};
+use crate::api::{Album, Artist, Comment, Playlist, Track};
+use crate::config::strip_api_base;
 use crate::player::Player;
 
 use super::state::{AppData, AppState, FollowingTracksFocus, PlaybackSource, QueuedTrack};
 
+/// How long a timed comment stays on screen after its timestamp.
+const COMMENT_DISPLAY_MS: u64 = 6000;
+
 fn fuzzy_score_subsequence(query: &str, candidate: &str) -> Option<i64> {
     if query.is_empty() || candidate.is_empty() {
         return None;
@@ -124,7 +128,7 @@ pub fn build_search_matches(
                 .iter()
                 .enumerate()
                 .filter_map(|(i, track)| {
-                    fuzzy_score_track_tokens(&tokens, &track.title, &track.artists)
+                    fuzzy_score_track_tokens(&tokens, &track.title, track.artists())
                         .map(|s| (s, i))
                 })
                 .collect();
@@ -136,7 +140,7 @@ pub fn build_search_matches(
                 .iter()
                 .enumerate()
                 .filter_map(|(i, track)| {
-                    fuzzy_score_track_tokens(&tokens, &track.title, &track.artists)
+                    fuzzy_score_track_tokens(&tokens, &track.title, track.artists())
                         .map(|s| (s, i))
                 })
                 .collect();
@@ -148,7 +152,7 @@ pub fn build_search_matches(
                 .iter()
                 .enumerate()
                 .filter_map(|(i, album)| {
-                    fuzzy_score_track_tokens(&tokens, &album.title, &album.artists)
+                    fuzzy_score_track_tokens(&tokens, &album.title, album.artists())
                         .map(|s| (s, i))
                 })
                 .collect();
@@ -182,10 +186,7 @@ pub fn soundcloud_playlist_id_from_tracks_uri(tracks_uri: &str) -> Option<u64> {
         return None;
     }
 
-    let s = s
-        .strip_prefix("https://api.soundcloud.com")
-        .or_else(|| s.strip_prefix("http://api.soundcloud.com"))
-        .unwrap_or(s);
+    let s = strip_api_base(s);
 
     let s = s.split('?').next().unwrap_or(s);
 
@@ -231,6 +232,27 @@ pub fn soundcloud_playlist_id_from_tracks_uri(tracks_uri: &str) -> Option<u64> {
     best.parse::<u64>().ok()
 }
 
+/// The most recent comment whose timestamp has passed, while it is still fresh.
+/// `comments` must be sorted by timestamp.
+pub fn due_comment(comments: &[Comment], progress_ms: u64) -> Option<&Comment> {
+    let end = comments.partition_point(|c| c.timestamp_ms <= progress_ms);
+    comments[..end]
+        .last()
+        .filter(|c| progress_ms - c.timestamp_ms < COMMENT_DISPLAY_MS)
+}
+
+/// The tracks `current_playing_index` and the auto queue point into.
+pub fn active_tracks<'a>(state: &AppState, data: &'a AppData) -> &'a Vec<Track> {
+    match state.playback_source {
+        PlaybackSource::Likes => &data.likes,
+        PlaybackSource::Playlist
+        | PlaybackSource::Album
+        | PlaybackSource::FollowingPublished
+        | PlaybackSource::FollowingLikes
+        | PlaybackSource::Radio => &data.playback_tracks,
+    }
+}
+
 pub fn build_queue(
     current_idx: usize,
     tracks: &[Track],
@@ -257,6 +279,88 @@ pub fn build_queue(
     }
 }
 
+/// Moves on from the track that just ended: repeats it, or plays the next
+/// queued track, falling back to radio.
+pub fn advance_after_track_end(state: &mut AppState, data: &mut AppData, player: &Player) {
+    state.preloaded = None;
+    let Some(current_idx) = state.current_playing_index else {
+        return;
+    };
+    let active_tracks = active_tracks(state, data);
+
+    if state.repeat_enabled {
+        if let Some(track) = active_tracks.get(current_idx) {
+            player.play(track.clone());
+            state.override_playing = None;
+        }
+        return;
+    }
+
+    if state.manual_queue.is_empty() && state.auto_queue.is_empty() {
+        state.auto_queue = build_queue(current_idx, active_tracks, state.shuffle_enabled);
+    }
+    if let Some(queued) = state.manual_queue.pop_front() {
+        if let Some(current) = queued_from_current(state, data) {
+            state.playback_history.push(current);
+        }
+        play_queued_track(queued, state, data, player, true);
+    } else if let Some(next_idx) = state.auto_queue.pop_front() {
+        if let Some(track) = active_tracks.get(next_idx).cloned() {
+            if let Some(current) = queued_from_current(state, data) {
+                state.playback_history.push(current);
+            }
+            player.play(track);
+            state.override_playing = None;
+            state.current_playing_index = Some(next_idx);
+        }
+    } else if !continue_with_radio(state, data, player) {
+        player.pause();
+        state.current_playing_index = None;
+    }
+}
+
+/// Asks the player to load whatever `advance_after_track_end` would play next.
+pub fn preload_next_track(state: &mut AppState, data: &AppData, player: &Player) {
+    state.preloaded = Some(None);
+    sync_preload(state, data, player);
+}
+
+/// Hands the player the new next track if the queue changed since it was
+/// asked to preload, so it doesn't run into the old one.
+pub fn sync_preload(state: &mut AppState, data: &AppData, player: &Player) {
+    let Some(preloaded) = state.preloaded.clone() else {
+        return;
+    };
+    let next_track = next_track(state, data).filter(|track| {
+        track.track_urn != player.current_track().track_urn && track.is_playable()
+    });
+    if next_track.as_ref().map(|t| &t.track_urn) == preloaded.as_ref() {
+        return;
+    }
+
+    state.preloaded = Some(next_track.as_ref().map(|t| t.track_urn.clone()));
+    match next_track {
+        Some(track) => player.preload_next(track),
+        None => player.clear_preload(),
+    }
+}
+
+fn next_track(state: &mut AppState, data: &AppData) -> Option<Track> {
+    let current_idx = state.current_playing_index?;
+    let active_tracks = active_tracks(state, data);
+
+    if state.repeat_enabled {
+        active_tracks.get(current_idx).cloned()
+    } else if let Some(queued) = state.manual_queue.front() {
+        Some(queued.track.clone())
+    } else {
+        if state.auto_queue.is_empty() {
+            state.auto_queue = build_queue(current_idx, active_tracks, state.shuffle_enabled);
+        }
+        state.auto_queue.front().and_then(|&idx| active_tracks.get(idx).cloned())
+    }
+}
+
 pub fn play_queued_track(
     queued: QueuedTrack,
     state: &mut AppState,
@@ -290,12 +394,14 @@ pub fn play_queued_track(
         PlaybackSource::Playlist
         | PlaybackSource::Album
         | PlaybackSource::FollowingPublished
-        | PlaybackSource::FollowingLikes => {
+        | PlaybackSource::FollowingLikes
+        | PlaybackSource::Radio => {
             let tracks = queued.tracks_snapshot.unwrap_or_else(|| match queued.source {
                 PlaybackSource::Playlist => data.playlist_tracks.clone(),
                 PlaybackSource::Album => data.album_tracks.clone(),
                 PlaybackSource::FollowingPublished => data.following_tracks.clone(),
                 PlaybackSource::FollowingLikes => data.following_likes_tracks.clone(),
+                PlaybackSource::Radio => data.playback_tracks.clone(),
                 PlaybackSource::Likes => Vec::new(),
             });
             if tracks.is_empty() || queued.index >= tracks.len() {
@@ -418,5 +524,125 @@ pub fn queued_from_current(state: &AppState, data: &AppData) -> Option<QueuedTra
                 user_added: false,
             })
         }
+        PlaybackSource::Radio => {
+            let track = data.playback_tracks.get(idx)?.clone();
+            Some(QueuedTrack {
+                source: PlaybackSource::Radio,
+                index: idx,
+                track,
+                tracks_snapshot: Some(data.playback_tracks.clone()),
+                playlist_uri: None,
+                album_uri: None,
+                following_user_urn: None,
+                user_added: false,
+            })
+        }
+    }
+}
+
+/// Drops unplayable tracks, repeats within `tracks` and anything played recently
+/// or already lined up on the radio.
+pub fn dedupe_radio_tracks(tracks: Vec<Track>, state: &AppState, data: &AppData) -> Vec<Track> {
+    let mut seen: HashSet<String> = state.recently_played.iter().cloned().collect();
+    if state.playback_source == PlaybackSource::Radio {
+        seen.extend(data.playback_tracks.iter().map(|t| t.track_urn.clone()));
+    }
+    seen.extend(data.radio_tracks.iter().map(|t| t.track_urn.clone()));
+    tracks
+        .into_iter()
+        .filter(|t| t.is_playable() && seen.insert(t.track_urn.clone()))
+        .collect()
+}
+
+/// Plays `tracks[index]` with `tracks` as the new playback context. `context_uri` is
+/// the playlist or album `tracks_uri`, depending on `source`.
+pub fn play_track_list(
+    tracks: Vec<Track>,
+    index: usize,
+    source: PlaybackSource,
+    context_uri: Option<String>,
+    state: &mut AppState,
+    data: &mut AppData,
+    player: &Player,
+) {
+    let Some(track) = tracks.get(index).cloned() else {
+        return;
+    };
+    if !track.is_playable() {
+        return;
+    }
+
+    if state.playback_source != source {
+        state.playback_history.clear();
+        state.manual_queue.clear();
+    } else if let Some(current) = queued_from_current(state, data) {
+        state.playback_history.push(current);
     }
+
+    player.play(track);
+    state.playback_source = source;
+    state.override_playing = None;
+    state.current_playing_index = Some(index);
+    data.playback_tracks = tracks;
+    data.playback_playlist_uri = context_uri.clone().filter(|_| source == PlaybackSource::Playlist);
+    data.playback_album_uri = context_uri.filter(|_| source == PlaybackSource::Album);
+    data.playback_following_user_urn = None;
+    state.auto_queue = build_queue(index, &data.playback_tracks, state.shuffle_enabled);
+}
+
+/// Replaces the playback context with `tracks` and plays the first one. Tracks the
+/// user queued by hand stay queued and play before the rest of the radio.
+pub fn start_radio(tracks: Vec<Track>, state: &mut AppState, data: &mut AppData, player: &Player) {
+    let Some(first) = tracks.first().cloned() else {
+        return;
+    };
+
+    if state.playback_source != PlaybackSource::Radio {
+        state.playback_history.clear();
+    } else if let Some(current) = queued_from_current(state, data) {
+        state.playback_history.push(current);
+    }
+
+    player.play(first);
+    state.playback_source = PlaybackSource::Radio;
+    state.override_playing = None;
+    state.current_playing_index = Some(0);
+    data.playback_tracks = tracks;
+    data.playback_playlist_uri = None;
+    data.playback_album_uri = None;
+    data.playback_following_user_urn = None;
+    data.radio_tracks.clear();
+    state.auto_queue = build_queue(0, &data.playback_tracks, false);
+}
+
+/// Called when both queues are empty: moves on to the autoplay tracks, if any
+/// have been fetched. Returns whether something started playing.
+pub fn continue_with_radio(state: &mut AppState, data: &mut AppData, player: &Player) -> bool {
+    if data.radio_tracks.is_empty() {
+        return false;
+    }
+    let tracks = std::mem::take(&mut data.radio_tracks);
+
+    if let Some(current) = queued_from_current(state, data) {
+        state.playback_history.push(current);
+    }
+
+    let start = if state.playback_source == PlaybackSource::Radio {
+        let start = data.playback_tracks.len();
+        data.playback_tracks.extend(tracks);
+        start
+    } else {
+        data.playback_tracks = tracks;
+        data.playback_playlist_uri = None;
+        data.playback_album_uri = None;
+        data.playback_following_user_urn = None;
+        state.playback_source = PlaybackSource::Radio;
+        0
+    };
+
+    player.play(data.playback_tracks[start].clone());
+    state.override_playing = None;
+    state.current_playing_index = Some(start);
+    state.auto_queue = build_queue(start, &data.playback_tracks, false);
+    true
 }
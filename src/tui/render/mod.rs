@@ -12,15 +12,22 @@ use ratatui::{
     Frame,
     layout::{Alignment, Constraint, Layout},
     style::{Color, Modifier, Style},
-    text::Span,
+    text::{Line, Span},
     widgets::{Block, BorderType, Borders, TableState, Tabs},
 };
 use ratatui_image::thread::ThreadProtocol;
 
-use crate::api::{Album, Artist, Playlist, Track};
-use crate::tui::logic::state::QueuedTrack;
+use crate::api::{Activity, ApiError, Album, Artist, Comment, Playlist, Track};
+use crate::auth::SessionStatus;
+use crate::config::{DEFAULT_PROFILE, Settings};
+use crate::tui::logic::state::{
+    AccountPicker, LibraryStatus, PlaylistMenu, PlaylistPicker, ProfileView, Prompt, QueuedTrack,
+    SettingsMenu, UserProfile,
+};
 use crate::tui::render::visualizer::render_visualizer;
 
+pub(crate) use utils::format_duration;
+
 pub fn render(
     frame: &mut Frame,
     likes_view: &Vec<Track>,
@@ -30,6 +37,8 @@ pub fn render(
     liked_album_uris: &HashSet<String>,
     liked_playlist_uris: &HashSet<String>,
     followed_user_urns: &HashSet<String>,
+    reposted_track_urns: &HashSet<String>,
+    reposted_playlist_uris: &HashSet<String>,
     playlists: &Vec<Playlist>,
     playlists_state: &mut TableState,
     playlist_tracks: &Vec<Track>,
@@ -78,24 +87,53 @@ pub fn render(
     search_selected_person_track_row: usize,
     search_selected_person_like_row: usize,
     search_people_focus_is_likes: bool,
+    feed: &[Activity],
+    feed_state: &mut TableState,
+    feed_tracks: &[Track],
+    feed_tracks_state: &mut TableState,
     info_pane_selected: bool,
     selected_info_row: usize,
     data: &mut Vec<(f64, f64)>,
     window: &mut [f64; 2],
     progress: &mut u64,
+    buffering: bool,
+    reconnecting: bool,
+    player_error: Option<&str>,
+    speed: f32,
+    stream: Option<&str>,
     selected_track: Track,
     cover_art_async: &mut ThreadProtocol,
     current_volume: f32,
     shuffle_enabled: bool,
     repeat_enabled: bool,
+    autoplay_enabled: bool,
+    comments: &[Comment],
+    due_comment: Option<&Comment>,
+    comments_visible: bool,
+    comments_selected: usize,
     queue_visible: bool,
     manual_queue: &VecDeque<QueuedTrack>,
     auto_queue: &VecDeque<usize>,
     current_playing_track: Option<Track>,
     previous_playing_track: Option<Track>,
+    radio_active: bool,
+    radio_tracks: &[Track],
     help_visible: bool,
     quit_confirm_visible: bool,
     quit_confirm_selected: usize,
+    playlist_picker: Option<&PlaylistPicker>,
+    playlist_menu: Option<&PlaylistMenu>,
+    prompt: Option<&Prompt>,
+    profile_view: Option<&ProfileView>,
+    profile: Option<&UserProfile>,
+    profile_avatar: &mut ThreadProtocol,
+    account_picker: Option<&AccountPicker>,
+    settings_menu: Option<&SettingsMenu>,
+    settings: &Settings,
+    account: &str,
+    session: &SessionStatus,
+    api_error: Option<&ApiError>,
+    library: LibraryStatus,
     search_popup_visible: bool,
     search_query: &str,
     search_match_count: usize,
@@ -127,15 +165,34 @@ pub fn render(
             auto_queue,
             current_playing_track,
             previous_playing_track,
+            radio_active,
+            radio_tracks,
             queue_visible,
             help_visible,
             quit_confirm_visible,
             quit_confirm_selected,
+            comments,
+            comments_visible,
+            comments_selected,
+            playlists,
+            playlist_picker,
+            playlist_menu,
+            prompt,
+            profile_view,
+            profile,
+            profile_avatar,
+            followed_user_urns,
+            account_picker,
+            settings_menu,
+            settings,
+            account,
+            session,
         );
         return;
     }
 
-    render_tabs(frame, chunks[0], tab_titles, selected_tab);
+    let indicator = status_indicator(session, api_error, library);
+    render_tabs(frame, chunks[0], tab_titles, selected_tab, account, indicator, library);
 
     if selected_tab == 0 {
         tabs::render_library(
@@ -158,6 +215,8 @@ pub fn render(
             following_tracks_state,
             following_likes_tracks,
             following_likes_state,
+            reposted_track_urns,
+            reposted_playlist_uris,
             selected_subtab,
             subtab_titles,
             selected_row,
@@ -182,6 +241,8 @@ pub fn render(
             liked_album_uris,
             liked_playlist_uris,
             followed_user_urns,
+            reposted_track_urns,
+            reposted_playlist_uris,
             search_tracks,
             search_tracks_state,
             search_playlists,
@@ -209,6 +270,10 @@ pub fn render(
             frame,
             chunks[1],
             width,
+            feed,
+            feed_state,
+            feed_tracks,
+            feed_tracks_state,
             selected_row,
             selected_info_row,
             info_pane_selected,
@@ -221,11 +286,18 @@ pub fn render(
         data,
         window,
         progress,
+        buffering,
+        reconnecting,
+        player_error,
+        speed,
+        stream,
         selected_track,
         cover_art_async,
         current_volume,
         shuffle_enabled,
         repeat_enabled,
+        autoplay_enabled,
+        due_comment,
     );
 
     overlays::render_overlays(
@@ -235,23 +307,64 @@ pub fn render(
         auto_queue,
         current_playing_track,
         previous_playing_track,
+        radio_active,
+        radio_tracks,
         queue_visible,
         help_visible,
         quit_confirm_visible,
         quit_confirm_selected,
+        comments,
+        comments_visible,
+        comments_selected,
+        playlists,
+        playlist_picker,
+        playlist_menu,
+        prompt,
+        profile_view,
+        profile,
+        profile_avatar,
+        followed_user_urns,
+        account_picker,
+        settings_menu,
+        settings,
+        account,
+        session,
     );
 }
 
-fn render_tabs(frame: &mut Frame, area: ratatui::layout::Rect, tab_titles: &[&str], selected: usize) {
+fn render_tabs(
+    frame: &mut Frame,
+    area: ratatui::layout::Rect,
+    tab_titles: &[&str],
+    selected: usize,
+    account: &str,
+    indicator: Line<'static>,
+    library: LibraryStatus,
+) {
     let tabs: Vec<_> = tab_titles.iter().map(|t| Span::raw(*t)).collect();
+    let title = if account == DEFAULT_PROFILE {
+        "sctui".to_string()
+    } else {
+        format!("sctui · {}", account)
+    };
+    let offline_banner = if library == LibraryStatus::Offline {
+        Line::styled(
+            " offline · showing your saved library ",
+            Style::default().fg(Color::Black).bg(Color::Red),
+        )
+    } else {
+        Line::default()
+    };
     let tabs_widget = Tabs::new(tabs)
         .block(
             Block::default()
                 .title(Span::styled(
-                    "sctui",
+                    title,
                     Style::default().add_modifier(Modifier::BOLD),
                 ))
                 .title_alignment(Alignment::Center)
+                .title(indicator.right_aligned())
+                .title_bottom(offline_banner.centered())
                 .borders(Borders::ALL)
                 .border_type(BorderType::Rounded),
         )
@@ -264,3 +377,31 @@ fn render_tabs(frame: &mut Frame, area: ratatui::layout::Rect, tab_titles: &[&st
         );
     frame.render_widget(tabs_widget, area);
 }
+
+/// Right end of the tab bar: session state, or why the library isn't current.
+fn status_indicator(
+    session: &SessionStatus,
+    api_error: Option<&ApiError>,
+    library: LibraryStatus,
+) -> Line<'static> {
+    match session {
+        SessionStatus::Active => match (library, api_error) {
+            (LibraryStatus::Refreshing, _) => {
+                Line::styled(" refreshing… ● ", Style::default().fg(Color::Yellow))
+            }
+            (LibraryStatus::Offline, _) | (_, Some(ApiError::Offline)) => {
+                Line::styled(" offline ● ", Style::default().fg(Color::Red))
+            }
+            (_, Some(error)) => {
+                Line::styled(format!(" {} ● ", error), Style::default().fg(Color::Yellow))
+            }
+            (_, None) => Line::styled("● ", Style::default().fg(Color::Green)),
+        },
+        SessionStatus::Expired | SessionStatus::LoginFailed(_) => {
+            Line::styled(" session expired ● ", Style::default().fg(Color::Red))
+        }
+        SessionStatus::LoggingIn => {
+            Line::styled(" logging in ● ", Style::default().fg(Color::Yellow))
+        }
+    }
+}
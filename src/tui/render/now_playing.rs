@@ -3,25 +3,13 @@ use ratatui::{
     layout::{Constraint, Layout, Rect},
     style::{Color, Modifier, Style},
     symbols::{self},
-    text::{Span, Text},
+    text::{Line, Span, Text},
     widgets::{Axis, Block, Chart, Dataset, Gauge, Paragraph},
 };
 use ratatui_image::{Resize, StatefulImage, thread::ThreadProtocol};
 
-use crate::api::Track;
-
-fn format_duration(duration_ms: u64) -> String {
-    let duration_sec = duration_ms / 1000;
-    let hours = duration_sec / 3600;
-    let minutes = (duration_sec % 3600) / 60;
-    let seconds = duration_sec % 60;
-
-    if hours > 0 {
-        format!("{:02}:{:02}:{:02}", hours, minutes, seconds)
-    } else {
-        format!("{:02}:{:02}", minutes, seconds)
-    }
-}
+use crate::api::{Comment, Track};
+use crate::tui::render::utils::{format_duration, truncate_with_ellipsis};
 
 pub fn render_now_playing(
     frame: &mut Frame,
@@ -29,11 +17,18 @@ pub fn render_now_playing(
     data: &mut Vec<(f64, f64)>,
     window: &mut [f64; 2],
     progress: &mut u64,
+    buffering: bool,
+    reconnecting: bool,
+    player_error: Option<&str>,
+    speed: f32,
+    stream: Option<&str>,
     selected_track: Track,
     cover_art_async: &mut ThreadProtocol,
     current_volume: f32,
     shuffle_enabled: bool,
     repeat_enabled: bool,
+    autoplay_enabled: bool,
+    due_comment: Option<&Comment>,
 ) {
     let subchunks = Layout::default()
         .direction(ratatui::layout::Direction::Horizontal)
@@ -47,10 +42,42 @@ pub fn render_now_playing(
         )
         .split(area);
 
-    let now_playing = Block::default()
+    let mut now_playing = Block::default()
         .borders(ratatui::widgets::Borders::ALL)
         .border_type(ratatui::widgets::BorderType::Rounded);
 
+    if let Some(stream) = stream {
+        now_playing = now_playing.title(
+            Line::from(Span::styled(
+                format!(" {} ", stream),
+                Style::default().fg(Color::DarkGray),
+            ))
+            .right_aligned(),
+        );
+    }
+
+    if let Some(reason) = player_error {
+        let max_width = (subchunks[1].width as usize).saturating_sub(4);
+        let text = format!(" can't play: {} ", reason);
+        now_playing = now_playing.title_bottom(
+            Line::from(Span::styled(
+                truncate_with_ellipsis(&text, max_width),
+                Style::default().fg(Color::Red),
+            ))
+            .centered(),
+        );
+    } else if let Some(comment) = due_comment {
+        let max_width = (subchunks[1].width as usize).saturating_sub(4);
+        let text = format!(" {}: {} ", comment.user.name, comment.body.replace('\n', " "));
+        now_playing = now_playing.title_bottom(
+            Line::from(Span::styled(
+                truncate_with_ellipsis(&text, max_width),
+                Style::default().fg(Color::Cyan),
+            ))
+            .centered(),
+        );
+    }
+
     frame.render_widget(now_playing.clone(), subchunks[1]);
     let inner_area = now_playing.inner(subchunks[1]);
 
@@ -115,7 +142,7 @@ pub fn render_now_playing(
 
     frame.render_widget(song_name, subsubchunks[1]);
 
-    let artist = Paragraph::new(selected_track.artists.clone())
+    let artist = Paragraph::new(selected_track.artists().to_string())
         .alignment(ratatui::layout::Alignment::Center);
 
     frame.render_widget(artist, subsubchunks[3]);
@@ -126,9 +153,17 @@ pub fn render_now_playing(
 
     let label = Span::styled(
         format!(
-            "{} / {}",
+            "{}{} / {}{}",
+            if reconnecting {
+                "reconnecting… "
+            } else if buffering {
+                "buffering… "
+            } else {
+                ""
+            },
             format_duration(*progress),
-            selected_track.duration.clone()
+            format_duration(selected_track.duration_ms),
+            if speed != 1.0 { format!(" · {:.1}×", speed) } else { String::new() }
         ),
         Style::default().fg(Color::White),
     );
@@ -145,6 +180,7 @@ pub fn render_now_playing(
 
     let shuffle_indicator = if shuffle_enabled { "✔︎" } else { "×" };
     let repeat_indicator = if repeat_enabled { "✔︎" } else { "×" };
+    let autoplay_indicator = if autoplay_enabled { "✔︎" } else { "×" };
 
     let lines = vec![
         "".to_string(),
@@ -152,6 +188,7 @@ pub fn render_now_playing(
         format!("shf:   {}", shuffle_indicator),
         format!("vol: {:.1}", current_volume),
         format!("rep:   {}", repeat_indicator),
+        format!("rad:   {}", autoplay_indicator),
     ];
 
     let text = Text::from(lines.join("\n"));
@@ -0,0 +1,78 @@
+use ratatui::{
+    Frame,
+    layout::{Alignment, Constraint},
+    style::{Color, Modifier, Style},
+    text::Line,
+    widgets::{Block, Borders, Clear, Row, Table},
+};
+
+use crate::tui::logic::state::AccountPicker;
+use crate::tui::render::utils::truncate_with_ellipsis;
+
+use super::utils::centered_rect;
+
+pub fn render_account_picker(frame: &mut Frame, picker: &AccountPicker, active: &str) {
+    let popup_area = centered_rect(40, 40, frame.area());
+    frame.render_widget(Clear, popup_area);
+
+    let name_width = popup_area.width.saturating_sub(6) as usize;
+    let mut rows: Vec<Row> = picker
+        .profiles
+        .iter()
+        .map(|profile| {
+            let marker = if profile == active { "●" } else { " " };
+            Row::new(vec![
+                marker.to_string(),
+                truncate_with_ellipsis(profile, name_width),
+            ])
+        })
+        .collect();
+    rows.push(Row::new(vec![String::new(), "+ Add account…".to_string()]));
+
+    // keep the selected row on screen
+    let max_rows = popup_area.height.saturating_sub(2) as usize;
+    let offset = (picker.selected + 1).saturating_sub(max_rows);
+    let rows: Vec<Row> = rows
+        .into_iter()
+        .enumerate()
+        .skip(offset)
+        .map(|(i, row)| {
+            if i == picker.selected {
+                row.style(
+                    Style::default()
+                        .fg(Color::Black)
+                        .bg(Color::LightBlue)
+                        .add_modifier(Modifier::BOLD),
+                )
+            } else {
+                row
+            }
+        })
+        .collect();
+
+    let status = if let Some(profile) = &picker.switching {
+        Line::styled(
+            format!(" Logging in to {}… ", profile),
+            Style::default().fg(Color::Cyan),
+        )
+    } else if let Some(error) = &picker.error {
+        Line::styled(
+            format!(" {} ", truncate_with_ellipsis(error, name_width)),
+            Style::default().fg(Color::Red),
+        )
+    } else {
+        Line::default()
+    };
+
+    let table = Table::new(rows, vec![Constraint::Length(1), Constraint::Min(0)])
+        .block(
+            Block::default()
+                .title("Accounts")
+                .title_alignment(Alignment::Center)
+                .title_bottom(status.centered())
+                .borders(Borders::ALL)
+                .border_type(ratatui::widgets::BorderType::Rounded),
+        )
+        .column_spacing(1);
+    frame.render_widget(table, popup_area);
+}
@@ -0,0 +1,59 @@
+use ratatui::{
+    Frame,
+    layout::{Alignment, Constraint},
+    style::{Color, Style},
+    widgets::{Block, Borders, Clear, Row, Table},
+};
+
+use crate::api::Comment;
+use crate::tui::render::utils::{format_duration, styled_header, truncate_with_ellipsis};
+
+use super::utils::centered_rect;
+
+pub fn render_comments(frame: &mut Frame, comments: &[Comment], selected: usize) {
+    let popup_area = centered_rect(70, 60, frame.area());
+    frame.render_widget(Clear, popup_area);
+
+    let total_width = popup_area.width as usize;
+    let user_width = (total_width * 20) / 100;
+    let body_width = (total_width * 68) / 100;
+
+    let mut rows: Vec<Row> = Vec::new();
+    if comments.is_empty() {
+        rows.push(Row::new(vec!["", "No comments yet", ""]));
+    }
+
+    // keep the selected row on screen
+    let max_rows = popup_area.height.saturating_sub(3) as usize;
+    let offset = (selected + 1).saturating_sub(max_rows);
+    for (i, comment) in comments.iter().enumerate().skip(offset).take(max_rows) {
+        let mut row = Row::new(vec![
+            format_duration(comment.timestamp_ms),
+            truncate_with_ellipsis(&comment.user.name, user_width),
+            truncate_with_ellipsis(&comment.body.replace('\n', " "), body_width),
+        ]);
+        if i == selected {
+            row = row.style(Style::default().bg(Color::LightBlue).fg(Color::White));
+        }
+        rows.push(row);
+    }
+
+    let table = Table::new(
+        rows,
+        vec![
+            Constraint::Percentage(10),
+            Constraint::Percentage(20),
+            Constraint::Percentage(70),
+        ],
+    )
+    .header(styled_header(&["At", "User", "Comment"]))
+    .block(
+        Block::default()
+            .title(format!("Comments ({}) · c: comment here", comments.len()))
+            .title_alignment(Alignment::Center)
+            .borders(Borders::ALL)
+            .border_type(ratatui::widgets::BorderType::Rounded),
+    )
+    .column_spacing(1);
+    frame.render_widget(table, popup_area);
+}
@@ -0,0 +1,85 @@
+use ratatui::{
+    Frame,
+    layout::{Alignment, Constraint},
+    style::{Color, Modifier, Style},
+    text::Line,
+    widgets::{Block, Borders, Clear, Row, Table},
+};
+
+use crate::config::{EQ_BAND_COUNT, EQ_BANDS_HZ, EQ_PRESETS, MAX_EQ_DB, Settings, eq_preset_index};
+
+use super::utils::centered_rect_fixed;
+
+pub fn render_equalizer(frame: &mut Frame, selected: usize, settings: &Settings) {
+    let height = EQ_BAND_COUNT as u16 + 3;
+    let popup_area = centered_rect_fixed(48, height, frame.area());
+    frame.render_widget(Clear, popup_area);
+
+    let preset = eq_preset_index(&settings.eq_gains_db)
+        .map(|i| EQ_PRESETS[i].name)
+        .unwrap_or("Custom");
+    let mut rows = vec![Row::new(vec![
+        "Preset".to_string(),
+        format!("◀ {} ▶", preset),
+        String::new(),
+    ])];
+    rows.extend(EQ_BANDS_HZ.iter().zip(settings.eq_gains_db).map(|(hz, gain)| {
+        let gain = gain.clamp(-MAX_EQ_DB, MAX_EQ_DB);
+        Row::new(vec![band_label(*hz), slider(gain), format!("{:+} dB", gain)])
+    }));
+    let rows: Vec<Row> = rows
+        .into_iter()
+        .enumerate()
+        .map(|(i, row)| {
+            if i == selected {
+                row.style(
+                    Style::default()
+                        .fg(Color::Black)
+                        .bg(Color::LightBlue)
+                        .add_modifier(Modifier::BOLD),
+                )
+            } else {
+                row
+            }
+        })
+        .collect();
+
+    let slider_width = 2 * MAX_EQ_DB as u16 + 1;
+    let table = Table::new(
+        rows,
+        vec![
+            Constraint::Length(8),
+            Constraint::Length(slider_width),
+            Constraint::Min(0),
+        ],
+    )
+    .block(
+        Block::default()
+            .title("Equalizer")
+            .title_alignment(Alignment::Center)
+            .title_bottom(Line::from(" ↑/↓ band · ←/→ adjust · Esc back ").centered())
+            .borders(Borders::ALL)
+            .border_type(ratatui::widgets::BorderType::Rounded),
+    )
+    .column_spacing(1);
+    frame.render_widget(table, popup_area);
+}
+
+fn band_label(hz: u32) -> String {
+    if hz >= 1_000 {
+        format!("{} kHz", hz / 1_000)
+    } else {
+        format!("{} Hz", hz)
+    }
+}
+
+/// A knob on a line from -`MAX_EQ_DB` to +`MAX_EQ_DB`, with a tick at 0.
+fn slider(gain: i8) -> String {
+    (-MAX_EQ_DB..=MAX_EQ_DB)
+        .map(|db| match db {
+            db if db == gain => '●',
+            0 => '┼',
+            _ => '─',
+        })
+        .collect()
+}
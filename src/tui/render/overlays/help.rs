@@ -21,8 +21,13 @@ pub fn render_help(frame: &mut Frame) {
         Row::new(vec!["Enter", "Play selected track"]),
         Row::new(vec!["Shift + Right", "Skip song"]),
         Row::new(vec!["Shift + Left", "Go back a song"]),
-        Row::new(vec!["Option + Right", "Fast forward 10s"]),
-        Row::new(vec!["Option + Left", "Rewind 10s"]),
+        Row::new(vec!["Option + Right", "Fast forward (10s, seek_step_secs)"]),
+        Row::new(vec!["Option + Left", "Rewind (10s, seek_step_secs)"]),
+        Row::new(vec!["Option + Shift + Left/Right", "Seek by 60s (seek_coarse_step_secs)"]),
+        Row::new(vec!["0-9", "Jump to 0-90% of the track"]),
+        Row::new(vec![":", "Seek to a position (1:23:45, -30, 50%)"]),
+        Row::new(vec!["[ / ]", "Slower / faster (0.5x-2x)"]),
+        Row::new(vec!["\\", "Normal speed"]),
         Row::new(vec!["Option + Up/Down", "Move selector by 10"]),
         Row::new(vec!["Shift + Up/Down", "Move secondary selector"]),
         Row::new(vec!["Shift + J/K", "Move tertiary selector"]),
@@ -36,6 +41,18 @@ pub fn render_help(frame: &mut Frame) {
             "Shift + L",
             "Like selected item (or follow selected person)",
         ]),
+        Row::new(vec!["Shift + C", "Toggle comments popup (c: comment at current time)"]),
+        Row::new(vec!["Shift + W", "Comment at current playback position"]),
+        Row::new(vec!["Shift + T", "Repost / unrepost selected item"]),
+        Row::new(vec!["Shift + I", "Open profile of selected artist / uploader"]),
+        Row::new(vec!["Shift + G", "Start radio from selected track / artist"]),
+        Row::new(vec!["Shift + O", "Toggle autoplay radio when the queue runs out"]),
+        Row::new(vec!["Shift + P", "Add selected track to a playlist"]),
+        Row::new(vec!["Shift + E", "Edit selected playlist / new playlist"]),
+        Row::new(vec!["Shift + X", "Remove track from your playlist"]),
+        Row::new(vec!["Ctrl + Up/Down", "Move track in your playlist"]),
+        Row::new(vec!["Shift + M", "Switch account / add an account"]),
+        Row::new(vec!["Shift + B", "Settings (crossfade, loudness, EQ, quality)"]),
         Row::new(vec!["Shift + V", "Toggle visualizer mode"]),
         Row::new(vec!["Shift + F", "Search current view (only works in library)"]),
         Row::new(vec!["Shift + Q", "Toggle queue popup"]),
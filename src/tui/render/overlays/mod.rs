@@ -1,14 +1,29 @@
+mod accounts;
+mod comments;
+mod equalizer;
 mod help;
+mod playlist_menu;
+mod playlist_picker;
+mod profile;
+mod prompt;
 mod queue;
 mod quit;
+mod session;
+mod settings;
 mod utils;
 
-use std::collections::VecDeque;
+use std::collections::{HashSet, VecDeque};
 
 use ratatui::Frame;
+use ratatui_image::thread::ThreadProtocol;
 
-use crate::api::Track;
-use crate::tui::logic::state::QueuedTrack;
+use crate::api::{Comment, Playlist, Track};
+use crate::auth::SessionStatus;
+use crate::config::Settings;
+use crate::tui::logic::state::{
+    AccountPicker, PlaylistMenu, PlaylistPicker, ProfileView, Prompt, QueuedTrack, SettingsMenu,
+    UserProfile,
+};
 
 pub fn render_overlays(
     frame: &mut Frame,
@@ -17,11 +32,34 @@ pub fn render_overlays(
     auto_queue: &VecDeque<usize>,
     current_playing_track: Option<Track>,
     previous_playing_track: Option<Track>,
+    radio_active: bool,
+    radio_tracks: &[Track],
     queue_visible: bool,
     help_visible: bool,
     quit_confirm_visible: bool,
     quit_confirm_selected: usize,
+    comments: &[Comment],
+    comments_visible: bool,
+    comments_selected: usize,
+    playlists: &[Playlist],
+    playlist_picker: Option<&PlaylistPicker>,
+    playlist_menu: Option<&PlaylistMenu>,
+    prompt: Option<&Prompt>,
+    profile_view: Option<&ProfileView>,
+    profile: Option<&UserProfile>,
+    profile_avatar: &mut ThreadProtocol,
+    followed_user_urns: &HashSet<String>,
+    account_picker: Option<&AccountPicker>,
+    settings_menu: Option<&SettingsMenu>,
+    settings: &Settings,
+    active_account: &str,
+    session: &SessionStatus,
 ) {
+    if let Some(view) = profile_view {
+        let is_followed = followed_user_urns.contains(&view.user_urn);
+        profile::render_profile(frame, view, profile, profile_avatar, is_followed);
+    }
+
     if queue_visible {
         queue::render_queue(
             frame,
@@ -30,9 +68,15 @@ pub fn render_overlays(
             auto_queue,
             current_playing_track,
             previous_playing_track,
+            radio_active,
+            radio_tracks,
         );
     }
 
+    if comments_visible {
+        comments::render_comments(frame, comments, comments_selected);
+    }
+
     if help_visible {
         help::render_help(frame);
     }
@@ -40,4 +84,35 @@ pub fn render_overlays(
     if quit_confirm_visible {
         quit::render_quit_confirm(frame, quit_confirm_selected);
     }
+
+    if let Some(picker) = playlist_picker {
+        let owned: Vec<&Playlist> = playlists.iter().filter(|p| p.is_owned).collect();
+        playlist_picker::render_playlist_picker(frame, picker, &owned);
+    }
+
+    if let Some(menu) = playlist_menu {
+        playlist_menu::render_playlist_menu(frame, menu);
+    }
+
+    if let Some(picker) = account_picker {
+        accounts::render_account_picker(frame, picker, active_account);
+    }
+
+    if let Some(menu) = settings_menu {
+        match menu.equalizer {
+            Some(selected) => equalizer::render_equalizer(frame, selected, settings),
+            None => settings::render_settings(frame, menu, settings),
+        }
+    }
+
+    if let Some(prompt) = prompt {
+        prompt::render_prompt(frame, prompt);
+    }
+
+    if *session != SessionStatus::Active {
+        session::render_session(frame, session);
+        if quit_confirm_visible {
+            quit::render_quit_confirm(frame, quit_confirm_selected);
+        }
+    }
 }
@@ -0,0 +1,65 @@
+use ratatui::{
+    Frame,
+    layout::Alignment,
+    style::{Color, Modifier, Style},
+    text::{Line, Span, Text},
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+
+use crate::tui::logic::state::{PlaylistMenu, PlaylistMenuItem};
+use crate::tui::render::utils::truncate_with_ellipsis;
+
+use super::utils::centered_rect_fixed;
+
+pub fn render_playlist_menu(frame: &mut Frame, menu: &PlaylistMenu) {
+    let items = menu.items();
+    let popup_area = centered_rect_fixed(40, items.len() as u16 + 2, frame.area());
+    frame.render_widget(Clear, popup_area);
+
+    let lines: Vec<Line> = items
+        .iter()
+        .enumerate()
+        .map(|(i, item)| {
+            let label = match item {
+                PlaylistMenuItem::Rename => "Rename…",
+                PlaylistMenuItem::TogglePrivacy if menu.playlist.sharing == "private" => {
+                    "Make public"
+                }
+                PlaylistMenuItem::TogglePrivacy => "Make private",
+                PlaylistMenuItem::Delete if menu.confirm_delete => "Press Enter again to delete",
+                PlaylistMenuItem::Delete => "Delete",
+                PlaylistMenuItem::New => "New playlist…",
+            };
+            let style = if i == menu.selected {
+                let bg = if menu.confirm_delete {
+                    Color::LightRed
+                } else {
+                    Color::LightBlue
+                };
+                Style::default()
+                    .fg(Color::Black)
+                    .bg(bg)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(Color::White)
+            };
+            Line::from(Span::styled(label, style))
+        })
+        .collect();
+
+    let title = if menu.playlist.is_owned {
+        truncate_with_ellipsis(&menu.playlist.title, 34)
+    } else {
+        "Playlists".to_string()
+    };
+    let box_widget = Paragraph::new(Text::from(lines))
+        .alignment(Alignment::Center)
+        .block(
+            Block::default()
+                .title(title)
+                .title_alignment(Alignment::Center)
+                .borders(Borders::ALL)
+                .border_type(ratatui::widgets::BorderType::Rounded),
+        );
+    frame.render_widget(box_widget, popup_area);
+}
@@ -0,0 +1,67 @@
+use ratatui::{
+    Frame,
+    layout::{Alignment, Constraint},
+    style::{Color, Modifier, Style},
+    widgets::{Block, Borders, Clear, Row, Table},
+};
+
+use crate::api::Playlist;
+use crate::tui::logic::state::PlaylistPicker;
+use crate::tui::render::utils::truncate_with_ellipsis;
+
+use super::utils::centered_rect;
+
+pub fn render_playlist_picker(frame: &mut Frame, picker: &PlaylistPicker, playlists: &[&Playlist]) {
+    let popup_area = centered_rect(50, 50, frame.area());
+    frame.render_widget(Clear, popup_area);
+
+    let title_width = (popup_area.width as usize * 80) / 100;
+    let mut rows: Vec<Row> = vec![Row::new(vec![
+        "+ New playlist…".to_string(),
+        String::new(),
+    ])];
+    for playlist in playlists {
+        rows.push(Row::new(vec![
+            truncate_with_ellipsis(&playlist.title, title_width),
+            playlist.track_count.to_string(),
+        ]));
+    }
+
+    // keep the selected row on screen
+    let max_rows = popup_area.height.saturating_sub(2) as usize;
+    let offset = (picker.selected + 1).saturating_sub(max_rows);
+    let rows: Vec<Row> = rows
+        .into_iter()
+        .enumerate()
+        .skip(offset)
+        .map(|(i, row)| {
+            if i == picker.selected {
+                row.style(
+                    Style::default()
+                        .fg(Color::Black)
+                        .bg(Color::LightBlue)
+                        .add_modifier(Modifier::BOLD),
+                )
+            } else {
+                row
+            }
+        })
+        .collect();
+
+    let table = Table::new(
+        rows,
+        vec![Constraint::Percentage(85), Constraint::Percentage(15)],
+    )
+    .block(
+        Block::default()
+            .title(format!(
+                "Add \"{}\" to playlist",
+                truncate_with_ellipsis(&picker.track.title, title_width / 2)
+            ))
+            .title_alignment(Alignment::Center)
+            .borders(Borders::ALL)
+            .border_type(ratatui::widgets::BorderType::Rounded),
+    )
+    .column_spacing(1);
+    frame.render_widget(table, popup_area);
+}
@@ -0,0 +1,199 @@
+use ratatui::{
+    Frame,
+    layout::{Alignment, Constraint, Layout},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph, Row, Table, Tabs, Wrap},
+};
+use ratatui_image::{Resize, StatefulImage, thread::ThreadProtocol};
+
+use crate::tui::logic::state::{PROFILE_TABS, ProfileView, UserProfile};
+use crate::tui::render::utils::{
+    format_count, format_duration, format_year, styled_header, truncate_with_ellipsis,
+};
+
+use super::utils::centered_rect;
+
+pub fn render_profile(
+    frame: &mut Frame,
+    view: &ProfileView,
+    profile: Option<&UserProfile>,
+    avatar: &mut ThreadProtocol,
+    is_followed: bool,
+) {
+    let popup_area = centered_rect(80, 80, frame.area());
+    frame.render_widget(Clear, popup_area);
+
+    let block = Block::default()
+        .title("Profile · Shift+L: follow · Esc: close")
+        .title_alignment(Alignment::Center)
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded);
+    let inner = block.inner(popup_area);
+    frame.render_widget(block, popup_area);
+
+    let Some(profile) = profile else {
+        let loading = match &view.error {
+            Some(error) => Paragraph::new(format!("Couldn't load profile: {}", error))
+                .style(Style::default().fg(Color::Red))
+                .wrap(Wrap { trim: true }),
+            None => Paragraph::new("Loading..."),
+        };
+        frame.render_widget(loading.alignment(Alignment::Center), inner);
+        return;
+    };
+    let artist = &profile.artist;
+
+    let chunks = Layout::default()
+        .direction(ratatui::layout::Direction::Vertical)
+        .constraints([
+            Constraint::Length(8),
+            Constraint::Length(3),
+            Constraint::Min(0),
+        ])
+        .split(inner);
+    let header = Layout::default()
+        .direction(ratatui::layout::Direction::Horizontal)
+        .constraints([Constraint::Length(18), Constraint::Min(0)])
+        .split(chunks[0]);
+
+    let image_widget = StatefulImage::new().resize(Resize::Scale(None));
+    frame.render_stateful_widget(image_widget, header[0], avatar);
+
+    let mut name = vec![Span::styled(
+        artist.name.clone(),
+        Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD),
+    )];
+    if !artist.full_name.is_empty() {
+        name.push(Span::raw(format!("  {}", artist.full_name)));
+    }
+    if is_followed {
+        name.push(Span::styled("  ✔︎ following", Style::default().fg(Color::Green)));
+    }
+    let location = [artist.city.as_str(), artist.country.as_str()]
+        .into_iter()
+        .filter(|s| !s.is_empty())
+        .collect::<Vec<_>>()
+        .join(", ");
+    let counts = format!(
+        "{} followers · {} following · {} tracks",
+        format_count(artist.followers_count),
+        format_count(artist.followings_count),
+        format_count(artist.track_count),
+    );
+    let lines = vec![
+        Line::from(name),
+        Line::from(Span::styled(location, Style::default().fg(Color::Gray))),
+        Line::from(counts),
+        Line::from(""),
+        Line::from(artist.description.replace('\n', " ")),
+    ];
+    let details = Paragraph::new(lines).wrap(Wrap { trim: true });
+    frame.render_widget(details, header[1]);
+
+    let tabs = Tabs::new(PROFILE_TABS.iter().map(|t| Span::raw(*t)).collect::<Vec<_>>())
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded),
+        )
+        .select(view.selected_tab)
+        .style(Style::default().fg(Color::White))
+        .highlight_style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD));
+    frame.render_widget(tabs, chunks[1]);
+
+    let table_area = chunks[2];
+    let width = table_area.width as usize;
+    let title_width = width / 2;
+    let (header, widths, rows): (Row, Vec<Constraint>, Vec<Vec<String>>) = match view.selected_tab {
+        0 => (
+            styled_header(&["Title", "No. Songs", "Duration"]),
+            vec![
+                Constraint::Percentage(70),
+                Constraint::Percentage(15),
+                Constraint::Percentage(15),
+            ],
+            profile
+                .playlists
+                .iter()
+                .map(|p| {
+                    vec![
+                        truncate_with_ellipsis(&p.title, title_width),
+                        p.track_count.to_string(),
+                        format_duration(p.duration_ms),
+                    ]
+                })
+                .collect(),
+        ),
+        1 => (
+            styled_header(&["Title", "Year", "No. Songs", "Duration"]),
+            vec![
+                Constraint::Percentage(60),
+                Constraint::Percentage(10),
+                Constraint::Percentage(15),
+                Constraint::Percentage(15),
+            ],
+            profile
+                .albums
+                .iter()
+                .map(|a| {
+                    vec![
+                        truncate_with_ellipsis(&a.title, title_width),
+                        format_year(a.release_year),
+                        a.track_count.to_string(),
+                        format_duration(a.duration_ms),
+                    ]
+                })
+                .collect(),
+        ),
+        _ => (
+            styled_header(&["Title", "Artist(s)", "Duration", "Streams"]),
+            vec![
+                Constraint::Percentage(50),
+                Constraint::Percentage(30),
+                Constraint::Percentage(10),
+                Constraint::Percentage(10),
+            ],
+            profile
+                .reposts
+                .iter()
+                .map(|t| {
+                    vec![
+                        truncate_with_ellipsis(&t.title, title_width),
+                        truncate_with_ellipsis(t.artists(), width * 3 / 10),
+                        format_duration(t.duration_ms),
+                        format_count(t.playback_count),
+                    ]
+                })
+                .collect(),
+        ),
+    };
+
+    // keep the selected row on screen
+    let max_rows = table_area.height.saturating_sub(3) as usize;
+    let offset = (view.selected_row + 1).saturating_sub(max_rows);
+    let rows: Vec<Row> = rows
+        .into_iter()
+        .enumerate()
+        .skip(offset)
+        .take(max_rows)
+        .map(|(i, cols)| {
+            let row = Row::new(cols);
+            if i == view.selected_row {
+                row.style(Style::default().bg(Color::LightBlue).fg(Color::White))
+            } else {
+                row
+            }
+        })
+        .collect();
+
+    let table = Table::new(rows, widths)
+        .header(header)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded),
+        )
+        .column_spacing(1);
+    frame.render_widget(table, table_area);
+}
@@ -0,0 +1,39 @@
+use ratatui::{
+    Frame,
+    layout::Alignment,
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+
+use crate::tui::logic::state::Prompt;
+
+use super::utils::centered_rect_fixed;
+
+pub fn render_prompt(frame: &mut Frame, prompt: &Prompt) {
+    let popup_area = centered_rect_fixed(50, 3, frame.area());
+    frame.render_widget(Clear, popup_area);
+
+    // show the tail of long input so the cursor stays visible
+    let inner_width = popup_area.width.saturating_sub(3) as usize;
+    let chars: Vec<char> = prompt.input.chars().collect();
+    let visible: String = chars[chars.len().saturating_sub(inner_width)..]
+        .iter()
+        .collect();
+
+    let line = Line::from(vec![
+        Span::raw(visible),
+        Span::styled("█", Style::default().fg(Color::LightBlue)),
+    ]);
+
+    let box_widget = Paragraph::new(line)
+        .alignment(Alignment::Left)
+        .block(
+            Block::default()
+                .title(prompt.title.as_str())
+                .title_alignment(Alignment::Center)
+                .borders(Borders::ALL)
+                .border_type(ratatui::widgets::BorderType::Rounded),
+        );
+    frame.render_widget(box_widget, popup_area);
+}
@@ -9,10 +9,12 @@ use ratatui::{
 
 use crate::api::Track;
 use crate::tui::logic::state::QueuedTrack;
-use crate::tui::render::utils::{styled_header, truncate_with_ellipsis};
+use crate::tui::render::utils::{format_duration, styled_header, truncate_with_ellipsis};
 
 use super::utils::centered_rect;
 
+const RADIO_MARKER: &str = "↝";
+
 pub fn render_queue(
     frame: &mut Frame,
     queue_tracks: &Vec<Track>,
@@ -20,6 +22,8 @@ pub fn render_queue(
     auto_queue: &VecDeque<usize>,
     current_playing_track: Option<Track>,
     previous_playing_track: Option<Track>,
+    radio_active: bool,
+    radio_tracks: &[Track],
 ) {
     let popup_area = centered_rect(70, 60, frame.area());
     frame.render_widget(Clear, popup_area);
@@ -27,6 +31,9 @@ pub fn render_queue(
     let total_width = popup_area.width as usize;
     let title_width = (total_width * 65) / 100;
     let artist_width = (total_width * 25) / 100;
+    let radio_title = |track: &Track| {
+        truncate_with_ellipsis(&format!("{RADIO_MARKER} {}", track.title), title_width)
+    };
 
     let mut rows: Vec<Row> = Vec::new();
 
@@ -34,8 +41,8 @@ pub fn render_queue(
         rows.push(
             Row::new(vec![
                 truncate_with_ellipsis(&track.title, title_width),
-                truncate_with_ellipsis(&track.artists, artist_width),
-                track.duration.clone(),
+                truncate_with_ellipsis(track.artists(), artist_width),
+                format_duration(track.duration_ms),
             ])
             .style(Style::default().fg(Color::DarkGray)),
         );
@@ -49,8 +56,8 @@ pub fn render_queue(
         rows.push(
             Row::new(vec![
                 truncate_with_ellipsis(&track.title, title_width),
-                truncate_with_ellipsis(&track.artists, artist_width),
-                track.duration.clone(),
+                truncate_with_ellipsis(track.artists(), artist_width),
+                format_duration(track.duration_ms),
             ])
             .style(Style::default().bg(Color::LightBlue).fg(Color::White)),
         );
@@ -68,8 +75,8 @@ pub fn render_queue(
         let track = &queued.track;
         let mut row = Row::new(vec![
             truncate_with_ellipsis(&track.title, title_width),
-            truncate_with_ellipsis(&track.artists, artist_width),
-            track.duration.clone(),
+            truncate_with_ellipsis(track.artists(), artist_width),
+            format_duration(track.duration_ms),
         ]);
         if !track.is_playable() {
             row = row.style(Style::default().fg(Color::DarkGray));
@@ -83,10 +90,15 @@ pub fn render_queue(
             break;
         }
         if let Some(track) = queue_tracks.get(*idx) {
+            let title = if radio_active {
+                radio_title(track)
+            } else {
+                truncate_with_ellipsis(&track.title, title_width)
+            };
             let mut row = Row::new(vec![
-                truncate_with_ellipsis(&track.title, title_width),
-                truncate_with_ellipsis(&track.artists, artist_width),
-                track.duration.clone(),
+                title,
+                truncate_with_ellipsis(track.artists(), artist_width),
+                format_duration(track.duration_ms),
             ]);
             if !track.is_playable() {
                 row = row.style(Style::default().fg(Color::DarkGray));
@@ -96,6 +108,21 @@ pub fn render_queue(
         }
     }
 
+    for track in radio_tracks.iter() {
+        if remaining == 0 {
+            break;
+        }
+        rows.push(
+            Row::new(vec![
+                radio_title(track),
+                truncate_with_ellipsis(track.artists(), artist_width),
+                format_duration(track.duration_ms),
+            ])
+            .style(Style::default().fg(Color::Cyan)),
+        );
+        remaining -= 1;
+    }
+
     if rows.len() <= 2 {
         rows.push(Row::new(vec!["Queue is empty", "", ""]));
     }
@@ -112,7 +139,7 @@ pub fn render_queue(
     .header(header)
     .block(
         Block::default()
-            .title("Queue")
+            .title(if radio_active { "Queue · Radio" } else { "Queue" })
             .title_alignment(Alignment::Center)
             .borders(Borders::ALL)
             .border_type(ratatui::widgets::BorderType::Rounded),
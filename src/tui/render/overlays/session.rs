@@ -0,0 +1,54 @@
+use ratatui::{
+    Frame,
+    layout::Alignment,
+    style::{Color, Modifier, Style},
+    text::{Line, Text},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+};
+
+use crate::auth::SessionStatus;
+
+use super::utils::centered_rect_fixed;
+
+/// Blocks the UI while the session is expired, until a new login went through.
+pub fn render_session(frame: &mut Frame, session: &SessionStatus) {
+    let popup_area = centered_rect_fixed(52, 7, frame.area());
+    frame.render_widget(Clear, popup_area);
+
+    let (title, detail, hint) = match session {
+        SessionStatus::Active => return,
+        SessionStatus::Expired => (
+            "Session expired",
+            "SoundCloud no longer accepts the saved login.".to_string(),
+            "Press Enter to log in",
+        ),
+        SessionStatus::LoggingIn => (
+            "Logging in…",
+            "Finish logging in in your browser.".to_string(),
+            "",
+        ),
+        SessionStatus::LoginFailed(error) => {
+            ("Login failed", error.clone(), "Press Enter to try again")
+        }
+    };
+
+    let text = Text::from(vec![
+        Line::styled(
+            title,
+            Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+        ),
+        Line::from(""),
+        Line::from(detail),
+        Line::styled(hint, Style::default().fg(Color::Cyan)),
+    ]);
+
+    let box_widget = Paragraph::new(text)
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true })
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_type(ratatui::widgets::BorderType::Rounded),
+        );
+    frame.render_widget(box_widget, popup_area);
+}
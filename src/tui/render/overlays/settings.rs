@@ -0,0 +1,84 @@
+use ratatui::{
+    Frame,
+    layout::{Alignment, Constraint},
+    style::{Color, Modifier, Style},
+    text::Line,
+    widgets::{Block, Borders, Clear, Row, Table},
+};
+
+use crate::config::{
+    EQ_PRESETS, MAX_CROSSFADE_SECS, MAX_TARGET_LUFS, MIN_TARGET_LUFS, Settings, eq_preset_index,
+};
+use crate::tui::logic::state::{SETTINGS_ITEMS, SettingsItem, SettingsMenu};
+
+use super::utils::centered_rect_fixed;
+
+pub fn render_settings(frame: &mut Frame, menu: &SettingsMenu, settings: &Settings) {
+    let height = SETTINGS_ITEMS.len() as u16 + 2;
+    let popup_area = centered_rect_fixed(48, height, frame.area());
+    frame.render_widget(Clear, popup_area);
+
+    let rows: Vec<Row> = SETTINGS_ITEMS
+        .iter()
+        .enumerate()
+        .map(|(i, item)| {
+            let row = Row::new(vec![label(*item).to_string(), value(*item, settings)]);
+            if i == menu.selected {
+                row.style(
+                    Style::default()
+                        .fg(Color::Black)
+                        .bg(Color::LightBlue)
+                        .add_modifier(Modifier::BOLD),
+                )
+            } else {
+                row
+            }
+        })
+        .collect();
+
+    let table = Table::new(rows, vec![Constraint::Min(0), Constraint::Length(20)])
+        .block(
+            Block::default()
+                .title("Settings")
+                .title_alignment(Alignment::Center)
+                .title_bottom(Line::from(" ←/→ change · Esc close ").centered())
+                .borders(Borders::ALL)
+                .border_type(ratatui::widgets::BorderType::Rounded),
+        )
+        .column_spacing(1);
+    frame.render_widget(table, popup_area);
+}
+
+fn label(item: SettingsItem) -> &'static str {
+    match item {
+        SettingsItem::Crossfade => "Crossfade",
+        SettingsItem::Normalize => "Normalize loudness",
+        SettingsItem::TargetLoudness => "Target loudness",
+        SettingsItem::Equalizer => "Equalizer (Enter)",
+        SettingsItem::PreservePitch => "Keep pitch at speed",
+        SettingsItem::StreamQuality => "Stream quality",
+    }
+}
+
+fn value(item: SettingsItem, settings: &Settings) -> String {
+    match item {
+        SettingsItem::Crossfade if settings.crossfade_secs == 0 => "◀ off ▶".to_string(),
+        SettingsItem::Crossfade => format!(
+            "◀ {}s ▶",
+            settings.crossfade_secs.min(MAX_CROSSFADE_SECS)
+        ),
+        SettingsItem::Normalize if settings.normalize => "◀ on ▶".to_string(),
+        SettingsItem::Normalize => "◀ off ▶".to_string(),
+        SettingsItem::PreservePitch if settings.preserve_pitch => "◀ on ▶".to_string(),
+        SettingsItem::PreservePitch => "◀ off ▶".to_string(),
+        SettingsItem::TargetLoudness => format!(
+            "◀ {} LUFS ▶",
+            settings.target_lufs.clamp(MIN_TARGET_LUFS, MAX_TARGET_LUFS)
+        ),
+        SettingsItem::Equalizer => format!(
+            "◀ {} ▶",
+            eq_preset_index(&settings.eq_gains_db).map_or("Custom", |i| EQ_PRESETS[i].name)
+        ),
+        SettingsItem::StreamQuality => format!("◀ {} ▶", settings.stream_quality().label()),
+    }
+}
@@ -2,20 +2,26 @@ use ratatui::{
     Frame,
     layout::{Constraint, Layout, Rect},
     style::{Color, Style},
-    widgets::{Block, Borders, Row, Table},
+    widgets::{Block, Borders, Row, Table, TableState},
 };
 
+use crate::api::{Activity, ActivityOrigin, Track};
 use crate::tui::render::utils::{
-    calculate_column_widths, calculate_min_widths, styled_header, truncate_with_ellipsis,
+    calculate_column_widths, calculate_min_widths, format_age, format_duration, styled_header,
+    truncate_with_ellipsis,
 };
 
-const NUM_FEED_ACTIVITY_COLS: usize = 4;
+const NUM_FEED_ACTIVITY_COLS: usize = 5;
 const NUM_FEED_INFO_COLS: usize = 3;
 
 pub fn render_feed(
     frame: &mut Frame,
     area: Rect,
     width: usize,
+    feed: &[Activity],
+    feed_state: &mut TableState,
+    feed_tracks: &[Track],
+    feed_tracks_state: &mut TableState,
     selected_row: usize,
     selected_info_row: usize,
     info_pane_selected: bool,
@@ -25,38 +31,45 @@ pub fn render_feed(
         .constraints([Constraint::Percentage(60), Constraint::Percentage(40)].as_ref())
         .split(area);
 
-    let activity_header = styled_header(&["User", "Action", "Media Type", "Age"]);
+    let activity_header = styled_header(&["User", "Action", "Title", "Media Type", "Age"]);
 
     let activity_col_widths = calculate_column_widths(NUM_FEED_ACTIVITY_COLS);
-    let activity_col_min_widths = calculate_min_widths(&activity_col_widths, width / 2);
+    let activity_col_min_widths = calculate_min_widths(&activity_col_widths, width * 6 / 10);
 
-    let activity_rows = vec![
-        vec![
-            truncate_with_ellipsis("User 1", activity_col_min_widths[0]),
-            truncate_with_ellipsis("Post", activity_col_min_widths[1]),
-            truncate_with_ellipsis("Track", activity_col_min_widths[2]),
-            truncate_with_ellipsis("2d", activity_col_min_widths[3]),
-        ],
-        vec![
-            truncate_with_ellipsis("User 2", activity_col_min_widths[0]),
-            truncate_with_ellipsis("Repost", activity_col_min_widths[1]),
-            truncate_with_ellipsis("Album", activity_col_min_widths[2]),
-            truncate_with_ellipsis("5d", activity_col_min_widths[3]),
-        ],
-    ]
-    .into_iter()
-    .enumerate()
-    .map(|(i, cols)| {
-        let row = Row::new(cols);
-        if i == selected_row && info_pane_selected {
-            row.style(Style::default().bg(Color::Gray).fg(Color::Black))
-        } else if i == selected_row && !info_pane_selected {
-            row.style(Style::default().bg(Color::LightBlue).fg(Color::White))
-        } else {
-            row
-        }
-    })
-    .collect::<Vec<_>>();
+    let activity_rows = feed
+        .iter()
+        .enumerate()
+        .map(|(i, activity)| {
+            let (title, media_type) = match &activity.origin {
+                ActivityOrigin::Track(track) | ActivityOrigin::TrackRepost(track) => {
+                    (track.title.as_str(), "Track")
+                }
+                ActivityOrigin::Playlist(playlist) | ActivityOrigin::PlaylistRepost(playlist) => {
+                    let media_type = if playlist.playlist_type.eq_ignore_ascii_case("album") {
+                        "Album"
+                    } else {
+                        "Playlist"
+                    };
+                    (playlist.title.as_str(), media_type)
+                }
+            };
+            let action = if activity.is_repost() { "Repost" } else { "Post" };
+            let row = Row::new(vec![
+                truncate_with_ellipsis(&activity.user().name, activity_col_min_widths[0]),
+                truncate_with_ellipsis(action, activity_col_min_widths[1]),
+                truncate_with_ellipsis(title, activity_col_min_widths[2]),
+                truncate_with_ellipsis(media_type, activity_col_min_widths[3]),
+                truncate_with_ellipsis(&format_age(activity.created_at), activity_col_min_widths[4]),
+            ]);
+            if i == selected_row && info_pane_selected {
+                row.style(Style::default().bg(Color::Gray).fg(Color::Black))
+            } else if i == selected_row && !info_pane_selected {
+                row.style(Style::default().bg(Color::LightBlue).fg(Color::White))
+            } else {
+                row
+            }
+        })
+        .collect::<Vec<_>>();
 
     let table = Table::new(activity_rows, activity_col_widths)
         .header(activity_header)
@@ -73,38 +86,34 @@ pub fn render_feed(
                 }),
         )
         .column_spacing(1);
-    frame.render_widget(table, subchunks[0]);
+    frame.render_stateful_widget(table, subchunks[0], feed_state);
 
     let info_header = styled_header(&["Title", "Artist", "Dur."]);
 
     let info_col_widths = calculate_column_widths(NUM_FEED_INFO_COLS);
-    let info_col_min_widths = calculate_min_widths(&info_col_widths, width / 2);
+    let info_col_min_widths = calculate_min_widths(&info_col_widths, width * 4 / 10);
 
-    let info_rows = vec![
-        vec![
-            truncate_with_ellipsis("Track 1", info_col_min_widths[0]),
-            truncate_with_ellipsis("Artist 1", info_col_min_widths[1]),
-            truncate_with_ellipsis("1:30", info_col_min_widths[2]),
-        ],
-        vec![
-            truncate_with_ellipsis("Track 2", info_col_min_widths[0]),
-            truncate_with_ellipsis("Artist 1", info_col_min_widths[1]),
-            truncate_with_ellipsis("2:10", info_col_min_widths[2]),
-        ],
-    ]
-    .into_iter()
-    .enumerate()
-    .map(|(i, cols)| {
-        let row = Row::new(cols);
-        if i == selected_info_row && info_pane_selected {
-            row.style(Style::default().bg(Color::LightBlue).fg(Color::White))
-        } else if i == selected_info_row && !info_pane_selected {
-            row.style(Style::default().bg(Color::Gray).fg(Color::Black))
-        } else {
-            row
-        }
-    })
-    .collect::<Vec<_>>();
+    let info_rows = feed_tracks
+        .iter()
+        .enumerate()
+        .map(|(i, track)| {
+            let mut row = Row::new(vec![
+                truncate_with_ellipsis(&track.title, info_col_min_widths[0]),
+                truncate_with_ellipsis(track.artists(), info_col_min_widths[1]),
+                truncate_with_ellipsis(&format_duration(track.duration_ms), info_col_min_widths[2]),
+            ]);
+            if !track.is_playable() {
+                row = row.style(Style::default().fg(Color::DarkGray));
+            }
+            if i == selected_info_row && info_pane_selected {
+                row.style(Style::default().bg(Color::LightBlue).fg(Color::White))
+            } else if i == selected_info_row && !info_pane_selected {
+                row.style(Style::default().bg(Color::Gray).fg(Color::Black))
+            } else {
+                row
+            }
+        })
+        .collect::<Vec<_>>();
 
     let table = Table::new(info_rows, info_col_widths)
         .header(info_header)
@@ -122,5 +131,5 @@ pub fn render_feed(
         )
         .column_spacing(1);
 
-    frame.render_widget(table, subchunks[1]);
+    frame.render_stateful_widget(table, subchunks[1], feed_tracks_state);
 }
@@ -7,8 +7,12 @@ use ratatui::{
 };
 
 use crate::api::{Album, Artist, Playlist, Track};
+use std::collections::HashSet;
 
-use crate::tui::render::utils::{calculate_min_widths, styled_header, truncate_with_ellipsis};
+use crate::tui::render::utils::{
+    calculate_min_widths, format_count, format_duration, format_year, styled_header,
+    truncate_with_ellipsis,
+};
 
 pub fn render_library(
     frame: &mut Frame,
@@ -30,6 +34,8 @@ pub fn render_library(
     following_tracks_state: &mut TableState,
     following_likes_tracks: &Vec<Track>,
     following_likes_state: &mut TableState,
+    reposted_track_urns: &HashSet<String>,
+    reposted_playlist_uris: &HashSet<String>,
     selected_subtab: usize,
     subtab_titles: &[&str],
     selected_row: usize,
@@ -88,8 +94,9 @@ pub fn render_library(
 
     let (header, col_widths) = match selected_subtab {
         0 => (
-            styled_header(&["Title", "Artist(s)", "Duration", "Streams"]),
+            styled_header(&["↻", "Title", "Artist(s)", "Duration", "Streams"]),
             vec![
+                Constraint::Length(1),
                 Constraint::Percentage(55),
                 Constraint::Percentage(25),
                 Constraint::Percentage(10),
@@ -97,16 +104,18 @@ pub fn render_library(
             ],
         ),
         1 => (
-            styled_header(&["Name", "No. Songs", "Duration"]),
+            styled_header(&["↻", "Name", "No. Songs", "Duration"]),
             vec![
+                Constraint::Length(1),
                 Constraint::Percentage(70),
                 Constraint::Percentage(15),
                 Constraint::Percentage(15),
             ],
         ),
         2 => (
-            styled_header(&["Title", "Artist(s)", "Year", "No. Songs", "Duration"]),
+            styled_header(&["↻", "Title", "Artist(s)", "Year", "No. Songs", "Duration"]),
             vec![
+                Constraint::Length(1),
                 Constraint::Percentage(50),
                 Constraint::Percentage(20),
                 Constraint::Percentage(10),
@@ -127,11 +136,17 @@ pub fn render_library(
         0 => likes_view
             .iter()
             .map(|track| {
+                let reposted = if reposted_track_urns.contains(&track.track_urn) {
+                    "↻"
+                } else {
+                    ""
+                };
                 let mut row = Row::new(vec![
-                    truncate_with_ellipsis(&track.title, col_min_widths[0]),
-                    truncate_with_ellipsis(&track.artists, col_min_widths[1]),
-                    truncate_with_ellipsis(&track.duration, col_min_widths[2]),
-                    truncate_with_ellipsis(&track.playback_count, col_min_widths[3]),
+                    truncate_with_ellipsis(reposted, col_min_widths[0]),
+                    truncate_with_ellipsis(&track.title, col_min_widths[1]),
+                    truncate_with_ellipsis(track.artists(), col_min_widths[2]),
+                    truncate_with_ellipsis(&format_duration(track.duration_ms), col_min_widths[3]),
+                    truncate_with_ellipsis(&format_count(track.playback_count), col_min_widths[4]),
                 ]);
                 if !track.is_playable() {
                     row = row.style(Style::default().fg(Color::DarkGray));
@@ -142,22 +157,39 @@ pub fn render_library(
         1 => playlists
             .iter()
             .map(|playlist| {
+                let title = if playlist.sharing == "private" {
+                    format!("{} (private)", playlist.title)
+                } else {
+                    playlist.title.clone()
+                };
+                let reposted = if reposted_playlist_uris.contains(&playlist.tracks_uri) {
+                    "↻"
+                } else {
+                    ""
+                };
                 Row::new(vec![
-                    truncate_with_ellipsis(&playlist.title, col_min_widths[0]),
-                    truncate_with_ellipsis(&playlist.track_count, col_min_widths[1]),
-                    truncate_with_ellipsis(&playlist.duration, col_min_widths[2]),
+                    truncate_with_ellipsis(reposted, col_min_widths[0]),
+                    truncate_with_ellipsis(&title, col_min_widths[1]),
+                    truncate_with_ellipsis(&playlist.track_count.to_string(), col_min_widths[2]),
+                    truncate_with_ellipsis(&format_duration(playlist.duration_ms), col_min_widths[3]),
                 ])
             })
             .collect(),
         2 => albums
             .iter()
             .map(|album| {
+                let reposted = if reposted_playlist_uris.contains(&album.tracks_uri) {
+                    "↻"
+                } else {
+                    ""
+                };
                 Row::new(vec![
-                    truncate_with_ellipsis(&album.title, col_min_widths[0]),
-                    truncate_with_ellipsis(&album.artists, col_min_widths[1]),
-                    truncate_with_ellipsis(&album.release_year, col_min_widths[2]),
-                    truncate_with_ellipsis(&album.track_count, col_min_widths[3]),
-                    truncate_with_ellipsis(&album.duration, col_min_widths[4]),
+                    truncate_with_ellipsis(reposted, col_min_widths[0]),
+                    truncate_with_ellipsis(&album.title, col_min_widths[1]),
+                    truncate_with_ellipsis(album.artists(), col_min_widths[2]),
+                    truncate_with_ellipsis(&format_year(album.release_year), col_min_widths[3]),
+                    truncate_with_ellipsis(&album.track_count.to_string(), col_min_widths[4]),
+                    truncate_with_ellipsis(&format_duration(album.duration_ms), col_min_widths[5]),
                 ])
             })
             .collect(),
@@ -243,9 +275,9 @@ pub fn render_library(
             .map(|(i, track)| {
                 let mut row = Row::new(vec![
                     truncate_with_ellipsis(&track.title, track_min_widths[0]),
-                    truncate_with_ellipsis(&track.artists, track_min_widths[1]),
-                    truncate_with_ellipsis(&track.duration, track_min_widths[2]),
-                    truncate_with_ellipsis(&track.playback_count, track_min_widths[3]),
+                    truncate_with_ellipsis(track.artists(), track_min_widths[1]),
+                    truncate_with_ellipsis(&format_duration(track.duration_ms), track_min_widths[2]),
+                    truncate_with_ellipsis(&format_count(track.playback_count), track_min_widths[3]),
                 ]);
                 if !track.is_playable() {
                     row = row.style(Style::default().fg(Color::DarkGray));
@@ -302,8 +334,8 @@ pub fn render_library(
             .map(|(i, track)| {
                 let mut row = Row::new(vec![
                     truncate_with_ellipsis(&track.title, track_min_widths[0]),
-                    truncate_with_ellipsis(&track.duration, track_min_widths[1]),
-                    truncate_with_ellipsis(&track.playback_count, track_min_widths[2]),
+                    truncate_with_ellipsis(&format_duration(track.duration_ms), track_min_widths[1]),
+                    truncate_with_ellipsis(&format_count(track.playback_count), track_min_widths[2]),
                 ]);
                 if !track.is_playable() {
                     row = row.style(Style::default().fg(Color::DarkGray));
@@ -364,8 +396,8 @@ pub fn render_library(
             .map(|(i, track)| {
                 let mut row = Row::new(vec![
                     truncate_with_ellipsis(&track.title, published_min_widths[0]),
-                    truncate_with_ellipsis(&track.duration, published_min_widths[1]),
-                    truncate_with_ellipsis(&track.playback_count, published_min_widths[2]),
+                    truncate_with_ellipsis(&format_duration(track.duration_ms), published_min_widths[1]),
+                    truncate_with_ellipsis(&format_count(track.playback_count), published_min_widths[2]),
                 ]);
                 if !track.is_playable() {
                     row = row.style(Style::default().fg(Color::DarkGray));
@@ -412,9 +444,9 @@ pub fn render_library(
             .map(|(i, track)| {
                 let mut row = Row::new(vec![
                     truncate_with_ellipsis(&track.title, likes_min_widths[0]),
-                    truncate_with_ellipsis(&track.artists, likes_min_widths[1]),
-                    truncate_with_ellipsis(&track.duration, likes_min_widths[2]),
-                    truncate_with_ellipsis(&track.playback_count, likes_min_widths[3]),
+                    truncate_with_ellipsis(track.artists(), likes_min_widths[1]),
+                    truncate_with_ellipsis(&format_duration(track.duration_ms), likes_min_widths[2]),
+                    truncate_with_ellipsis(&format_count(track.playback_count), likes_min_widths[3]),
                 ]);
                 if !track.is_playable() {
                     row = row.style(Style::default().fg(Color::DarkGray));
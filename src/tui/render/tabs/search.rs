@@ -9,7 +9,10 @@ use ratatui::{
 use crate::api::{Album, Artist, Playlist, Track};
 use std::collections::HashSet;
 
-use crate::tui::render::utils::{calculate_min_widths, styled_header, truncate_with_ellipsis};
+use crate::tui::render::utils::{
+    calculate_min_widths, format_count, format_duration, format_year, styled_header,
+    truncate_with_ellipsis,
+};
 
 const NUM_SEARCHFILTERS: usize = 4;
 
@@ -25,6 +28,8 @@ pub fn render_search(
     liked_album_uris: &HashSet<String>,
     liked_playlist_uris: &HashSet<String>,
     followed_user_urns: &HashSet<String>,
+    reposted_track_urns: &HashSet<String>,
+    reposted_playlist_uris: &HashSet<String>,
     search_tracks: &Vec<Track>,
     search_tracks_state: &mut TableState,
     search_playlists: &Vec<Playlist>,
@@ -73,8 +78,9 @@ pub fn render_search(
     let table_area = subchunks[1];
 
     if selected_searchfilter == 0 {
-        let header = styled_header(&["♥", "Title", "Artist(s)", "Duration", "Streams"]);
+        let header = styled_header(&["♥", "↻", "Title", "Artist(s)", "Duration", "Streams"]);
         let col_widths = vec![
+            Constraint::Length(1),
             Constraint::Length(1),
             Constraint::Percentage(53),
             Constraint::Percentage(26),
@@ -97,12 +103,18 @@ pub fn render_search(
                 } else {
                     ""
                 };
+                let reposted = if reposted_track_urns.contains(&track.track_urn) {
+                    "↻"
+                } else {
+                    ""
+                };
                 let mut row = Row::new(vec![
                     truncate_with_ellipsis(liked, col_min_widths[0]),
-                    truncate_with_ellipsis(&track.title, col_min_widths[1]),
-                    truncate_with_ellipsis(&track.artists, col_min_widths[2]),
-                    truncate_with_ellipsis(&track.duration, col_min_widths[3]),
-                    truncate_with_ellipsis(&track.playback_count, col_min_widths[4]),
+                    truncate_with_ellipsis(reposted, col_min_widths[1]),
+                    truncate_with_ellipsis(&track.title, col_min_widths[2]),
+                    truncate_with_ellipsis(track.artists(), col_min_widths[3]),
+                    truncate_with_ellipsis(&format_duration(track.duration_ms), col_min_widths[4]),
+                    truncate_with_ellipsis(&format_count(track.playback_count), col_min_widths[5]),
                 ]);
                 if !track.is_playable() {
                     row = row.style(Style::default().fg(Color::DarkGray));
@@ -134,8 +146,9 @@ pub fn render_search(
             .constraints([Constraint::Percentage(33), Constraint::Percentage(67)].as_ref())
             .split(table_area);
 
-        let header = styled_header(&["♥", "Name", "No. Songs", "Duration"]);
+        let header = styled_header(&["♥", "↻", "Name", "No. Songs", "Duration"]);
         let left_col_widths = vec![
+            Constraint::Length(1),
             Constraint::Length(1),
             Constraint::Percentage(68),
             Constraint::Percentage(16),
@@ -152,11 +165,17 @@ pub fn render_search(
                 } else {
                     ""
                 };
+                let reposted = if reposted_playlist_uris.contains(&playlist.tracks_uri) {
+                    "↻"
+                } else {
+                    ""
+                };
                 let mut row = Row::new(vec![
                     truncate_with_ellipsis(liked, left_min_widths[0]),
-                    truncate_with_ellipsis(&playlist.title, left_min_widths[1]),
-                    truncate_with_ellipsis(&playlist.track_count, left_min_widths[2]),
-                    truncate_with_ellipsis(&playlist.duration, left_min_widths[3]),
+                    truncate_with_ellipsis(reposted, left_min_widths[1]),
+                    truncate_with_ellipsis(&playlist.title, left_min_widths[2]),
+                    truncate_with_ellipsis(&playlist.track_count.to_string(), left_min_widths[3]),
+                    truncate_with_ellipsis(&format_duration(playlist.duration_ms), left_min_widths[4]),
                 ]);
                 if i == selected_row {
                     row = row.style(Style::default().bg(Color::Gray).fg(Color::Black));
@@ -190,9 +209,9 @@ pub fn render_search(
             .map(|(i, track)| {
                 let mut row = Row::new(vec![
                     truncate_with_ellipsis(&track.title, track_min_widths[0]),
-                    truncate_with_ellipsis(&track.artists, track_min_widths[1]),
-                    truncate_with_ellipsis(&track.duration, track_min_widths[2]),
-                    truncate_with_ellipsis(&track.playback_count, track_min_widths[3]),
+                    truncate_with_ellipsis(track.artists(), track_min_widths[1]),
+                    truncate_with_ellipsis(&format_duration(track.duration_ms), track_min_widths[2]),
+                    truncate_with_ellipsis(&format_count(track.playback_count), track_min_widths[3]),
                 ]);
                 if !track.is_playable() {
                     row = row.style(Style::default().fg(Color::DarkGray));
@@ -222,8 +241,10 @@ pub fn render_search(
             .constraints([Constraint::Percentage(65), Constraint::Percentage(35)].as_ref())
             .split(table_area);
 
-        let header = styled_header(&["♥", "Title", "Artist(s)", "Year", "No. Songs", "Duration"]);
+        let header =
+            styled_header(&["♥", "↻", "Title", "Artist(s)", "Year", "No. Songs", "Duration"]);
         let left_col_widths = vec![
+            Constraint::Length(1),
             Constraint::Length(1),
             Constraint::Percentage(47),
             Constraint::Percentage(21),
@@ -242,13 +263,19 @@ pub fn render_search(
                 } else {
                     ""
                 };
+                let reposted = if reposted_playlist_uris.contains(&album.tracks_uri) {
+                    "↻"
+                } else {
+                    ""
+                };
                 let mut row = Row::new(vec![
                     truncate_with_ellipsis(liked, left_min_widths[0]),
-                    truncate_with_ellipsis(&album.title, left_min_widths[1]),
-                    truncate_with_ellipsis(&album.artists, left_min_widths[2]),
-                    truncate_with_ellipsis(&album.release_year, left_min_widths[3]),
-                    truncate_with_ellipsis(&album.track_count, left_min_widths[4]),
-                    truncate_with_ellipsis(&album.duration, left_min_widths[5]),
+                    truncate_with_ellipsis(reposted, left_min_widths[1]),
+                    truncate_with_ellipsis(&album.title, left_min_widths[2]),
+                    truncate_with_ellipsis(album.artists(), left_min_widths[3]),
+                    truncate_with_ellipsis(&format_year(album.release_year), left_min_widths[4]),
+                    truncate_with_ellipsis(&album.track_count.to_string(), left_min_widths[5]),
+                    truncate_with_ellipsis(&format_duration(album.duration_ms), left_min_widths[6]),
                 ]);
                 if i == selected_row {
                     row = row.style(Style::default().bg(Color::Gray).fg(Color::Black));
@@ -281,8 +308,8 @@ pub fn render_search(
             .map(|(i, track)| {
                 let mut row = Row::new(vec![
                     truncate_with_ellipsis(&track.title, track_min_widths[0]),
-                    truncate_with_ellipsis(&track.duration, track_min_widths[1]),
-                    truncate_with_ellipsis(&track.playback_count, track_min_widths[2]),
+                    truncate_with_ellipsis(&format_duration(track.duration_ms), track_min_widths[1]),
+                    truncate_with_ellipsis(&format_count(track.playback_count), track_min_widths[2]),
                 ]);
                 if !track.is_playable() {
                     row = row.style(Style::default().fg(Color::DarkGray));
@@ -364,8 +391,8 @@ pub fn render_search(
             .map(|(i, track)| {
                 let mut row = Row::new(vec![
                     truncate_with_ellipsis(&track.title, published_min_widths[0]),
-                    truncate_with_ellipsis(&track.duration, published_min_widths[1]),
-                    truncate_with_ellipsis(&track.playback_count, published_min_widths[2]),
+                    truncate_with_ellipsis(&format_duration(track.duration_ms), published_min_widths[1]),
+                    truncate_with_ellipsis(&format_count(track.playback_count), published_min_widths[2]),
                 ]);
                 if !track.is_playable() {
                     row = row.style(Style::default().fg(Color::DarkGray));
@@ -412,9 +439,9 @@ pub fn render_search(
             .map(|(i, track)| {
                 let mut row = Row::new(vec![
                     truncate_with_ellipsis(&track.title, likes_min_widths[0]),
-                    truncate_with_ellipsis(&track.artists, likes_min_widths[1]),
-                    truncate_with_ellipsis(&track.duration, likes_min_widths[2]),
-                    truncate_with_ellipsis(&track.playback_count, likes_min_widths[3]),
+                    truncate_with_ellipsis(track.artists(), likes_min_widths[1]),
+                    truncate_with_ellipsis(&format_duration(track.duration_ms), likes_min_widths[2]),
+                    truncate_with_ellipsis(&format_count(track.playback_count), likes_min_widths[3]),
                 ]);
                 if !track.is_playable() {
                     row = row.style(Style::default().fg(Color::DarkGray));
@@ -1,3 +1,4 @@
+use chrono::{DateTime, FixedOffset, Utc};
 use ratatui::{
     layout::Constraint,
     style::{Color, Modifier, Style},
@@ -64,3 +65,45 @@ pub fn truncate_with_ellipsis(s: &str, min_width: usize) -> String {
         s.to_string()
     }
 }
+
+pub fn format_duration(duration_ms: u64) -> String {
+    let duration_sec = duration_ms / 1000;
+    let hours = duration_sec / 3600;
+    let minutes = (duration_sec % 3600) / 60;
+    let seconds = duration_sec % 60;
+
+    if hours > 0 {
+        format!("{:02}:{:02}:{:02}", hours, minutes, seconds)
+    } else {
+        format!("{:02}:{:02}", minutes, seconds)
+    }
+}
+
+pub fn format_count(n: u64) -> String {
+    match n {
+        0..=999 => n.to_string(),
+        1_000..=999_999 => format!("{:.2}K", n as f64 / 1_000.0),
+        1_000_000..=999_999_999 => format!("{:.2}M", n as f64 / 1_000_000.0),
+        1_000_000_000..=999_999_999_999 => format!("{:.2}B", n as f64 / 1_000_000_000.0),
+        _ => format!("{:.2}T", n as f64 / 1_000_000_000_000.0),
+    }
+}
+
+pub fn format_year(year: Option<u32>) -> String {
+    year.map(|y| y.to_string()).unwrap_or_default()
+}
+
+/// Compact age like `5m`, `3h`, `2d`, `6w` or `1y`.
+pub fn format_age(created_at: Option<DateTime<FixedOffset>>) -> String {
+    let Some(created_at) = created_at else {
+        return String::new();
+    };
+    let minutes = (Utc::now() - created_at.with_timezone(&Utc)).num_minutes().max(0);
+    match minutes {
+        0..60 => format!("{}m", minutes),
+        60..1_440 => format!("{}h", minutes / 60),
+        1_440..10_080 => format!("{}d", minutes / 1_440),
+        10_080..525_600 => format!("{}w", minutes / 10_080),
+        _ => format!("{}y", minutes / 525_600),
+    }
+}